@@ -0,0 +1,20 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{EnumIter, EnumString};
+
+/// Parsed from the `slug` of the `category` table, e.g. `smart_home`
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, EnumIter, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum CategorySlug {
+    SmartHome,
+    Smartphone,
+    Headphones,
+    Watches,
+}
+
+impl fmt::Display for CategorySlug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
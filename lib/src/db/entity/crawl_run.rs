@@ -0,0 +1,85 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use strum_macros::Display;
+
+use crate::schema::crawl_run;
+
+#[derive(Display, Debug, Clone, Copy)]
+#[strum(serialize_all = "snake_case")]
+pub enum CrawlRunKind {
+    Category,
+    Page,
+    Details,
+}
+
+/// Unfinished runs stay `running`
+#[derive(Display, Debug, Clone, Copy)]
+#[strum(serialize_all = "snake_case")]
+pub enum CrawlRunStatus {
+    Completed,
+    Failed,
+}
+
+#[derive(Insertable)]
+#[table_name = "crawl_run"]
+pub struct NewCrawlRun {
+    pub source_id: i32,
+    pub category_id: Option<i32>,
+    pub kind: String,
+}
+
+#[derive(AsChangeset, Queryable, Default, Debug, Clone, Copy, PartialEq)]
+#[table_name = "crawl_run"]
+pub struct CrawlRunCounters {
+    pub pages: i32,
+    /// Offers seen for the first time
+    pub products_new: i32,
+    /// Offers with changed price or availability
+    pub products_updated: i32,
+    pub products_unchanged: i32,
+    /// Requests which failed
+    pub failures: i32,
+    /// Jobs sent to the queue, including the retries
+    pub postponed: i32,
+    /// Products skipped because of the missing html nodes
+    pub warnings: i32,
+    /// Items of the listing pages, including the skipped ones
+    pub items: i32,
+    pub missing_id: i32,
+    pub missing_title: i32,
+    pub missing_price: i32,
+    /// Items without both available and unavailable nodes
+    pub missing_availability: i32,
+    /// Details pages without description
+    pub missing_description: i32,
+    pub unknown_characteristics: i32,
+}
+
+#[derive(Queryable, Serialize, Debug)]
+pub struct CrawlRunReport {
+    pub id: i32,
+    /// `site_name` of the source
+    pub source: String,
+    /// `slug` of the category
+    pub category: Option<String>,
+    pub kind: String,
+    pub status: String,
+    pub pages: i32,
+    pub products_new: i32,
+    pub products_updated: i32,
+    pub products_unchanged: i32,
+    pub failures: i32,
+    pub postponed: i32,
+    pub warnings: i32,
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+    pub items: i32,
+    pub missing_id: i32,
+    pub missing_title: i32,
+    pub missing_price: i32,
+    pub missing_availability: i32,
+    pub missing_description: i32,
+    pub unknown_characteristics: i32,
+    /// Health metrics which crossed the threshold, comma separated
+    pub health_alert: Option<String>,
+}
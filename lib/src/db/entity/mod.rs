@@ -1,2 +1,7 @@
 pub mod exchange_rate;
-pub mod characteristic;
\ No newline at end of file
+pub mod category;
+pub mod characteristic;
+pub mod crawl_run;
+pub mod product_audit;
+pub mod product_redirect;
+pub mod unknown_characteristic;
@@ -0,0 +1,22 @@
+use strum_macros::Display;
+
+use crate::schema::product_audit;
+
+#[derive(Display, Debug, Clone, Copy)]
+#[strum(serialize_all = "snake_case")]
+pub enum ProductOperation {
+    Merge,
+    Split,
+}
+
+#[derive(Insertable)]
+#[table_name = "product_audit"]
+pub struct NewProductAudit<'a> {
+    pub operation: String,
+    /// Product that stayed after the merge or was created by the split
+    pub product_id: i32,
+    /// Product that was merged or which the offer was split off from
+    pub affected_product_id: i32,
+    pub source_product_id: Option<i32>,
+    pub actor: &'a str,
+}
@@ -0,0 +1,9 @@
+use crate::schema::product_redirect;
+
+#[derive(Insertable)]
+#[table_name = "product_redirect"]
+pub struct NewProductRedirect<'a> {
+    pub from_product_id: i32,
+    pub from_title: &'a str,
+    pub to_product_id: i32,
+}
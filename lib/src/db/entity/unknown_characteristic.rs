@@ -0,0 +1,37 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use strum_macros::Display;
+
+#[derive(Display, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+pub enum UnknownCharacteristicKind {
+    /// Title which none of the dictionaries of the crawler knows
+    Title,
+    /// Value which the parser of the known title refused
+    Value,
+}
+
+/// Occurrences of one unknown characteristic collected during a crawl run
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownCharacteristicSightings {
+    pub source_id: i32,
+    pub kind: UnknownCharacteristicKind,
+    pub title: String,
+    pub occurrences: i32,
+    pub external_ids: Vec<String>,
+    pub sample_values: Vec<String>,
+}
+
+#[derive(Queryable, Serialize, Debug)]
+pub struct UnknownCharacteristicReport {
+    pub id: i32,
+    /// `site_name` of the source
+    pub source: String,
+    pub kind: String,
+    pub title: String,
+    pub occurrences: i32,
+    pub external_ids: Vec<String>,
+    pub sample_values: Vec<String>,
+    pub first_seen_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+}
@@ -0,0 +1,143 @@
+use chrono::Utc;
+use diesel::{QueryDsl, RunQueryDsl};
+
+use crate::db;
+use crate::db::entity::crawl_run::{
+    CrawlRunCounters, CrawlRunKind, CrawlRunReport, CrawlRunStatus, NewCrawlRun,
+};
+use crate::diesel::prelude::*;
+use crate::schema::{category, crawl_run, source};
+
+pub fn start(new_run: &NewCrawlRun) -> i32 {
+    let connection = &db::establish_connection();
+
+    diesel::insert_into(crawl_run::table)
+        .values(new_run)
+        .returning(crawl_run::id)
+        .get_result(connection)
+        .expect("Error saving crawl run")
+}
+
+pub fn finish(run_id: i32, status: CrawlRunStatus, counters: &CrawlRunCounters) {
+    let connection = &db::establish_connection();
+    let now = Utc::now();
+
+    diesel::update(crawl_run::table.find(run_id))
+        .set((
+            counters,
+            crawl_run::status.eq(status.to_string()),
+            crawl_run::finished_at.eq(now.naive_utc()),
+        ))
+        .execute(connection)
+        .expect("Error finishing crawl run");
+}
+
+/// The latest runs first, optionally of one source only
+pub fn get_recent_runs(source_name: Option<&str>, limit: i64) -> Vec<CrawlRunReport> {
+    let connection = &db::establish_connection();
+
+    let mut query = crawl_run::table
+        .inner_join(source::table)
+        .left_join(category::table)
+        .select((
+            crawl_run::id,
+            source::site_name,
+            category::slug.nullable(),
+            crawl_run::kind,
+            crawl_run::status,
+            crawl_run::pages,
+            crawl_run::products_new,
+            crawl_run::products_updated,
+            crawl_run::products_unchanged,
+            crawl_run::failures,
+            crawl_run::postponed,
+            crawl_run::warnings,
+            crawl_run::started_at,
+            crawl_run::finished_at,
+            crawl_run::items,
+            crawl_run::missing_id,
+            crawl_run::missing_title,
+            crawl_run::missing_price,
+            crawl_run::missing_availability,
+            crawl_run::missing_description,
+            crawl_run::unknown_characteristics,
+            crawl_run::health_alert,
+        ))
+        .into_boxed();
+
+    if let Some(name) = source_name {
+        query = query.filter(source::site_name.eq(name));
+    }
+
+    query
+        .order(crawl_run::started_at.desc())
+        .limit(limit)
+        .load::<CrawlRunReport>(connection)
+        .expect("Error loading crawl runs")
+}
+
+/// Counters of the finished runs of the source and kind, the latest first
+pub fn get_finished_counters(
+    source_id: i32,
+    kind: CrawlRunKind,
+    offset: i64,
+    limit: i64,
+) -> Vec<CrawlRunCounters> {
+    let connection = &db::establish_connection();
+
+    crawl_run::table
+        .filter(crawl_run::source_id.eq(source_id))
+        .filter(crawl_run::kind.eq(kind.to_string()))
+        .filter(crawl_run::finished_at.is_not_null())
+        .order(crawl_run::finished_at.desc())
+        .offset(offset)
+        .limit(limit)
+        .select((
+            crawl_run::pages,
+            crawl_run::products_new,
+            crawl_run::products_updated,
+            crawl_run::products_unchanged,
+            crawl_run::failures,
+            crawl_run::postponed,
+            crawl_run::warnings,
+            crawl_run::items,
+            crawl_run::missing_id,
+            crawl_run::missing_title,
+            crawl_run::missing_price,
+            crawl_run::missing_availability,
+            crawl_run::missing_description,
+            crawl_run::unknown_characteristics,
+        ))
+        .load::<CrawlRunCounters>(connection)
+        .expect("Error loading crawl run counters")
+}
+
+/// Health alert of the latest finished run of the source and kind before the given one
+pub fn get_previous_health_alert(
+    source_id: i32,
+    kind: CrawlRunKind,
+    run_id: i32,
+) -> Option<String> {
+    let connection = &db::establish_connection();
+
+    crawl_run::table
+        .filter(crawl_run::source_id.eq(source_id))
+        .filter(crawl_run::kind.eq(kind.to_string()))
+        .filter(crawl_run::finished_at.is_not_null())
+        .filter(crawl_run::id.ne(run_id))
+        .order(crawl_run::finished_at.desc())
+        .select(crawl_run::health_alert)
+        .first::<Option<String>>(connection)
+        .optional()
+        .expect("Error loading crawl run health")
+        .flatten()
+}
+
+pub fn set_health_alert(run_id: i32, health_alert: Option<&str>) {
+    let connection = &db::establish_connection();
+
+    diesel::update(crawl_run::table.find(run_id))
+        .set(crawl_run::health_alert.eq(health_alert))
+        .execute(connection)
+        .expect("Error saving crawl run health");
+}
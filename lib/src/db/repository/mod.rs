@@ -1 +1,6 @@
+pub mod crawl_run;
 pub mod exchange_rate;
+pub mod price_range;
+pub mod product_admin;
+pub mod product_redirect;
+pub mod unknown_characteristic;
@@ -0,0 +1,71 @@
+use diesel::sql_types::Int4;
+use diesel::{sql_query, PgConnection, QueryResult, RunQueryDsl};
+
+/// Recalculates price ranges of the product and its variants from the enabled offers.
+/// Ranges without any enabled offer are left as they are.
+pub fn recompute_price_range(connection: &PgConnection, product_id: i32) -> QueryResult<()> {
+    sql_query(
+        "update product_variant
+        set lowest_price = offers.lowest_price, highest_price = offers.highest_price
+        from (
+            select variant_id, min(price) lowest_price, max(price) highest_price
+            from source_product
+            where product_id = $1 and enabled
+            group by variant_id
+        ) offers
+        where product_variant.id = offers.variant_id",
+    )
+    .bind::<Int4, _>(product_id)
+    .execute(connection)?;
+
+    sql_query(
+        "update product
+        set lowest_price = offers.lowest_price, highest_price = offers.highest_price
+        from (
+            select min(price) lowest_price, max(price) highest_price
+            from source_product
+            where product_id = $1 and enabled
+        ) offers
+        where product.id = $1 and offers.lowest_price is not null",
+    )
+    .bind::<Int4, _>(product_id)
+    .execute(connection)?;
+
+    Ok(())
+}
+
+/// Disables the product and its variants which have no enabled offers left.
+/// Products with live offers are not enabled here, they also need details for that.
+pub fn disable_without_live_offers(connection: &PgConnection, product_id: i32) -> QueryResult<()> {
+    disable_variants_without_live_offers(connection, product_id)?;
+
+    sql_query(
+        "update product
+        set enabled = false
+        where id = $1
+            and enabled
+            and not exists (select 1 from source_product where product_id = $1 and enabled)",
+    )
+    .bind::<Int4, _>(product_id)
+    .execute(connection)?;
+
+    Ok(())
+}
+
+/// Disables variants of the product which have no enabled offers left, e.g. the offer is sold out or has another color now
+pub fn disable_variants_without_live_offers(
+    connection: &PgConnection,
+    product_id: i32,
+) -> QueryResult<usize> {
+    sql_query(
+        "update product_variant
+        set enabled = false
+        where product_id = $1
+            and enabled
+            and not exists (
+                select 1 from source_product where variant_id = product_variant.id and enabled
+            )",
+    )
+    .bind::<Int4, _>(product_id)
+    .execute(connection)
+}
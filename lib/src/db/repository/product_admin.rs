@@ -0,0 +1,752 @@
+use std::fmt;
+
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use diesel::result::Error as DieselError;
+use diesel::sql_types::Int4;
+use diesel::{sql_query, Connection, PgConnection, QueryDsl, RunQueryDsl};
+
+use crate::db;
+use crate::db::entity::product_audit::{NewProductAudit, ProductOperation};
+use crate::db::entity::product_redirect::NewProductRedirect;
+use crate::db::repository::price_range::recompute_price_range;
+use crate::diesel::prelude::*;
+use crate::schema::{
+    product, product_audit, product_identifier, product_image, product_redirect, product_variant,
+    source_product, source_product_price_history,
+};
+
+#[derive(Debug)]
+pub enum ProductAdminError {
+    ProductNotFound(i32),
+    SameProduct(i32),
+    SourceProductNotFound(i32),
+    OnlyOffer(i32),
+    TitleIsTaken(String),
+    Database(DieselError),
+}
+
+impl fmt::Display for ProductAdminError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProductAdminError::ProductNotFound(id) => write!(f, "Product {} not found", id),
+            ProductAdminError::SameProduct(id) => {
+                write!(f, "Product {} can not be merged into itself", id)
+            }
+            ProductAdminError::SourceProductNotFound(id) => {
+                write!(f, "Source product {} not found", id)
+            }
+            ProductAdminError::OnlyOffer(id) => {
+                write!(f, "Source product {} is the only offer of its product", id)
+            }
+            ProductAdminError::TitleIsTaken(title) => {
+                write!(f, "Product with title \"{}\" already exists", title)
+            }
+            ProductAdminError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<DieselError> for ProductAdminError {
+    fn from(e: DieselError) -> Self {
+        ProductAdminError::Database(e)
+    }
+}
+
+/// Moves everything of the merged product into the target one and leaves a redirect from the merged id
+pub fn merge_products(
+    target_id: i32,
+    merged_id: i32,
+    actor: &str,
+) -> Result<(), ProductAdminError> {
+    if target_id == merged_id {
+        return Err(ProductAdminError::SameProduct(target_id));
+    }
+
+    let connection = &db::establish_connection();
+
+    merge(connection, target_id, merged_id, actor)
+}
+
+fn merge(
+    connection: &PgConnection,
+    target_id: i32,
+    merged_id: i32,
+    actor: &str,
+) -> Result<(), ProductAdminError> {
+    connection.transaction::<_, ProductAdminError, _>(|| {
+        // always lock in the same order, so concurrent merges can not deadlock
+        lock_product(connection, target_id.min(merged_id))?;
+        lock_product(connection, target_id.max(merged_id))?;
+
+        move_variants(connection, target_id, merged_id)?;
+        move_offers(connection, target_id, merged_id)?;
+        move_characteristics(connection, target_id, merged_id)?;
+        merge_details(connection, target_id, merged_id)?;
+
+        diesel::update(product_identifier::table)
+            .filter(product_identifier::product_id.eq(merged_id))
+            .set(product_identifier::product_id.eq(target_id))
+            .execute(connection)?;
+        // images are removed with the merged product otherwise, and their files are left in the store
+        diesel::update(product_image::table)
+            .filter(product_image::product_id.eq(merged_id))
+            .set(product_image::product_id.eq(target_id))
+            .execute(connection)?;
+
+        diesel::update(product_redirect::table)
+            .filter(product_redirect::to_product_id.eq(merged_id))
+            .set(product_redirect::to_product_id.eq(target_id))
+            .execute(connection)?;
+        // the title is kept, so the next crawl links offers with it to the target instead of creating it again
+        let merged_title = product::table
+            .find(merged_id)
+            .select(product::title)
+            .first::<String>(connection)?;
+        diesel::insert_into(product_redirect::table)
+            .values(&NewProductRedirect {
+                from_product_id: merged_id,
+                from_title: &merged_title,
+                to_product_id: target_id,
+            })
+            .execute(connection)?;
+
+        diesel::delete(product::table.find(merged_id)).execute(connection)?;
+        recompute_price_range(connection, target_id)?;
+
+        audit(
+            connection,
+            NewProductAudit {
+                operation: ProductOperation::Merge.to_string(),
+                product_id: target_id,
+                affected_product_id: merged_id,
+                source_product_id: None,
+                actor,
+            },
+        )
+    })
+}
+
+/// Moves the offer with its price history into a new product, returns id of the new product
+pub fn split_source_product(
+    source_product_id: i32,
+    title: &str,
+    actor: &str,
+) -> Result<i32, ProductAdminError> {
+    let connection = &db::establish_connection();
+
+    split(connection, source_product_id, title, actor)
+}
+
+fn split(
+    connection: &PgConnection,
+    source_product_id: i32,
+    title: &str,
+    actor: &str,
+) -> Result<i32, ProductAdminError> {
+    connection.transaction::<_, ProductAdminError, _>(|| {
+        let (product_id, variant_id, source_id, external_id, price, enabled) =
+            source_product::table
+                .find(source_product_id)
+                .select((
+                    source_product::product_id,
+                    source_product::variant_id,
+                    source_product::source_id,
+                    source_product::external_id,
+                    source_product::price,
+                    source_product::enabled,
+                ))
+                .for_update()
+                .first::<(i32, i32, i32, String, BigDecimal, bool)>(connection)
+                .optional()?
+                .ok_or(ProductAdminError::SourceProductNotFound(source_product_id))?;
+        lock_product(connection, product_id)?;
+
+        let offers_count: i64 = source_product::table
+            .filter(source_product::product_id.eq(product_id))
+            .count()
+            .get_result(connection)?;
+        if offers_count < 2 {
+            return Err(ProductAdminError::OnlyOffer(source_product_id));
+        }
+
+        let title_count: i64 = product::table
+            .filter(product::title.eq(title))
+            .count()
+            .get_result(connection)?;
+        if title_count > 0 {
+            return Err(ProductAdminError::TitleIsTaken(title.to_string()));
+        }
+
+        let now = Utc::now().naive_utc();
+        let (description, images, category) = product::table
+            .find(product_id)
+            .select((product::description, product::images, product::category))
+            .first::<(Option<String>, Option<Vec<String>>, i32)>(connection)?;
+        let new_product_id: i32 = diesel::insert_into(product::table)
+            .values((
+                product::title.eq(title),
+                product::description.eq(description),
+                product::lowest_price.eq(&price),
+                product::highest_price.eq(&price),
+                product::images.eq(images),
+                product::category.eq(category),
+                product::enabled.eq(enabled),
+                product::updated_at.eq(now),
+            ))
+            .returning(product::id)
+            .get_result(connection)?;
+
+        sql_query(
+            "insert into product_characteristic (product_id, characteristic_id, value_id)
+            select $1, characteristic_id, value_id from product_characteristic where product_id = $2",
+        )
+        .bind::<Int4, _>(new_product_id)
+        .bind::<Int4, _>(product_id)
+        .execute(connection)?;
+        copy_source_images(connection, product_id, new_product_id, source_id)?;
+
+        let (color, built_in_memory_gb, ram_gb) = product_variant::table
+            .find(variant_id)
+            .select((
+                product_variant::color,
+                product_variant::built_in_memory_gb,
+                product_variant::ram_gb,
+            ))
+            .first::<(Option<String>, Option<i32>, Option<i32>)>(connection)?;
+        let new_variant_id: i32 = diesel::insert_into(product_variant::table)
+            .values((
+                product_variant::product_id.eq(new_product_id),
+                product_variant::color.eq(color),
+                product_variant::built_in_memory_gb.eq(built_in_memory_gb),
+                product_variant::ram_gb.eq(ram_gb),
+                product_variant::lowest_price.eq(&price),
+                product_variant::highest_price.eq(&price),
+                product_variant::enabled.eq(enabled),
+                product_variant::updated_at.eq(now),
+            ))
+            .returning(product_variant::id)
+            .get_result(connection)?;
+
+        diesel::update(source_product::table.find(source_product_id))
+            .set((
+                source_product::product_id.eq(new_product_id),
+                source_product::variant_id.eq(new_variant_id),
+            ))
+            .execute(connection)?;
+        diesel::update(source_product_price_history::table)
+            .filter(source_product_price_history::product_id.eq(product_id))
+            .filter(source_product_price_history::source_id.eq(source_id))
+            .filter(source_product_price_history::external_id.eq(&external_id))
+            .set(source_product_price_history::product_id.eq(new_product_id))
+            .execute(connection)?;
+
+        recompute_price_range(connection, product_id)?;
+
+        audit(
+            connection,
+            NewProductAudit {
+                operation: ProductOperation::Split.to_string(),
+                product_id: new_product_id,
+                affected_product_id: product_id,
+                source_product_id: Some(source_product_id),
+                actor,
+            },
+        )?;
+
+        Ok(new_product_id)
+    })
+}
+
+fn lock_product(connection: &PgConnection, product_id: i32) -> Result<(), ProductAdminError> {
+    product::table
+        .find(product_id)
+        .select(product::id)
+        .for_update()
+        .first::<i32>(connection)
+        .optional()?
+        .map(|_| ())
+        .ok_or(ProductAdminError::ProductNotFound(product_id))
+}
+
+/// Offers of the merged variants which the target already has are moved to the target variants,
+/// the rest of the variants is moved as is
+fn move_variants(connection: &PgConnection, target_id: i32, merged_id: i32) -> QueryResult<()> {
+    sql_query(
+        "update source_product
+        set variant_id = target_variant.id
+        from product_variant merged_variant, product_variant target_variant
+        where source_product.variant_id = merged_variant.id
+            and merged_variant.product_id = $2
+            and target_variant.product_id = $1
+            and coalesce(target_variant.color, '') = coalesce(merged_variant.color, '')
+            and coalesce(target_variant.built_in_memory_gb, 0) = coalesce(merged_variant.built_in_memory_gb, 0)
+            and coalesce(target_variant.ram_gb, 0) = coalesce(merged_variant.ram_gb, 0)",
+    )
+    .bind::<Int4, _>(target_id)
+    .bind::<Int4, _>(merged_id)
+    .execute(connection)?;
+
+    sql_query(
+        "update product_variant target_variant
+        set enabled = target_variant.enabled or merged_variant.enabled
+        from product_variant merged_variant
+        where merged_variant.product_id = $2
+            and target_variant.product_id = $1
+            and coalesce(target_variant.color, '') = coalesce(merged_variant.color, '')
+            and coalesce(target_variant.built_in_memory_gb, 0) = coalesce(merged_variant.built_in_memory_gb, 0)
+            and coalesce(target_variant.ram_gb, 0) = coalesce(merged_variant.ram_gb, 0)",
+    )
+    .bind::<Int4, _>(target_id)
+    .bind::<Int4, _>(merged_id)
+    .execute(connection)?;
+
+    sql_query(
+        "delete from product_variant merged_variant
+        using product_variant target_variant
+        where merged_variant.product_id = $2
+            and target_variant.product_id = $1
+            and coalesce(target_variant.color, '') = coalesce(merged_variant.color, '')
+            and coalesce(target_variant.built_in_memory_gb, 0) = coalesce(merged_variant.built_in_memory_gb, 0)
+            and coalesce(target_variant.ram_gb, 0) = coalesce(merged_variant.ram_gb, 0)",
+    )
+    .bind::<Int4, _>(target_id)
+    .bind::<Int4, _>(merged_id)
+    .execute(connection)?;
+
+    diesel::update(product_variant::table)
+        .filter(product_variant::product_id.eq(merged_id))
+        .set(product_variant::product_id.eq(target_id))
+        .execute(connection)?;
+
+    Ok(())
+}
+
+/// The same offer linked to both products is kept only once
+fn move_offers(connection: &PgConnection, target_id: i32, merged_id: i32) -> QueryResult<()> {
+    sql_query(
+        "delete from source_product merged_offer
+        using source_product target_offer
+        where merged_offer.product_id = $2
+            and target_offer.product_id = $1
+            and merged_offer.source_id = target_offer.source_id
+            and merged_offer.external_id = target_offer.external_id",
+    )
+    .bind::<Int4, _>(target_id)
+    .bind::<Int4, _>(merged_id)
+    .execute(connection)?;
+
+    diesel::update(source_product::table)
+        .filter(source_product::product_id.eq(merged_id))
+        .set(source_product::product_id.eq(target_id))
+        .execute(connection)?;
+    diesel::update(source_product_price_history::table)
+        .filter(source_product_price_history::product_id.eq(merged_id))
+        .set(source_product_price_history::product_id.eq(target_id))
+        .execute(connection)?;
+
+    Ok(())
+}
+
+/// Values of multi selectors are united, the rest of the characteristics have one value and the target one wins
+fn move_characteristics(
+    connection: &PgConnection,
+    target_id: i32,
+    merged_id: i32,
+) -> QueryResult<()> {
+    sql_query(
+        "insert into product_characteristic (product_id, characteristic_id, value_id)
+        select $1, merged.characteristic_id, merged.value_id
+        from product_characteristic merged
+                 inner join characteristic on characteristic.id = merged.characteristic_id
+        where merged.product_id = $2
+            and (
+                characteristic.visualisation_type = 'multi_selector'
+                or not exists(
+                    select 1
+                    from product_characteristic target
+                    where target.product_id = $1 and target.characteristic_id = merged.characteristic_id
+                )
+            )
+        on conflict do nothing",
+    )
+    .bind::<Int4, _>(target_id)
+    .bind::<Int4, _>(merged_id)
+    .execute(connection)?;
+
+    Ok(())
+}
+
+/// Description of the target wins, images are united, product is enabled if any of them is
+fn merge_details(connection: &PgConnection, target_id: i32, merged_id: i32) -> QueryResult<()> {
+    sql_query(
+        "update product target
+        set description = coalesce(target.description, merged.description),
+            images = case
+                when target.images is null and merged.images is null then null
+                else coalesce(target.images, '{}') || array(
+                    select image
+                    from unnest(coalesce(merged.images, '{}')) image
+                    where image <> all(coalesce(target.images, '{}'))
+                )
+            end,
+            enabled = target.enabled or merged.enabled
+        from product merged
+        where target.id = $1 and merged.id = $2",
+    )
+    .bind::<Int4, _>(target_id)
+    .bind::<Int4, _>(merged_id)
+    .execute(connection)?;
+
+    Ok(())
+}
+
+/// Images parsed from the source are copied with their stored variants, the files are shared by both products
+fn copy_source_images(
+    connection: &PgConnection,
+    product_id: i32,
+    new_product_id: i32,
+    source_id: i32,
+) -> QueryResult<()> {
+    let image_ids = product_image::table
+        .filter(product_image::product_id.eq(product_id))
+        .filter(product_image::source_id.eq(source_id))
+        .select(product_image::id)
+        .load::<i32>(connection)?;
+
+    for image_id in image_ids {
+        sql_query(
+            "with copied as (
+                insert into product_image (product_id, source_id, original_url, hash, width, height, created_at)
+                select $1, source_id, original_url, hash, width, height, created_at
+                from product_image
+                where id = $2
+                returning id
+            )
+            insert into product_image_variant (image_id, size, format, file_path, width, height)
+            select copied.id, variant.size, variant.format, variant.file_path, variant.width, variant.height
+            from copied, product_image_variant variant
+            where variant.image_id = $2",
+        )
+        .bind::<Int4, _>(new_product_id)
+        .bind::<Int4, _>(image_id)
+        .execute(connection)?;
+    }
+
+    Ok(())
+}
+
+fn audit(connection: &PgConnection, record: NewProductAudit) -> Result<(), ProductAdminError> {
+    diesel::insert_into(product_audit::table)
+        .values(&record)
+        .execute(connection)?;
+
+    Ok(())
+}
+
+/// Run against the database from `DATABASE_URL` with migrations applied: `cargo test -p lib -- --ignored`
+#[cfg(test)]
+mod tests {
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use diesel::sql_types::{Int2, Int4, Varchar};
+    use diesel::{sql_query, Connection, PgConnection, QueryDsl, RunQueryDsl};
+
+    use crate::db;
+    use crate::db::repository::product_admin::{merge, split};
+    use crate::diesel::prelude::*;
+    use crate::schema::{
+        category, product, product_image, product_image_variant, product_redirect, product_variant,
+        source, source_product,
+    };
+
+    static SINGLE_CHARACTERISTIC_ID: i16 = 32001;
+    static MULTI_CHARACTERISTIC_ID: i16 = 32002;
+
+    #[derive(QueryableByName, Debug, PartialEq)]
+    struct CharacteristicValue {
+        #[sql_type = "Int2"]
+        characteristic_id: i16,
+        #[sql_type = "Int4"]
+        value_id: i32,
+    }
+
+    fn create_characteristic(connection: &PgConnection, id: i16, visualisation_type: &str) {
+        sql_query(format!(
+            "insert into characteristic (id, slug, enabled, visualisation_type, value_type)
+            values ($1, $2, true, '{}', 'enum')",
+            visualisation_type
+        ))
+        .bind::<Int2, _>(id)
+        .bind::<Varchar, _>(format!("merge_test_{}", id))
+        .execute(connection)
+        .unwrap();
+    }
+
+    fn create_product(connection: &PgConnection, title: &str, values: &[(i16, i32)]) -> i32 {
+        let category_id = category::table
+            .select(category::id)
+            .first::<i32>(connection)
+            .unwrap();
+        let product_id = diesel::insert_into(product::table)
+            .values((
+                product::title.eq(title),
+                product::lowest_price.eq(BigDecimal::from(1)),
+                product::highest_price.eq(BigDecimal::from(1)),
+                product::category.eq(category_id),
+                product::enabled.eq(true),
+                product::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .returning(product::id)
+            .get_result::<i32>(connection)
+            .unwrap();
+
+        for &(characteristic_id, value_id) in values {
+            sql_query(
+                "insert into product_characteristic (product_id, characteristic_id, value_id)
+                values ($1, $2, $3)",
+            )
+            .bind::<Int4, _>(product_id)
+            .bind::<Int2, _>(characteristic_id)
+            .bind::<Int4, _>(value_id)
+            .execute(connection)
+            .unwrap();
+        }
+
+        product_id
+    }
+
+    fn create_offer(connection: &PgConnection, product_id: i32, external_id: &str) -> i32 {
+        let now = Utc::now().naive_utc();
+        let source_id = source::table
+            .select(source::id)
+            .first::<i32>(connection)
+            .unwrap();
+        let variant_id = diesel::insert_into(product_variant::table)
+            .values((
+                product_variant::product_id.eq(product_id),
+                product_variant::lowest_price.eq(BigDecimal::from(1)),
+                product_variant::highest_price.eq(BigDecimal::from(1)),
+                product_variant::enabled.eq(true),
+                product_variant::updated_at.eq(now),
+            ))
+            .returning(product_variant::id)
+            .get_result::<i32>(connection)
+            .unwrap();
+
+        diesel::insert_into(source_product::table)
+            .values((
+                source_product::source_id.eq(source_id),
+                source_product::product_id.eq(product_id),
+                source_product::external_id.eq(external_id),
+                source_product::original_price.eq(BigDecimal::from(1)),
+                source_product::price.eq(BigDecimal::from(1)),
+                source_product::enabled.eq(true),
+                source_product::updated_at.eq(now),
+                source_product::variant_id.eq(variant_id),
+            ))
+            .returning(source_product::id)
+            .get_result::<i32>(connection)
+            .unwrap()
+    }
+
+    fn create_image(connection: &PgConnection, product_id: i32) -> i32 {
+        let source_id = source::table
+            .select(source::id)
+            .first::<i32>(connection)
+            .unwrap();
+        let image_id = diesel::insert_into(product_image::table)
+            .values((
+                product_image::product_id.eq(product_id),
+                product_image::source_id.eq(source_id),
+                product_image::original_url.eq("https://mi-shop.com/upload/phone.png"),
+                product_image::hash.eq(1_i64),
+                product_image::width.eq(400),
+                product_image::height.eq(300),
+            ))
+            .returning(product_image::id)
+            .get_result::<i32>(connection)
+            .unwrap();
+        diesel::insert_into(product_image_variant::table)
+            .values((
+                product_image_variant::image_id.eq(image_id),
+                product_image_variant::size.eq("large"),
+                product_image_variant::format.eq("jpg"),
+                product_image_variant::file_path.eq("product_images/mi_shop_com/phone/large.jpg"),
+                product_image_variant::width.eq(400),
+                product_image_variant::height.eq(300),
+            ))
+            .execute(connection)
+            .unwrap();
+
+        image_id
+    }
+
+    /// File paths of the stored variants of the product images
+    fn get_image_files(connection: &PgConnection, product_id: i32) -> Vec<String> {
+        product_image_variant::table
+            .inner_join(product_image::table)
+            .filter(product_image::product_id.eq(product_id))
+            .select(product_image_variant::file_path)
+            .load(connection)
+            .unwrap()
+    }
+
+    fn get_values(connection: &PgConnection, product_id: i32) -> Vec<CharacteristicValue> {
+        sql_query(
+            "select characteristic_id, value_id from product_characteristic
+            where product_id = $1
+            order by characteristic_id, value_id",
+        )
+        .bind::<Int4, _>(product_id)
+        .load(connection)
+        .unwrap()
+    }
+
+    fn value(characteristic_id: i16, value_id: i32) -> CharacteristicValue {
+        CharacteristicValue {
+            characteristic_id,
+            value_id,
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn it_keeps_target_single_values_and_unites_multi_values() {
+        let connection = &db::establish_connection();
+
+        connection.test_transaction::<_, diesel::result::Error, _>(|| {
+            create_characteristic(connection, SINGLE_CHARACTERISTIC_ID, "single_selector");
+            create_characteristic(connection, MULTI_CHARACTERISTIC_ID, "multi_selector");
+            let target_id = create_product(
+                connection,
+                "Merge test target",
+                &[(SINGLE_CHARACTERISTIC_ID, 1), (MULTI_CHARACTERISTIC_ID, 1)],
+            );
+            let merged_id = create_product(
+                connection,
+                "Merge test merged",
+                &[(SINGLE_CHARACTERISTIC_ID, 2), (MULTI_CHARACTERISTIC_ID, 2)],
+            );
+
+            merge(connection, target_id, merged_id, "test").unwrap();
+
+            assert_eq!(
+                get_values(connection, target_id),
+                vec![
+                    value(SINGLE_CHARACTERISTIC_ID, 1),
+                    value(MULTI_CHARACTERISTIC_ID, 1),
+                    value(MULTI_CHARACTERISTIC_ID, 2),
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[ignore]
+    fn it_takes_values_missing_in_target() {
+        let connection = &db::establish_connection();
+
+        connection.test_transaction::<_, diesel::result::Error, _>(|| {
+            create_characteristic(connection, SINGLE_CHARACTERISTIC_ID, "single_selector");
+            let target_id = create_product(connection, "Merge test target", &[]);
+            let merged_id = create_product(
+                connection,
+                "Merge test merged",
+                &[(SINGLE_CHARACTERISTIC_ID, 2)],
+            );
+
+            merge(connection, target_id, merged_id, "test").unwrap();
+
+            assert_eq!(
+                get_values(connection, target_id),
+                vec![value(SINGLE_CHARACTERISTIC_ID, 2)]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[ignore]
+    fn it_redirects_merged_title() {
+        let connection = &db::establish_connection();
+
+        connection.test_transaction::<_, diesel::result::Error, _>(|| {
+            let target_id = create_product(connection, "Merge test target", &[]);
+            let merged_id = create_product(connection, "Merge test merged", &[]);
+
+            merge(connection, target_id, merged_id, "test").unwrap();
+
+            let redirect: (i32, String, i32) = product_redirect::table
+                .select((
+                    product_redirect::from_product_id,
+                    product_redirect::from_title,
+                    product_redirect::to_product_id,
+                ))
+                .filter(product_redirect::from_product_id.eq(merged_id))
+                .first(connection)?;
+            assert_eq!(
+                redirect,
+                (merged_id, "Merge test merged".to_string(), target_id)
+            );
+            let merged_count: i64 = product::table
+                .find(merged_id)
+                .count()
+                .get_result(connection)?;
+            assert_eq!(merged_count, 0);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[ignore]
+    fn it_moves_images_of_merged_product() {
+        let connection = &db::establish_connection();
+
+        connection.test_transaction::<_, diesel::result::Error, _>(|| {
+            let target_id = create_product(connection, "Merge test target", &[]);
+            let merged_id = create_product(connection, "Merge test merged", &[]);
+            create_image(connection, merged_id);
+
+            merge(connection, target_id, merged_id, "test").unwrap();
+
+            assert_eq!(
+                get_image_files(connection, target_id),
+                vec!["product_images/mi_shop_com/phone/large.jpg".to_string()]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[ignore]
+    fn it_copies_images_of_split_offer_source() {
+        let connection = &db::establish_connection();
+
+        connection.test_transaction::<_, diesel::result::Error, _>(|| {
+            let product_id = create_product(connection, "Split test product", &[]);
+            let split_offer_id = create_offer(connection, product_id, "/split-test/1");
+            create_offer(connection, product_id, "/split-test/2");
+            create_image(connection, product_id);
+
+            let new_product_id =
+                split(connection, split_offer_id, "Split test new product", "test").unwrap();
+
+            assert_eq!(
+                get_image_files(connection, new_product_id),
+                vec!["product_images/mi_shop_com/phone/large.jpg".to_string()]
+            );
+            assert_eq!(
+                get_image_files(connection, product_id),
+                vec!["product_images/mi_shop_com/phone/large.jpg".to_string()]
+            );
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,31 @@
+use diesel::{QueryDsl, RunQueryDsl};
+
+use crate::db;
+use crate::diesel::prelude::*;
+use crate::schema::product_redirect;
+
+/// Id of the product which the merged product was redirected to
+pub fn get_redirect_target(product_id: i32) -> Option<i32> {
+    let connection = &db::establish_connection();
+
+    product_redirect::table
+        .filter(product_redirect::from_product_id.eq(product_id))
+        .select(product_redirect::to_product_id)
+        .first::<i32>(connection)
+        .optional()
+        .expect("Error loading product redirect")
+}
+
+/// Id of the product which the merged product with this title was redirected to,
+/// so the next crawl links offers to it instead of creating the merged product again
+pub fn get_redirect_target_by_title(title: &str) -> Option<i32> {
+    let connection = &db::establish_connection();
+
+    product_redirect::table
+        .filter(product_redirect::from_title.eq(title))
+        .order(product_redirect::id.desc())
+        .select(product_redirect::to_product_id)
+        .first::<i32>(connection)
+        .optional()
+        .expect("Error loading product redirect")
+}
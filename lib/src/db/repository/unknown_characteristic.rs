@@ -0,0 +1,171 @@
+use diesel::sql_types::{Array, Int4, Varchar};
+use diesel::{sql_query, PgConnection, QueryDsl, QueryResult, RunQueryDsl};
+
+use crate::db;
+use crate::db::entity::unknown_characteristic::{
+    UnknownCharacteristicReport, UnknownCharacteristicSightings,
+};
+use crate::diesel::prelude::*;
+use crate::schema::{source, unknown_characteristic};
+
+/// Amount of external ids and values kept as examples of the unknown characteristic
+pub const KEPT_EXAMPLES: usize = 5;
+
+/// Adds the occurrences to the known ones, the new examples go first
+pub fn save_sightings(sightings: &UnknownCharacteristicSightings) {
+    let connection = &db::establish_connection();
+
+    upsert(connection, sightings).expect("Error saving unknown characteristic");
+}
+
+/// The most frequent unknown characteristics first, optionally of one source only
+pub fn get_unknown_characteristics(
+    source_name: Option<&str>,
+    limit: i64,
+) -> Vec<UnknownCharacteristicReport> {
+    let connection = &db::establish_connection();
+
+    let mut query = unknown_characteristic::table
+        .inner_join(source::table)
+        .select((
+            unknown_characteristic::id,
+            source::site_name,
+            unknown_characteristic::kind,
+            unknown_characteristic::title,
+            unknown_characteristic::occurrences,
+            unknown_characteristic::external_ids,
+            unknown_characteristic::sample_values,
+            unknown_characteristic::first_seen_at,
+            unknown_characteristic::last_seen_at,
+        ))
+        .into_boxed();
+
+    if let Some(name) = source_name {
+        query = query.filter(source::site_name.eq(name));
+    }
+
+    query
+        .order((
+            unknown_characteristic::occurrences.desc(),
+            unknown_characteristic::last_seen_at.desc(),
+        ))
+        .limit(limit)
+        .load::<UnknownCharacteristicReport>(connection)
+        .expect("Error loading unknown characteristics")
+}
+
+fn upsert(
+    connection: &PgConnection,
+    sightings: &UnknownCharacteristicSightings,
+) -> QueryResult<usize> {
+    sql_query(format!(
+        "insert into unknown_characteristic (source_id, kind, title, occurrences, external_ids, sample_values)
+        values ($1, $2, $3, $4, $5, $6)
+        on conflict (source_id, kind, title) do update set
+            occurrences = unknown_characteristic.occurrences + excluded.occurrences,
+            external_ids = {external_ids},
+            sample_values = {sample_values},
+            last_seen_at = now()",
+        external_ids = latest_examples("external_ids"),
+        sample_values = latest_examples("sample_values"),
+    ))
+    .bind::<Int4, _>(sightings.source_id)
+    .bind::<Varchar, _>(sightings.kind.to_string())
+    .bind::<Varchar, _>(&sightings.title)
+    .bind::<Int4, _>(sightings.occurrences)
+    .bind::<Array<Varchar>, _>(&sightings.external_ids)
+    .bind::<Array<Varchar>, _>(&sightings.sample_values)
+    .execute(connection)
+}
+
+/// Distinct examples of both arrays, the ones of the new row first
+fn latest_examples(column: &str) -> String {
+    format!(
+        "array(
+            select example
+            from unnest(excluded.{column} || unknown_characteristic.{column}) with ordinality as t(example, position)
+            group by example
+            order by min(position)
+            limit {limit}
+        )",
+        column = column,
+        limit = KEPT_EXAMPLES,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{Connection, QueryDsl, RunQueryDsl};
+
+    use crate::db;
+    use crate::db::entity::unknown_characteristic::{
+        UnknownCharacteristicKind, UnknownCharacteristicSightings,
+    };
+    use crate::db::repository::unknown_characteristic::upsert;
+    use crate::diesel::prelude::*;
+    use crate::schema::{source, unknown_characteristic};
+
+    fn get_sightings(
+        source_id: i32,
+        occurrences: i32,
+        external_ids: &[&str],
+        sample_values: &[&str],
+    ) -> UnknownCharacteristicSightings {
+        UnknownCharacteristicSightings {
+            source_id,
+            kind: UnknownCharacteristicKind::Value,
+            title: String::from("Sightings test title"),
+            occurrences,
+            external_ids: external_ids.iter().map(|id| id.to_string()).collect(),
+            sample_values: sample_values
+                .iter()
+                .map(|value| value.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn it_sums_occurrences_and_keeps_latest_examples() {
+        let connection = &db::establish_connection();
+
+        connection.test_transaction::<_, diesel::result::Error, _>(|| {
+            let source_id = source::table.select(source::id).first::<i32>(connection)?;
+
+            upsert(
+                connection,
+                &get_sightings(source_id, 2, &["1", "2"], &["a"]),
+            )?;
+            upsert(
+                connection,
+                &get_sightings(source_id, 3, &["3", "4", "5", "6"], &["b", "a"]),
+            )?;
+
+            let saved: (i32, Vec<String>, Vec<String>) = unknown_characteristic::table
+                .select((
+                    unknown_characteristic::occurrences,
+                    unknown_characteristic::external_ids,
+                    unknown_characteristic::sample_values,
+                ))
+                .filter(unknown_characteristic::source_id.eq(source_id))
+                .filter(unknown_characteristic::title.eq("Sightings test title"))
+                .first(connection)?;
+            assert_eq!(
+                saved,
+                (
+                    5,
+                    vec![
+                        String::from("3"),
+                        String::from("4"),
+                        String::from("5"),
+                        String::from("6"),
+                        String::from("1"),
+                    ],
+                    vec![String::from("b"), String::from("a")],
+                )
+            );
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt;
+
+use strum_macros::EnumIter;
+
+/// Yes/no characteristics, stored as `1`/`0` so "No" of the source differs from the unknown value
+#[derive(Debug, EnumIter, Copy, Clone)]
+pub enum BoolCharacteristic {
+    NFC(bool),
+    FastCharging(bool),
+    InfraredPort(bool),
+    WirelessCharger(bool),
+    Autofocus(bool),
+}
+
+impl fmt::Display for BoolCharacteristic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl BoolCharacteristic {
+    pub fn name(&self) -> String {
+        let name = self.to_string();
+
+        name[0..name.find('(').unwrap()].to_string()
+    }
+
+    pub fn from_name(name: &str, value: bool) -> Option<BoolCharacteristic> {
+        use BoolCharacteristic::*;
+
+        let constructor = match name {
+            "NFC" => NFC,
+            "FastCharging" => FastCharging,
+            "InfraredPort" => InfraredPort,
+            "WirelessCharger" => WirelessCharger,
+            "Autofocus" => Autofocus,
+            _ => return None,
+        };
+
+        Some(constructor(value))
+    }
+
+    pub fn value(&self) -> bool {
+        use BoolCharacteristic::*;
+
+        match self {
+            NFC(v) | FastCharging(v) | InfraredPort(v) | WirelessCharger(v) | Autofocus(v) => *v,
+        }
+    }
+}
@@ -15,7 +15,6 @@ pub enum EnumCharacteristic {
     SatelliteNavigation(SatelliteNavigation),
     WifiStandard(WifiStandard),
     AudioJack(AudioJack),
-    TechnologySupport(Technology),
     ProducingCountry(Country),
     MemoryCardSlot(MemoryCardSlot),
     SupportedMediaFormat(MediaFormat),
@@ -54,7 +53,6 @@ impl EnumCharacteristic {
             SatelliteNavigation(e) => format!("{:?}", e),
             WifiStandard(e) => format!("{:?}", e),
             AudioJack(e) => format!("{:?}", e),
-            TechnologySupport(e) => format!("{:?}", e),
             ProducingCountry(e) => format!("{:?}", e),
             MemoryCardSlot(e) => format!("{:?}", e),
             SupportedMediaFormat(e) => format!("{:?}", e),
@@ -78,7 +76,6 @@ impl EnumCharacteristic {
             }
             "WifiStandard" => EnumCharacteristic::WifiStandard(WifiStandard::_4),
             "AudioJack" => EnumCharacteristic::AudioJack(AudioJack::_3_5mm),
-            "TechnologySupport" => EnumCharacteristic::TechnologySupport(Technology::Autofocus),
             "ProducingCountry" => EnumCharacteristic::ProducingCountry(Country::China),
             "MemoryCardSlot" => EnumCharacteristic::MemoryCardSlot(MemoryCardSlot::Hybrid),
             "SupportedMediaFormat" => EnumCharacteristic::SupportedMediaFormat(MediaFormat::_3GI),
@@ -160,15 +157,6 @@ pub enum AudioJack {
     USBTypeC,
 }
 
-#[derive(Debug, EnumVariantNames)]
-pub enum Technology {
-    NFC,
-    FastCharging,
-    InfraredPort,
-    WirelessCharger,
-    Autofocus,
-}
-
 #[derive(Debug, EnumVariantNames)]
 pub enum Country {
     China,
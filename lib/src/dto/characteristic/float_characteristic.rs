@@ -31,6 +31,26 @@ impl FloatCharacteristic {
         name[0..name.find('(').unwrap()].to_string()
     }
 
+    pub fn from_name(name: &str, value: f32) -> Option<FloatCharacteristic> {
+        use FloatCharacteristic::*;
+
+        let constructor = match name {
+            "Width_mm" => Width_mm,
+            "Height_mm" => Height_mm,
+            "Thickness_mm" => Thickness_mm,
+            "ScreenDiagonal" => ScreenDiagonal,
+            "BluetoothVersion" => BluetoothVersion,
+            "CPUFrequency_Ghz" => CPUFrequency_Ghz,
+            "Weight_gr" => Weight_gr,
+            "MIUIVersion" => MIUIVersion,
+            "AndroidVersion" => AndroidVersion,
+            "Aperture" => Aperture,
+            _ => return None,
+        };
+
+        Some(constructor(value))
+    }
+
     pub fn value(&self) -> f32 {
         use FloatCharacteristic::*;
 
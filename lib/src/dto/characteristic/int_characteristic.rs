@@ -38,6 +38,33 @@ impl IntCharacteristic {
         name[0..name.find('(').unwrap()].to_string()
     }
 
+    pub fn from_name(name: &str, value: i32) -> Option<IntCharacteristic> {
+        use IntCharacteristic::*;
+
+        let constructor = match name {
+            "BatteryCapacity_mA_h" => BatteryCapacity_mA_h,
+            "NumberOfProcessorCores" => NumberOfProcessorCores,
+            "BuiltInMemory_GB" => BuiltInMemory_GB,
+            "Ram_GB" => Ram_GB,
+            "FrontCamera_MP" => FrontCamera_MP,
+            "VideoResolution_Pix" => VideoResolution_Pix,
+            "AmountOfSimCards" => AmountOfSimCards,
+            "PPI" => PPI,
+            "Fps" => Fps,
+            "Brightness_cd_m2" => Brightness_cd_m2,
+            "UpdateFrequency_Hz" => UpdateFrequency_Hz,
+            "Camera_mp" => Camera_mp,
+            "LTEDiapason" => LTEDiapason,
+            "GSMDiapason" => GSMDiapason,
+            "UMTSDiapason" => UMTSDiapason,
+            "Warranty_month" => Warranty_month,
+            "MaxMemoryCardSize_GB" => MaxMemoryCardSize_GB,
+            _ => return None,
+        };
+
+        Some(constructor(value))
+    }
+
     pub fn value(&self) -> i32 {
         use IntCharacteristic::*;
 
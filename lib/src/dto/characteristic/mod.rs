@@ -1,8 +1,10 @@
+use crate::dto::characteristic::bool_characteristic::BoolCharacteristic;
 use crate::dto::characteristic::enum_characteristic::EnumCharacteristic;
 use crate::dto::characteristic::float_characteristic::FloatCharacteristic;
 use crate::dto::characteristic::int_characteristic::IntCharacteristic;
 use crate::dto::characteristic::string_characteristic::StringCharacteristic;
 
+pub mod bool_characteristic;
 pub mod enum_characteristic;
 pub mod float_characteristic;
 pub mod int_characteristic;
@@ -14,4 +16,5 @@ pub enum TypedCharacteristic {
     Int(IntCharacteristic),
     String(StringCharacteristic),
     Enum(EnumCharacteristic),
+    Bool(BoolCharacteristic),
 }
@@ -25,6 +25,22 @@ impl StringCharacteristic {
         name[0..name.find('(').unwrap()].to_string()
     }
 
+    pub fn from_name(name: &str, value: String) -> Option<StringCharacteristic> {
+        use StringCharacteristic::*;
+
+        let constructor = match name {
+            "Processor" => Processor,
+            "VideoProcessor" => VideoProcessor,
+            "AspectRatio" => AspectRatio,
+            "DisplayResolution" => DisplayResolution,
+            "Contrast" => Contrast,
+            "Model" => Model,
+            _ => return None,
+        };
+
+        Some(constructor(value))
+    }
+
     pub fn value(&self) -> String {
         use StringCharacteristic::*;
 
@@ -43,7 +43,7 @@ pub enum CharacteristicVisualisationType {
     Bool,
 }
 
-#[derive(diesel_derive_enum::DbEnum, EnumIter, Serialize, Deserialize, Debug, Copy, Clone)]
+#[derive(diesel_derive_enum::DbEnum, EnumIter, Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 #[DieselType = "Characteristic_group_slug"]
 pub enum CharacteristicGroupSlug {
     Processor,
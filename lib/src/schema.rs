@@ -35,6 +35,36 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    crawl_run (id) {
+        id -> Int4,
+        source_id -> Int4,
+        category_id -> Nullable<Int4>,
+        kind -> Varchar,
+        status -> Varchar,
+        pages -> Int4,
+        products_new -> Int4,
+        products_updated -> Int4,
+        products_unchanged -> Int4,
+        failures -> Int4,
+        postponed -> Int4,
+        warnings -> Int4,
+        started_at -> Timestamp,
+        finished_at -> Nullable<Timestamp>,
+        items -> Int4,
+        missing_id -> Int4,
+        missing_title -> Int4,
+        missing_price -> Int4,
+        missing_availability -> Int4,
+        missing_description -> Int4,
+        unknown_characteristics -> Int4,
+        health_alert -> Nullable<Varchar>,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::my_enum::*;
@@ -47,6 +77,19 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    http_cache (id) {
+        id -> Int4,
+        url -> Varchar,
+        etag -> Nullable<Varchar>,
+        last_modified -> Nullable<Varchar>,
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::my_enum::*;
@@ -65,6 +108,21 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    product_audit (id) {
+        id -> Int4,
+        operation -> Varchar,
+        product_id -> Int4,
+        affected_product_id -> Int4,
+        source_product_id -> Nullable<Int4>,
+        actor -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::my_enum::*;
@@ -73,6 +131,7 @@ table! {
         product_id -> Int4,
         characteristic_id -> Int2,
         value_id -> Int4,
+        source_id -> Nullable<Int4>,
     }
 }
 
@@ -106,6 +165,113 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    product_identifier (id) {
+        id -> Int4,
+        product_id -> Int4,
+        kind -> Varchar,
+        value -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    product_image (id) {
+        id -> Int4,
+        product_id -> Int4,
+        source_id -> Nullable<Int4>,
+        original_url -> Varchar,
+        hash -> Int8,
+        width -> Int4,
+        height -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    product_image_variant (id) {
+        id -> Int4,
+        image_id -> Int4,
+        size -> Varchar,
+        format -> Varchar,
+        file_path -> Varchar,
+        width -> Int4,
+        height -> Int4,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    product_match_candidate (id) {
+        id -> Int4,
+        product_id -> Int4,
+        candidate_product_id -> Int4,
+        score -> Numeric,
+        reason -> Varchar,
+        status -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    product_redirect (id) {
+        id -> Int4,
+        from_product_id -> Int4,
+        from_title -> Varchar,
+        to_product_id -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    product_variant (id) {
+        id -> Int4,
+        product_id -> Int4,
+        color -> Nullable<Varchar>,
+        built_in_memory_gb -> Nullable<Int4>,
+        ram_gb -> Nullable<Int4>,
+        lowest_price -> Numeric,
+        highest_price -> Numeric,
+        enabled -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    raw_page (id) {
+        id -> Int4,
+        source_id -> Int4,
+        category_id -> Nullable<Int4>,
+        kind -> Varchar,
+        external_id -> Varchar,
+        blob_key -> Varchar,
+        size -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::my_enum::*;
@@ -134,6 +300,7 @@ table! {
         price -> Numeric,
         enabled -> Bool,
         updated_at -> Timestamp,
+        variant_id -> Int4,
     }
 }
 
@@ -151,6 +318,23 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::my_enum::*;
+
+    unknown_characteristic (id) {
+        id -> Int4,
+        source_id -> Int4,
+        kind -> Varchar,
+        title -> Varchar,
+        occurrences -> Int4,
+        external_ids -> Array<Varchar>,
+        sample_values -> Array<Varchar>,
+        first_seen_at -> Timestamp,
+        last_seen_at -> Timestamp,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::my_enum::*;
@@ -178,27 +362,51 @@ table! {
 
 joinable!(category_characteristic -> category (category_id));
 joinable!(category_characteristic -> characteristic (characteristic_id));
+joinable!(crawl_run -> category (category_id));
+joinable!(crawl_run -> source (source_id));
 joinable!(product -> category (category));
 joinable!(product_characteristic -> product (product_id));
+joinable!(product_characteristic -> source (source_id));
+joinable!(product_identifier -> product (product_id));
+joinable!(product_image -> product (product_id));
+joinable!(product_image -> source (source_id));
+joinable!(product_image_variant -> product_image (image_id));
+joinable!(product_redirect -> product (to_product_id));
+joinable!(product_variant -> product (product_id));
+joinable!(raw_page -> category (category_id));
+joinable!(raw_page -> source (source_id));
 joinable!(source_product -> product (product_id));
+joinable!(source_product -> product_variant (variant_id));
 joinable!(source_product -> source (source_id));
 joinable!(source_product_price_history -> product (product_id));
 joinable!(source_product_price_history -> source (source_id));
+joinable!(unknown_characteristic -> source (source_id));
 joinable!(user_registration -> users (user_id));
 
 allow_tables_to_appear_in_same_query!(
     category,
     category_characteristic,
     characteristic,
+    crawl_run,
     exchange_rate,
+    http_cache,
     product,
+    product_audit,
     product_characteristic,
     product_characteristic_enum_value,
     product_characteristic_float_value,
     product_characteristic_string_value,
+    product_identifier,
+    product_image,
+    product_image_variant,
+    product_match_candidate,
+    product_redirect,
+    product_variant,
+    raw_page,
     source,
     source_product,
     source_product_price_history,
+    unknown_characteristic,
     user_registration,
     users,
 );
@@ -1,13 +1,16 @@
 use strum::IntoEnumIterator;
 use strum::VariantNames;
 
+use crate::db::entity::category::CategorySlug;
 use crate::db::entity::characteristic::Characteristic;
+use crate::dto::characteristic::bool_characteristic::BoolCharacteristic;
 use crate::dto::characteristic::enum_characteristic::EnumCharacteristic;
 use crate::dto::characteristic::float_characteristic::FloatCharacteristic;
 use crate::dto::characteristic::int_characteristic::IntCharacteristic;
 use crate::dto::characteristic::string_characteristic::StringCharacteristic;
 use crate::dto::characteristic::{TypedCharacteristic};
 use crate::my_enum::{CharacteristicValueType, CharacteristicVisualisationType, CharacteristicGroupSlug};
+use crate::util::characteristic_category::get_category_characteristic_ids;
 use crate::util::characteristic_id::get_characteristic_id;
 
 pub fn get_all_characteristics_dto() -> Vec<Characteristic> {
@@ -15,10 +18,20 @@ pub fn get_all_characteristics_dto() -> Vec<Characteristic> {
     chars.append(&mut get_int_characteristics());
     chars.append(&mut get_string_characteristics());
     chars.append(&mut get_enum_characteristics());
+    chars.append(&mut get_bool_characteristics());
 
     chars
 }
 
+pub fn get_category_characteristics_dto(category: CategorySlug) -> Vec<Characteristic> {
+    let ids = get_category_characteristic_ids(category);
+
+    get_all_characteristics_dto()
+        .into_iter()
+        .filter(|char| ids.contains(&char.id))
+        .collect()
+}
+
 pub fn get_float_characteristics() -> Vec<Characteristic> {
     let mut characteristics = vec![];
     for item in FloatCharacteristic::iter() {
@@ -114,6 +127,29 @@ pub fn get_enum_characteristics() -> Vec<Characteristic> {
     characteristics
 }
 
+pub fn get_bool_characteristics() -> Vec<Characteristic> {
+    let mut characteristics = vec![];
+    for item in BoolCharacteristic::iter() {
+        let value_type = CharacteristicValueType::Bool;
+        let visualisation_type = CharacteristicVisualisationType::Bool;
+        let sort_key = get_bool_char_sort_key(&item);
+        let group_slug = get_bool_char_group_slug(&item);
+        let id = get_characteristic_id(&TypedCharacteristic::Bool(item));
+
+        characteristics.push(Characteristic {
+            id,
+            slug: item.name(),
+            enabled: true,
+            visualisation_type,
+            value_type,
+            sort_key,
+            group_slug,
+        });
+    }
+
+    characteristics
+}
+
 fn get_float_char_sort_key(char: &FloatCharacteristic) -> i16 {
     use FloatCharacteristic::*;
 
@@ -271,7 +307,6 @@ fn get_enum_char_sort_key(char: &EnumCharacteristic) -> i16 {
         SatelliteNavigation(_) => 10,
         WifiStandard(_) => 3,
         AudioJack(_) => 2,
-        TechnologySupport(_) => 1,
         ProducingCountry(_) => 0,
         MemoryCardSlot(_) => 1,
         SupportedMediaFormat(_) => 3,
@@ -290,9 +325,31 @@ fn get_enum_char_group_slug(char: &EnumCharacteristic) -> CharacteristicGroupSlu
         SatelliteNavigation(_) => CharacteristicGroupSlug::Connection,
         WifiStandard(_) => CharacteristicGroupSlug::Connection,
         AudioJack(_) => CharacteristicGroupSlug::General,
-        TechnologySupport(_) => CharacteristicGroupSlug::General,
         ProducingCountry(_) => CharacteristicGroupSlug::General,
         MemoryCardSlot(_) => CharacteristicGroupSlug::Memory,
         SupportedMediaFormat(_) => CharacteristicGroupSlug::General,
     }
 }
+
+fn get_bool_char_sort_key(char: &BoolCharacteristic) -> i16 {
+    use BoolCharacteristic::*;
+
+    match char {
+        NFC(_) => 1,
+        FastCharging(_) => 1,
+        InfraredPort(_) => 3,
+        WirelessCharger(_) => 2,
+        Autofocus(_) => 2,
+    }
+}
+fn get_bool_char_group_slug(char: &BoolCharacteristic) -> CharacteristicGroupSlug {
+    use BoolCharacteristic::*;
+
+    match char {
+        NFC(_) => CharacteristicGroupSlug::Connection,
+        FastCharging(_) => CharacteristicGroupSlug::Power,
+        InfraredPort(_) => CharacteristicGroupSlug::Connection,
+        WirelessCharger(_) => CharacteristicGroupSlug::Power,
+        Autofocus(_) => CharacteristicGroupSlug::Camera,
+    }
+}
@@ -0,0 +1,143 @@
+use strum::IntoEnumIterator;
+use strum::VariantNames;
+
+use crate::db::entity::category::CategorySlug;
+use crate::db::entity::category::CategorySlug::{Headphones, SmartHome, Smartphone, Watches};
+use crate::dto::characteristic::bool_characteristic::BoolCharacteristic;
+use crate::dto::characteristic::enum_characteristic::EnumCharacteristic;
+use crate::dto::characteristic::float_characteristic::FloatCharacteristic;
+use crate::dto::characteristic::int_characteristic::IntCharacteristic;
+use crate::dto::characteristic::string_characteristic::StringCharacteristic;
+use crate::dto::characteristic::TypedCharacteristic;
+use crate::util::characteristic_id::get_characteristic_id;
+
+const ALL: &[CategorySlug] = &[Smartphone, Watches, Headphones, SmartHome];
+const PORTABLE: &[CategorySlug] = &[Smartphone, Watches, Headphones];
+const WITH_DISPLAY: &[CategorySlug] = &[Smartphone, Watches];
+const PHONE: &[CategorySlug] = &[Smartphone];
+
+/// Categories which show the characteristic in filters, `sync_characteristic_enum` links them in `category_characteristic`
+pub fn get_characteristic_categories(char: &TypedCharacteristic) -> &'static [CategorySlug] {
+    match char {
+        TypedCharacteristic::Float(v) => match v {
+            FloatCharacteristic::Width_mm(_) => ALL,
+            FloatCharacteristic::Height_mm(_) => ALL,
+            FloatCharacteristic::Thickness_mm(_) => ALL,
+            FloatCharacteristic::ScreenDiagonal(_) => WITH_DISPLAY,
+            FloatCharacteristic::BluetoothVersion(_) => ALL,
+            FloatCharacteristic::CPUFrequency_Ghz(_) => WITH_DISPLAY,
+            FloatCharacteristic::Weight_gr(_) => ALL,
+            FloatCharacteristic::MIUIVersion(_) => PHONE,
+            FloatCharacteristic::AndroidVersion(_) => PHONE,
+            FloatCharacteristic::Aperture(_) => PHONE,
+        },
+        TypedCharacteristic::Int(v) => match v {
+            IntCharacteristic::BatteryCapacity_mA_h(_) => PORTABLE,
+            IntCharacteristic::NumberOfProcessorCores(_) => WITH_DISPLAY,
+            IntCharacteristic::BuiltInMemory_GB(_) => WITH_DISPLAY,
+            IntCharacteristic::Ram_GB(_) => WITH_DISPLAY,
+            IntCharacteristic::FrontCamera_MP(_) => PHONE,
+            IntCharacteristic::VideoResolution_Pix(_) => PHONE,
+            IntCharacteristic::AmountOfSimCards(_) => PHONE,
+            IntCharacteristic::PPI(_) => WITH_DISPLAY,
+            IntCharacteristic::Fps(_) => PHONE,
+            IntCharacteristic::Brightness_cd_m2(_) => WITH_DISPLAY,
+            IntCharacteristic::UpdateFrequency_Hz(_) => WITH_DISPLAY,
+            IntCharacteristic::Camera_mp(_) => PHONE,
+            IntCharacteristic::LTEDiapason(_) => WITH_DISPLAY,
+            IntCharacteristic::GSMDiapason(_) => WITH_DISPLAY,
+            IntCharacteristic::UMTSDiapason(_) => WITH_DISPLAY,
+            IntCharacteristic::Warranty_month(_) => ALL,
+            IntCharacteristic::MaxMemoryCardSize_GB(_) => PHONE,
+        },
+        TypedCharacteristic::String(v) => match v {
+            StringCharacteristic::Processor(_) => WITH_DISPLAY,
+            StringCharacteristic::VideoProcessor(_) => PHONE,
+            StringCharacteristic::AspectRatio(_) => PHONE,
+            StringCharacteristic::DisplayResolution(_) => WITH_DISPLAY,
+            StringCharacteristic::Contrast(_) => PHONE,
+            StringCharacteristic::Model(_) => ALL,
+        },
+        TypedCharacteristic::Enum(v) => match v {
+            EnumCharacteristic::ChargingConnectorType(_) => PORTABLE,
+            EnumCharacteristic::BatteryType(_) => PORTABLE,
+            EnumCharacteristic::SimCard(_) => WITH_DISPLAY,
+            EnumCharacteristic::Material(_) => ALL,
+            EnumCharacteristic::DisplayType(_) => WITH_DISPLAY,
+            EnumCharacteristic::InternetConnectionTechnology(_) => WITH_DISPLAY,
+            EnumCharacteristic::SatelliteNavigation(_) => WITH_DISPLAY,
+            EnumCharacteristic::WifiStandard(_) => ALL,
+            EnumCharacteristic::AudioJack(_) => &[Smartphone, Headphones],
+            EnumCharacteristic::ProducingCountry(_) => ALL,
+            EnumCharacteristic::MemoryCardSlot(_) => PHONE,
+            EnumCharacteristic::SupportedMediaFormat(_) => WITH_DISPLAY,
+        },
+        TypedCharacteristic::Bool(v) => match v {
+            BoolCharacteristic::NFC(_) => WITH_DISPLAY,
+            BoolCharacteristic::FastCharging(_) => PHONE,
+            BoolCharacteristic::InfraredPort(_) => PHONE,
+            BoolCharacteristic::WirelessCharger(_) => PORTABLE,
+            BoolCharacteristic::Autofocus(_) => PHONE,
+        },
+    }
+}
+
+/// Ids of the characteristics of the category
+pub fn get_category_characteristic_ids(category: CategorySlug) -> Vec<i16> {
+    get_all_typed_characteristics()
+        .iter()
+        .filter(|char| get_characteristic_categories(char).contains(&category))
+        .map(get_characteristic_id)
+        .collect()
+}
+
+/// One value of every characteristic
+fn get_all_typed_characteristics() -> Vec<TypedCharacteristic> {
+    let mut chars: Vec<TypedCharacteristic> = vec![];
+    chars.extend(FloatCharacteristic::iter().map(TypedCharacteristic::Float));
+    chars.extend(IntCharacteristic::iter().map(TypedCharacteristic::Int));
+    chars.extend(StringCharacteristic::iter().map(TypedCharacteristic::String));
+    chars.extend(
+        EnumCharacteristic::VARIANTS
+            .iter()
+            .map(|name| TypedCharacteristic::Enum(EnumCharacteristic::type_from_name(name))),
+    );
+    chars.extend(BoolCharacteristic::iter().map(TypedCharacteristic::Bool));
+
+    chars
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::entity::category::CategorySlug;
+    use crate::util::characteristic_category::{
+        get_all_typed_characteristics, get_category_characteristic_ids,
+        get_characteristic_categories,
+    };
+
+    #[test]
+    fn it_shows_every_characteristic_in_a_category() {
+        for char in get_all_typed_characteristics() {
+            assert!(
+                !get_characteristic_categories(&char).is_empty(),
+                "{:?} has no category",
+                char
+            );
+        }
+    }
+
+    #[test]
+    fn it_gives_category_only_its_characteristics() {
+        let smart_home_ids = get_category_characteristic_ids(CategorySlug::SmartHome);
+        let smartphone_ids = get_category_characteristic_ids(CategorySlug::Smartphone);
+
+        // Width_mm and Model are declared for all categories, ScreenDiagonal and NFC need a display
+        assert!(smart_home_ids.contains(&1));
+        assert!(smart_home_ids.contains(&33));
+        assert!(!smart_home_ids.contains(&4));
+        assert!(!smart_home_ids.contains(&47));
+        assert!(smartphone_ids.contains(&4));
+        assert!(smartphone_ids.contains(&47));
+        assert!(!smartphone_ids.contains(&43));
+    }
+}
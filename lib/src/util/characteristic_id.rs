@@ -1,3 +1,4 @@
+use crate::dto::characteristic::bool_characteristic::BoolCharacteristic;
 use crate::dto::characteristic::enum_characteristic::EnumCharacteristic;
 use crate::dto::characteristic::float_characteristic::FloatCharacteristic;
 use crate::dto::characteristic::int_characteristic::IntCharacteristic;
@@ -55,10 +56,16 @@ pub fn get_characteristic_id(char: &TypedCharacteristic) -> i16 {
             EnumCharacteristic::SatelliteNavigation(_) => 40,
             EnumCharacteristic::WifiStandard(_) => 41,
             EnumCharacteristic::AudioJack(_) => 42,
-            EnumCharacteristic::TechnologySupport(_) => 43,
             EnumCharacteristic::ProducingCountry(_) => 44,
             EnumCharacteristic::MemoryCardSlot(_) => 45,
             EnumCharacteristic::SupportedMediaFormat(_) => 46,
         },
+        TypedCharacteristic::Bool(v) => match v {
+            BoolCharacteristic::NFC(_) => 47,
+            BoolCharacteristic::FastCharging(_) => 48,
+            BoolCharacteristic::InfraredPort(_) => 49,
+            BoolCharacteristic::WirelessCharger(_) => 50,
+            BoolCharacteristic::Autofocus(_) => 51,
+        },
     }
 }
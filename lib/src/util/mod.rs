@@ -1,2 +1,3 @@
 pub mod characteristic_id;
-pub mod all_characteristics;
\ No newline at end of file
+pub mod all_characteristics;
+pub mod characteristic_category;
@@ -0,0 +1 @@
+DROP TABLE http_cache;
\ No newline at end of file
@@ -0,0 +1,11 @@
+create table http_cache (
+    id              serial primary key,
+    url             varchar not null,
+    etag            varchar,
+    last_modified   varchar,
+
+    updated_at      timestamp not null
+);
+SELECT diesel_manage_updated_at('http_cache');
+CREATE UNIQUE INDEX idx_http_cache_url
+ON http_cache(url);
@@ -0,0 +1,2 @@
+ALTER TABLE source_product DROP COLUMN variant_id;
+DROP TABLE product_variant;
@@ -0,0 +1,35 @@
+create table product_variant (
+    id                  serial primary key,
+    product_id          int not null,
+    color               varchar,
+    built_in_memory_gb  int,
+    ram_gb              int,
+    lowest_price        numeric not null,
+    highest_price       numeric not null,
+    enabled             bool not null,
+
+    created_at          timestamp not null default now(),
+    updated_at          timestamp not null,
+
+    foreign key(product_id)
+	  references product(id)
+	  on delete cascade
+);
+SELECT diesel_manage_updated_at('product_variant');
+CREATE UNIQUE INDEX idx_product_variant
+ON product_variant(product_id, coalesce(color, ''), coalesce(built_in_memory_gb, 0), coalesce(ram_gb, 0));
+
+-- every existing product gets a variant without attributes, so offers keep their product
+insert into product_variant (product_id, lowest_price, highest_price, enabled, created_at, updated_at)
+select id, lowest_price, highest_price, enabled, created_at, updated_at from product;
+
+alter table source_product add column variant_id int;
+update source_product
+set variant_id = product_variant.id
+from product_variant
+where product_variant.product_id = source_product.product_id;
+alter table source_product alter column variant_id set not null;
+alter table source_product
+    add foreign key(variant_id)
+    references product_variant(id)
+    on delete cascade;
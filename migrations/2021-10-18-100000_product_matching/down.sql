@@ -0,0 +1,2 @@
+DROP TABLE product_match_candidate;
+DROP TABLE product_identifier;
@@ -0,0 +1,37 @@
+create table product_identifier (
+    id              serial primary key,
+    product_id      int not null,
+    kind            varchar not null,
+    value           varchar not null,
+
+    created_at      timestamp not null default now(),
+
+    foreign key(product_id)
+	  references product(id)
+	  on delete cascade
+);
+CREATE UNIQUE INDEX idx_product_identifier
+ON product_identifier(kind, value);
+
+create table product_match_candidate (
+    id                      serial primary key,
+    product_id              int not null,
+    candidate_product_id    int not null,
+    score                   numeric not null,
+    reason                  varchar not null,
+    status                  varchar not null default 'pending',
+
+    created_at              timestamp not null default now(),
+    updated_at              timestamp not null,
+
+    foreign key(product_id)
+	  references product(id)
+	  on delete cascade,
+
+    foreign key(candidate_product_id)
+	  references product(id)
+	  on delete cascade
+);
+SELECT diesel_manage_updated_at('product_match_candidate');
+CREATE UNIQUE INDEX idx_product_match_candidate
+ON product_match_candidate(product_id, candidate_product_id);
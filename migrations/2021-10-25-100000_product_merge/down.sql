@@ -0,0 +1,2 @@
+DROP TABLE product_audit;
+DROP TABLE product_redirect;
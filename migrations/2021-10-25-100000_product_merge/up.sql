@@ -0,0 +1,27 @@
+create table product_redirect (
+    id              serial primary key,
+    from_product_id int not null,
+    from_title      varchar not null,
+    to_product_id   int not null,
+
+    created_at      timestamp not null default now(),
+
+    foreign key(to_product_id)
+	  references product(id)
+	  on delete cascade
+);
+CREATE UNIQUE INDEX idx_product_redirect_from
+ON product_redirect(from_product_id);
+CREATE INDEX idx_product_redirect_from_title
+ON product_redirect(from_title);
+
+create table product_audit (
+    id                  serial primary key,
+    operation           varchar not null,
+    product_id          int not null,
+    affected_product_id int not null,
+    source_product_id   int,
+    actor               varchar not null,
+
+    created_at          timestamp not null default now()
+);
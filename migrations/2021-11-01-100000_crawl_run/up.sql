@@ -0,0 +1,27 @@
+create table crawl_run (
+    id                  serial primary key,
+    source_id           int not null,
+    category_id         int,
+    kind                varchar not null,
+    status              varchar not null default 'running',
+    pages               int not null default 0,
+    products_new        int not null default 0,
+    products_updated    int not null default 0,
+    products_unchanged  int not null default 0,
+    failures            int not null default 0,
+    postponed           int not null default 0,
+    warnings            int not null default 0,
+
+    started_at          timestamp not null default now(),
+    finished_at         timestamp,
+
+    foreign key(source_id)
+	  references source(id)
+	  on delete cascade,
+
+    foreign key(category_id)
+	  references category(id)
+	  on delete set null
+);
+CREATE INDEX idx_crawl_run_source
+ON crawl_run(source_id, started_at);
@@ -0,0 +1,9 @@
+alter table crawl_run
+    drop column items,
+    drop column missing_id,
+    drop column missing_title,
+    drop column missing_price,
+    drop column missing_availability,
+    drop column missing_description,
+    drop column unknown_characteristics,
+    drop column health_alert;
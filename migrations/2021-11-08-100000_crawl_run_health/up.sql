@@ -0,0 +1,9 @@
+alter table crawl_run
+    add column items                    int not null default 0,
+    add column missing_id               int not null default 0,
+    add column missing_title            int not null default 0,
+    add column missing_price            int not null default 0,
+    add column missing_availability     int not null default 0,
+    add column missing_description      int not null default 0,
+    add column unknown_characteristics  int not null default 0,
+    add column health_alert             varchar;
@@ -0,0 +1,22 @@
+create table raw_page (
+    id              serial primary key,
+    source_id       int not null,
+    category_id     int,
+    kind            varchar not null,
+    -- url of the listing page
+    external_id     varchar not null,
+    blob_key        varchar not null,
+    size            int not null,
+
+    created_at      timestamp not null default now(),
+
+    foreign key(source_id)
+	  references source(id)
+	  on delete cascade,
+
+    foreign key(category_id)
+	  references category(id)
+	  on delete set null
+);
+CREATE INDEX idx_raw_page_external_id
+ON raw_page(source_id, kind, external_id, created_at);
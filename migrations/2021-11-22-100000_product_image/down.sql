@@ -0,0 +1,2 @@
+DROP TABLE product_image_variant;
+DROP TABLE product_image;
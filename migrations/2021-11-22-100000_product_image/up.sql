@@ -0,0 +1,38 @@
+create table product_image (
+    id              serial primary key,
+    product_id      int not null,
+    source_id       int not null,
+    original_url    varchar not null,
+    -- difference hash of the photo
+    hash            bigint not null,
+    width           int not null,
+    height          int not null,
+
+    created_at      timestamp not null default now(),
+
+    foreign key(product_id)
+	  references product(id)
+	  on delete cascade,
+
+    foreign key(source_id)
+	  references source(id)
+	  on delete cascade
+);
+CREATE INDEX idx_product_image_product
+ON product_image(product_id);
+
+create table product_image_variant (
+    id              serial primary key,
+    image_id        int not null,
+    size            varchar not null,
+    format          varchar not null,
+    file_path       varchar not null,
+    width           int not null,
+    height          int not null,
+
+    foreign key(image_id)
+	  references product_image(id)
+	  on delete cascade
+);
+CREATE INDEX idx_product_image_variant_image
+ON product_image_variant(image_id);
@@ -0,0 +1,22 @@
+create table unknown_characteristic (
+    id              serial primary key,
+    source_id       int not null,
+    -- `title` which no dictionary knows or `value` which the parser of the known title refused
+    kind            varchar not null,
+    title           varchar not null,
+    occurrences     int not null default 0,
+    -- a few of the latest examples
+    external_ids    varchar[] not null default '{}',
+    sample_values   varchar[] not null default '{}',
+
+    first_seen_at   timestamp not null default now(),
+    last_seen_at    timestamp not null default now(),
+
+    foreign key(source_id)
+	  references source(id)
+	  on delete cascade,
+
+    unique (source_id, kind, title)
+);
+CREATE INDEX idx_unknown_characteristic_occurrences
+ON unknown_characteristic(occurrences);
@@ -0,0 +1,16 @@
+insert into product_characteristic (product_id, characteristic_id, value_id)
+select pc.product_id, 43, ev.id
+from product_characteristic pc
+         inner join product_characteristic_enum_value ev on ev.value = 'TechnologySupport.' ||
+    case pc.characteristic_id
+        when 47 then 'NFC'
+        when 48 then 'FastCharging'
+        when 49 then 'InfraredPort'
+        when 50 then 'WirelessCharger'
+        when 51 then 'Autofocus'
+    end
+where pc.characteristic_id between 47 and 51
+  and pc.value_id = 1
+on conflict do nothing;
+
+delete from product_characteristic where characteristic_id between 47 and 51;
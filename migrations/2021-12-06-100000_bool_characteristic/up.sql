@@ -0,0 +1,17 @@
+-- Yes/no specs were saved as values of TechnologySupport (43), they are bool characteristics (47-51) now
+insert into product_characteristic (product_id, characteristic_id, value_id)
+select pc.product_id,
+       case ev.value
+           when 'TechnologySupport.NFC' then 47
+           when 'TechnologySupport.FastCharging' then 48
+           when 'TechnologySupport.InfraredPort' then 49
+           when 'TechnologySupport.WirelessCharger' then 50
+           when 'TechnologySupport.Autofocus' then 51
+       end,
+       1
+from product_characteristic pc
+         inner join product_characteristic_enum_value ev on ev.id = pc.value_id
+where pc.characteristic_id = 43
+on conflict do nothing;
+
+delete from product_characteristic where characteristic_id = 43;
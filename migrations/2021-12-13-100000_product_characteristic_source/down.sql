@@ -0,0 +1,2 @@
+alter table product_characteristic
+    drop column source_id;
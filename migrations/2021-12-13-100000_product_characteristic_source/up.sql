@@ -0,0 +1,9 @@
+-- Source which the value was parsed from, so reparse of a source replaces only its own values.
+-- Values saved before are left without a source.
+alter table product_characteristic
+    add column source_id int;
+
+alter table product_characteristic
+    add foreign key (source_id)
+        references source (id)
+        on delete set null;
@@ -0,0 +1,5 @@
+delete from product_image
+where source_id is null;
+
+alter table product_image
+    alter column source_id set not null;
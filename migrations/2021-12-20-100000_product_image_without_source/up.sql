@@ -0,0 +1,3 @@
+-- Images stored before the photos were hashed are hashed later, their source is not known.
+alter table product_image
+    alter column source_id drop not null;
@@ -0,0 +1,11 @@
+insert into characteristic (id, slug, enabled, visualisation_type, value_type, sort_key, group_slug)
+values (43, 'TechnologySupport', true, 'multi_selector', 'enum', 1, 'general')
+on conflict do nothing;
+
+insert into product_characteristic_enum_value (value)
+values ('TechnologySupport.NFC'),
+       ('TechnologySupport.FastCharging'),
+       ('TechnologySupport.InfraredPort'),
+       ('TechnologySupport.WirelessCharger'),
+       ('TechnologySupport.Autofocus')
+on conflict do nothing;
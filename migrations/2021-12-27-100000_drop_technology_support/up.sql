@@ -0,0 +1,4 @@
+-- TechnologySupport (43) values were moved to bool characteristics, the characteristic itself is not synced anymore
+delete from characteristic where id = 43;
+
+delete from product_characteristic_enum_value where value like 'TechnologySupport.%';
@@ -1,6 +1,6 @@
-use std::fmt;
+use serde::Serialize;
 
-use serde::{Deserialize, Serialize};
+pub use lib::db::entity::category::CategorySlug;
 
 #[derive(Serialize, Queryable)]
 pub struct Category {
@@ -8,18 +8,3 @@ pub struct Category {
     pub slug: String,
     pub parent_id: Option<i32>,
 }
-
-#[allow(dead_code)]
-#[derive(Debug, Serialize, Deserialize, Copy, Clone)]
-pub enum CategorySlug {
-    SmartHome,
-    Smartphone,
-    Headphones,
-    Watches,
-}
-
-impl fmt::Display for CategorySlug {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self, f)
-    }
-}
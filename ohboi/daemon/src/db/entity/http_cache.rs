@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+
+use lib::schema::http_cache;
+
+#[derive(Queryable, Debug)]
+pub struct HttpCache {
+    pub id: i32,
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "http_cache"]
+pub struct NewHttpCache<'a> {
+    pub url: &'a str,
+    pub etag: Option<&'a str>,
+    pub last_modified: Option<&'a str>,
+    pub updated_at: &'a NaiveDateTime,
+}
@@ -1,6 +1,12 @@
 pub mod user;
 pub mod product;
+pub mod product_identifier;
+pub mod product_image;
+pub mod product_match_candidate;
+pub mod product_variant;
 pub mod category;
+pub mod raw_page;
+pub mod http_cache;
 pub mod source;
 pub mod source_product;
 pub mod source_product_price_history;
@@ -0,0 +1,19 @@
+use lib::schema::product_identifier;
+
+use strum_macros::Display;
+
+#[derive(Insertable)]
+#[table_name = "product_identifier"]
+pub struct NewProductIdentifier<'a> {
+    pub product_id: i32,
+    pub kind: String,
+    pub value: &'a str,
+}
+
+/// Codes which identify the product regardless of its title on the source
+#[derive(Debug, Display, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum IdentifierKind {
+    Gtin,
+    Model,
+}
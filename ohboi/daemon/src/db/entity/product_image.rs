@@ -0,0 +1,24 @@
+use lib::schema::{product_image, product_image_variant};
+
+#[derive(Insertable)]
+#[table_name = "product_image"]
+pub struct NewProductImage<'a> {
+    pub product_id: i32,
+    /// `None` for the images stored before the photos were hashed
+    pub source_id: Option<i32>,
+    pub original_url: &'a str,
+    pub hash: i64,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[derive(Insertable)]
+#[table_name = "product_image_variant"]
+pub struct NewProductImageVariant {
+    pub image_id: i32,
+    pub size: String,
+    pub format: String,
+    pub file_path: String,
+    pub width: i32,
+    pub height: i32,
+}
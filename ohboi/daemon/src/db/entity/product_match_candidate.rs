@@ -0,0 +1,25 @@
+use lib::schema::product_match_candidate;
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use strum_macros::Display;
+
+#[derive(Insertable)]
+#[table_name = "product_match_candidate"]
+pub struct NewProductMatchCandidate<'a> {
+    pub product_id: i32,
+    pub candidate_product_id: i32,
+    pub score: BigDecimal,
+    pub reason: String,
+
+    pub updated_at: &'a NaiveDateTime,
+}
+
+/// Why two products are suspected to be the same one
+#[derive(Debug, Display, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum MatchReason {
+    Title,
+    Gtin,
+    Model,
+}
@@ -0,0 +1,37 @@
+use lib::schema::product_variant;
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+#[derive(Serialize, Queryable, Debug)]
+pub struct ProductVariant {
+    pub id: i32,
+    pub product_id: i32,
+    pub color: Option<String>,
+    pub built_in_memory_gb: Option<i32>,
+    pub ram_gb: Option<i32>,
+    pub lowest_price: BigDecimal,
+    pub highest_price: BigDecimal,
+    #[serde(skip)]
+    pub enabled: bool,
+
+    #[serde(skip)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "product_variant"]
+pub struct NewProductVariant<'a> {
+    pub product_id: i32,
+    pub color: Option<&'a str>,
+    pub built_in_memory_gb: Option<i32>,
+    pub ram_gb: Option<i32>,
+    pub lowest_price: BigDecimal,
+    pub highest_price: BigDecimal,
+    pub enabled: bool,
+
+    pub updated_at: &'a NaiveDateTime,
+}
@@ -0,0 +1,22 @@
+use lib::schema::raw_page;
+
+use strum_macros::Display;
+
+#[derive(Insertable)]
+#[table_name = "raw_page"]
+pub struct NewRawPage<'a> {
+    pub source_id: i32,
+    pub category_id: Option<i32>,
+    pub kind: String,
+    pub external_id: &'a str,
+    pub blob_key: &'a str,
+    pub size: i32,
+}
+
+/// Listing pages are not archived, their prices and availability are outdated by the time of reparse
+#[derive(Debug, Display, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum RawPageKind {
+    /// Product page, archived by the external id of the offer
+    Details,
+}
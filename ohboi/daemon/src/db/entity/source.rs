@@ -1,9 +1,13 @@
 use std::fmt;
 
 use chrono::NaiveDateTime;
-use serde::{Deserialize, Serialize};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use lib::my_enum::CurrencyEnum;
+use lib::schema::source;
+
+use crate::parse::crawler::find_declarative_source;
 
 #[derive(Queryable, Debug)]
 pub struct Source {
@@ -17,14 +21,50 @@ pub struct Source {
     pub updated_at: NaiveDateTime,
 }
 
+#[derive(Insertable)]
+#[table_name = "source"]
+pub struct NewSource<'a> {
+    pub site_name: &'a str,
+    pub logo: &'a str,
+    pub currency: CurrencyEnum,
+    pub enabled: bool,
+
+    pub created_at: &'a NaiveDateTime,
+    pub updated_at: &'a NaiveDateTime,
+}
+
 #[derive(Debug, Serialize, Deserialize, Copy, Clone)]
 pub enum SourceName {
     MiShopCom,
     SamsungShopComUa,
+    Declarative(DeclarativeSource),
 }
 
 impl fmt::Display for SourceName {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self, f)
+        match self {
+            SourceName::Declarative(source) => f.write_str(source.0),
+            _ => fmt::Debug::fmt(self, f),
+        }
     }
-}
\ No newline at end of file
+}
+
+/// Source described by a spec file instead of a hand-written crawler.
+/// Holds `site_name` of the source, it is serialized as a plain string.
+#[derive(Debug, Copy, Clone)]
+pub struct DeclarativeSource(pub &'static str);
+
+impl Serialize for DeclarativeSource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for DeclarativeSource {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+
+        find_declarative_source(&name)
+            .ok_or_else(|| D::Error::custom(format!("Unknown declarative source: {}", name)))
+    }
+}
@@ -19,6 +19,7 @@ pub struct SourceProduct {
 
     #[serde(skip)]
     pub updated_at: NaiveDateTime,
+    pub variant_id: i32,
 }
 
 #[derive(Insertable)]
@@ -30,6 +31,7 @@ pub struct NewSourceProduct<'a> {
     pub original_price: BigDecimal,
     pub enabled: bool,
     pub external_id: &'a str,
+    pub variant_id: i32,
 
     pub updated_at: &'a NaiveDateTime,
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+use lib::db;
+use lib::diesel::prelude::*;
+use lib::schema::category_characteristic;
+
+use crate::db::entity::characteristic::category_characteristic::{
+    CategoryCharacteristic, NewCategoryCharacteristic,
+};
+
+pub fn get_linked_characteristic_ids(category_id: i32) -> Vec<i16> {
+    let connection = &db::establish_connection();
+
+    category_characteristic::table
+        .filter(category_characteristic::category_id.eq(category_id))
+        .load::<CategoryCharacteristic>(connection)
+        .expect("Cannot load category characteristics")
+        .into_iter()
+        .map(|link| link.characteristic_id)
+        .collect()
+}
+
+/// Links and unlinks together, so a failed sync leaves the category as it was
+pub fn relink(
+    category_id: i32,
+    new_ids: &[i16],
+    removed_ids: &[i16],
+) -> QueryResult<(usize, usize)> {
+    let connection = &db::establish_connection();
+
+    connection.transaction(|| {
+        let linked = link(connection, category_id, new_ids)?;
+        let unlinked = unlink(connection, category_id, removed_ids)?;
+
+        Ok((linked, unlinked))
+    })
+}
+
+fn link(
+    connection: &PgConnection,
+    category_id: i32,
+    characteristic_ids: &[i16],
+) -> QueryResult<usize> {
+    if characteristic_ids.is_empty() {
+        return Ok(0);
+    }
+    let links: Vec<NewCategoryCharacteristic> = characteristic_ids
+        .iter()
+        .map(|&characteristic_id| NewCategoryCharacteristic {
+            category_id,
+            characteristic_id,
+        })
+        .collect();
+
+    diesel::insert_into(category_characteristic::table)
+        .values(&links)
+        .on_conflict_do_nothing()
+        .execute(connection)
+}
+
+fn unlink(
+    connection: &PgConnection,
+    category_id: i32,
+    characteristic_ids: &[i16],
+) -> QueryResult<usize> {
+    if characteristic_ids.is_empty() {
+        return Ok(0);
+    }
+
+    diesel::delete(
+        category_characteristic::table
+            .filter(category_characteristic::category_id.eq(category_id))
+            .filter(category_characteristic::characteristic_id.eq_any(characteristic_ids)),
+    )
+    .execute(connection)
+}
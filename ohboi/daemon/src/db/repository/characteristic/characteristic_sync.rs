@@ -1,26 +1,23 @@
-use strum::VariantNames;
+use strum::{IntoEnumIterator, VariantNames};
 
-use lib::db::entity::characteristic::Characteristic;
-use lib::diesel::prelude::*;
 use lib::dto::characteristic::enum_characteristic::{
     AudioJack, BatteryType, ChargingConnectorType, Country, DisplayType, EnumCharacteristic,
     InternetConnectionTechnology, Material, MediaFormat, MemoryCardSlot, SatelliteNavigation,
-    SimCard, Technology, WifiStandard,
+    SimCard, WifiStandard,
 };
+use lib::error_reporting;
 use lib::error_reporting::ReportingContext;
-use lib::schema::category_characteristic;
 use lib::util::all_characteristics::{
-    get_enum_characteristics, get_float_characteristics, get_int_characteristics,
-    get_string_characteristics,
+    get_bool_characteristics, get_enum_characteristics, get_float_characteristics,
+    get_int_characteristics, get_string_characteristics,
 };
-use lib::{db, error_reporting};
+use lib::util::characteristic_category::get_category_characteristic_ids;
 
 use crate::db::entity::category::CategorySlug;
-use crate::db::entity::characteristic::category_characteristic::{
-    CategoryCharacteristic, NewCategoryCharacteristic,
-};
 use crate::db::repository::category::get_category;
-use crate::db::repository::characteristic::{create, product_characteristic_enum_value};
+use crate::db::repository::characteristic::{
+    category_characteristic, create, product_characteristic_enum_value,
+};
 use crate::db::Executor;
 
 // TODO delete removed
@@ -29,11 +26,13 @@ pub fn sync_characteristic_enum() {
     sync_int_chars();
     sync_string_chars();
     sync_enum_chars();
+    sync_bool_chars();
+    sync_category_chars();
 }
 
 fn sync_float_chars() {
     for item in get_float_characteristics() {
-        let created_char = create::upsert(
+        create::upsert(
             item.id,
             item.slug,
             item.visualisation_type,
@@ -41,15 +40,12 @@ fn sync_float_chars() {
             item.sort_key,
             item.group_slug,
         );
-        if let Some(c) = created_char {
-            connect_char_to_category(&c, CategorySlug::Smartphone)
-        }
     }
 }
 
 fn sync_int_chars() {
     for item in get_int_characteristics() {
-        let created_char = create::upsert(
+        create::upsert(
             item.id,
             item.slug,
             item.visualisation_type,
@@ -57,15 +53,12 @@ fn sync_int_chars() {
             item.sort_key,
             item.group_slug,
         );
-        if let Some(c) = created_char {
-            connect_char_to_category(&c, CategorySlug::Smartphone)
-        }
     }
 }
 
 fn sync_string_chars() {
     for item in get_string_characteristics() {
-        let created_char = create::upsert(
+        create::upsert(
             item.id,
             item.slug,
             item.visualisation_type,
@@ -73,15 +66,12 @@ fn sync_string_chars() {
             item.sort_key,
             item.group_slug,
         );
-        if let Some(c) = created_char {
-            connect_char_to_category(&c, CategorySlug::Smartphone)
-        }
     }
 }
 
 fn sync_enum_chars() {
     for item in get_enum_characteristics() {
-        let created_char = create::upsert(
+        create::upsert(
             item.id,
             item.slug,
             item.visualisation_type,
@@ -89,14 +79,24 @@ fn sync_enum_chars() {
             item.sort_key,
             item.group_slug,
         );
-        if let Some(c) = created_char {
-            connect_char_to_category(&c, CategorySlug::Smartphone)
-        }
     }
 
     sync_enum_char_values();
 }
 
+fn sync_bool_chars() {
+    for item in get_bool_characteristics() {
+        create::upsert(
+            item.id,
+            item.slug,
+            item.visualisation_type,
+            item.value_type,
+            item.sort_key,
+            item.group_slug,
+        );
+    }
+}
+
 fn sync_enum_char_values() {
     sync_one_enum_char_values(&EnumCharacteristic::ChargingConnectorType(
         ChargingConnectorType::USBTypeC,
@@ -113,9 +113,6 @@ fn sync_enum_char_values() {
     ));
     sync_one_enum_char_values(&EnumCharacteristic::WifiStandard(WifiStandard::_5));
     sync_one_enum_char_values(&EnumCharacteristic::AudioJack(AudioJack::USBTypeC));
-    sync_one_enum_char_values(&EnumCharacteristic::TechnologySupport(
-        Technology::FastCharging,
-    ));
     sync_one_enum_char_values(&EnumCharacteristic::ProducingCountry(Country::China));
     sync_one_enum_char_values(&EnumCharacteristic::MemoryCardSlot(MemoryCardSlot::Separate));
     sync_one_enum_char_values(&EnumCharacteristic::SupportedMediaFormat(MediaFormat::H264));
@@ -136,7 +133,6 @@ fn sync_one_enum_char_values(char: &EnumCharacteristic) {
         EnumCharacteristic::SatelliteNavigation(_) => SatelliteNavigation::VARIANTS,
         EnumCharacteristic::WifiStandard(_) => WifiStandard::VARIANTS,
         EnumCharacteristic::AudioJack(_) => AudioJack::VARIANTS,
-        EnumCharacteristic::TechnologySupport(_) => Technology::VARIANTS,
         EnumCharacteristic::ProducingCountry(_) => Country::VARIANTS,
         EnumCharacteristic::MemoryCardSlot(_) => MemoryCardSlot::VARIANTS,
         EnumCharacteristic::SupportedMediaFormat(_) => MediaFormat::VARIANTS,
@@ -149,40 +145,71 @@ fn sync_one_enum_char_values(char: &EnumCharacteristic) {
     }
 }
 
-fn connect_char_to_category(char: &Characteristic, category: CategorySlug) {
-    let connection = &db::establish_connection();
-    let category_id = get_category(category).id;
-
-    let new_category_char = NewCategoryCharacteristic {
-        characteristic_id: char.id,
-        category_id,
-    };
+/// Links every category to the characteristics which declare it and removes the links which are not declared anymore
+fn sync_category_chars() {
+    for category in CategorySlug::iter() {
+        let category_id = get_category(category).id;
+        let (new_ids, removed_ids) = get_link_changes(
+            &get_category_characteristic_ids(category),
+            &category_characteristic::get_linked_characteristic_ids(category_id),
+        );
 
-    let insert_result: Result<CategoryCharacteristic, diesel::result::Error> =
-        diesel::insert_into(category_characteristic::table)
-            .values(&new_category_char)
-            .get_result(connection);
-
-    match insert_result {
-        Ok(_) => {
-            log::info!(
-                "Characteristic {} was successfully matched to {} category",
-                char.slug,
-                category
-            );
-        }
-        Err(e) => {
-            error_reporting::fatal(
-                format!(
-                    "Characteristic {} can't be matched to {} category. {:?}",
-                    char.slug, category, e
-                )
-                .as_str(),
-                &ReportingContext {
-                    executor: &Executor::Characteristic,
-                    action: "save_characteristic_value",
-                },
-            );
+        match category_characteristic::relink(category_id, &new_ids, &removed_ids) {
+            Ok((linked, unlinked)) => {
+                log::info!(
+                    "{} category: {} characteristics were linked, {} were unlinked",
+                    category,
+                    linked,
+                    unlinked
+                );
+            }
+            Err(e) => {
+                error_reporting::fatal(
+                    format!(
+                        "Characteristics of {} category can't be synced. {:?}",
+                        category, e
+                    )
+                    .as_str(),
+                    &ReportingContext {
+                        executor: &Executor::Characteristic,
+                        action: "sync_category_characteristics",
+                    },
+                );
+            }
         }
     }
 }
+
+/// Declared characteristics which aren't linked yet and linked ones which aren't declared anymore
+fn get_link_changes(declared_ids: &[i16], linked_ids: &[i16]) -> (Vec<i16>, Vec<i16>) {
+    let new_ids = declared_ids
+        .iter()
+        .filter(|id| !linked_ids.contains(id))
+        .copied()
+        .collect();
+    let removed_ids = linked_ids
+        .iter()
+        .filter(|id| !declared_ids.contains(id))
+        .copied()
+        .collect();
+
+    (new_ids, removed_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::repository::characteristic::characteristic_sync::get_link_changes;
+
+    #[test]
+    fn it_links_declared_and_unlinks_undeclared_characteristics() {
+        assert_eq!(
+            get_link_changes(&[1, 2, 3], &[2, 3, 43]),
+            (vec![1], vec![43])
+        );
+    }
+
+    #[test]
+    fn it_keeps_synced_links() {
+        assert_eq!(get_link_changes(&[47, 1], &[1, 47]), (vec![], vec![]));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod create;
+pub mod category_characteristic;
 pub mod product_characteristic;
 pub mod characteristic_sync;
 pub mod product_characteristic_float_value;
@@ -1,3 +1,5 @@
+use lib::diesel::pg::upsert::excluded;
+use lib::diesel::prelude::*;
 use lib::diesel::result::{DatabaseErrorKind, Error};
 use lib::diesel::RunQueryDsl;
 
@@ -22,3 +24,156 @@ pub fn create_many_if_not_exists(product_chars: &[NewProductCharacteristic]) {
         }
     }
 }
+
+/// Values of the product parsed from the source (or saved before sources were recorded) are replaced with the new ones,
+/// values of the other sources are kept
+pub fn replace_for_source(
+    product_id: i32,
+    source_id: i32,
+    product_chars: &[NewProductCharacteristic],
+) {
+    let connection = &db::establish_connection();
+
+    connection
+        .transaction::<_, Error, _>(|| {
+            diesel::delete(
+                product_characteristic::table
+                    .filter(product_characteristic::product_id.eq(product_id))
+                    .filter(
+                        product_characteristic::source_id
+                            .eq(source_id)
+                            .or(product_characteristic::source_id.is_null()),
+                    ),
+            )
+            .execute(connection)?;
+
+            diesel::insert_into(product_characteristic::table)
+                .values(product_chars)
+                .on_conflict_do_nothing()
+                .execute(connection)
+        })
+        .expect("Failed to replace product characteristics");
+}
+
+/// A product has one value of a bool characteristic, so the opposite value is removed whatever source gave it
+pub fn replace_bool_values(product_chars: &[NewProductCharacteristic]) {
+    let connection = &db::establish_connection();
+
+    connection
+        .transaction(|| replace_bools(connection, product_chars))
+        .expect("Failed to replace product bool characteristics");
+}
+
+fn replace_bools(
+    connection: &PgConnection,
+    product_chars: &[NewProductCharacteristic],
+) -> QueryResult<()> {
+    if product_chars.is_empty() {
+        return Ok(());
+    }
+
+    for product_char in product_chars {
+        diesel::delete(
+            product_characteristic::table
+                .filter(product_characteristic::product_id.eq(product_char.product_id))
+                .filter(
+                    product_characteristic::characteristic_id.eq(product_char.characteristic_id),
+                )
+                .filter(product_characteristic::value_id.ne(product_char.value_id)),
+        )
+        .execute(connection)?;
+    }
+
+    diesel::insert_into(product_characteristic::table)
+        .values(product_chars)
+        .on_conflict((
+            product_characteristic::product_id,
+            product_characteristic::characteristic_id,
+            product_characteristic::value_id,
+        ))
+        .do_update()
+        .set(product_characteristic::source_id.eq(excluded(product_characteristic::source_id)))
+        .execute(connection)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+
+    use lib::db;
+    use lib::diesel::prelude::*;
+    use lib::schema::{product, product_characteristic};
+
+    use crate::db::entity::category::CategorySlug;
+    use crate::db::entity::characteristic::product_characteristic::NewProductCharacteristic;
+    use crate::db::entity::product::NewProduct;
+    use crate::db::entity::source::SourceName;
+    use crate::db::repository::category::get_category;
+    use crate::db::repository::characteristic::product_characteristic::replace_bools;
+    use crate::db::repository::source::get_source;
+
+    const NFC_ID: i16 = 47;
+
+    fn create_product(connection: &PgConnection) -> QueryResult<i32> {
+        let now = Utc::now().naive_utc();
+
+        diesel::insert_into(product::table)
+            .values(&NewProduct {
+                category: get_category(CategorySlug::Smartphone).id,
+                title: "Bool characteristic test phone",
+                lowest_price: BigDecimal::from(1),
+                highest_price: BigDecimal::from(1),
+                enabled: false,
+                created_at: &now,
+                updated_at: &now,
+            })
+            .returning(product::id)
+            .get_result(connection)
+    }
+
+    fn get_nfc(product_id: i32, source: SourceName, value: bool) -> NewProductCharacteristic {
+        NewProductCharacteristic {
+            product_id,
+            characteristic_id: NFC_ID,
+            value_id: i32::from(value),
+            source_id: get_source(source).id,
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn it_keeps_only_the_latest_bool_value() {
+        let connection = &db::establish_connection();
+
+        connection.test_transaction::<_, diesel::result::Error, _>(|| {
+            let product_id = create_product(connection)?;
+
+            replace_bools(
+                connection,
+                &[get_nfc(product_id, SourceName::MiShopCom, true)],
+            )?;
+            replace_bools(
+                connection,
+                &[get_nfc(product_id, SourceName::SamsungShopComUa, false)],
+            )?;
+
+            let saved: Vec<(i32, Option<i32>)> = product_characteristic::table
+                .select((
+                    product_characteristic::value_id,
+                    product_characteristic::source_id,
+                ))
+                .filter(product_characteristic::product_id.eq(product_id))
+                .filter(product_characteristic::characteristic_id.eq(NFC_ID))
+                .load(connection)?;
+            assert_eq!(
+                saved,
+                vec![(0, Some(get_source(SourceName::SamsungShopComUa).id))]
+            );
+
+            Ok(())
+        });
+    }
+}
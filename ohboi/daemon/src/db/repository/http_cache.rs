@@ -0,0 +1,45 @@
+use chrono::Utc;
+use lib::diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use lib::db;
+use lib::schema::http_cache;
+
+use crate::db::entity::http_cache::{HttpCache, NewHttpCache};
+
+pub fn get_by_url(expected_url: &str) -> Option<HttpCache> {
+    use lib::schema::http_cache::dsl::{http_cache, url};
+
+    let connection = &db::establish_connection();
+
+    http_cache
+        .filter(url.eq(expected_url))
+        .limit(1)
+        .load::<HttpCache>(connection)
+        .expect("Error loading http cache")
+        .into_iter()
+        .next()
+}
+
+pub fn create_or_update(url: &str, etag: Option<&str>, last_modified: Option<&str>) {
+    let connection = &db::establish_connection();
+    let now = Utc::now().naive_utc();
+
+    let new_cache = NewHttpCache {
+        url,
+        etag,
+        last_modified,
+        updated_at: &now,
+    };
+
+    diesel::insert_into(http_cache::table)
+        .values(&new_cache)
+        .on_conflict(http_cache::url)
+        .do_update()
+        .set((
+            http_cache::etag.eq(etag),
+            http_cache::last_modified.eq(last_modified),
+            http_cache::updated_at.eq(&now),
+        ))
+        .execute(connection)
+        .expect("Error saving http cache");
+}
@@ -2,7 +2,13 @@ pub use characteristic::characteristic_sync::sync_characteristic_enum;
 
 pub mod category;
 pub mod exchange_rate;
+pub mod http_cache;
 pub mod product;
+pub mod product_identifier;
+pub mod product_image;
+pub mod product_match_candidate;
+pub mod product_variant;
+pub mod raw_page;
 pub mod source;
 pub mod source_product;
 pub mod source_product_price_history;
@@ -2,8 +2,10 @@ use bigdecimal::{BigDecimal, ToPrimitive};
 use chrono::Utc;
 
 use lib::db;
+use lib::db::repository::product_redirect::get_redirect_target_by_title;
 use lib::diesel::prelude::*;
 use lib::diesel::{sql_query, QueryDsl, RunQueryDsl};
+use lib::dto::characteristic::string_characteristic::StringCharacteristic;
 use lib::dto::characteristic::TypedCharacteristic;
 use lib::schema::product;
 use lib::util::characteristic_id::get_characteristic_id;
@@ -11,13 +13,26 @@ use lib::util::characteristic_id::get_characteristic_id;
 use crate::db::entity::category::CategorySlug;
 use crate::db::entity::characteristic::product_characteristic::NewProductCharacteristic;
 use crate::db::entity::product::{NewProduct, Product};
+use crate::db::entity::product_match_candidate::MatchReason;
+use crate::db::entity::source::SourceName;
 use crate::db::repository::category::get_category;
-use crate::db::repository::characteristic::product_characteristic::create_many_if_not_exists;
+use crate::db::repository::characteristic::product_characteristic::{
+    create_many_if_not_exists, replace_bool_values, replace_for_source,
+};
+use crate::db::repository::product_match_candidate::create_many_if_not_exists as create_many_candidates;
+use crate::db::repository::source::get_source;
+use crate::db::repository::source_product::get_by_source_and_external_id;
 use crate::db::repository::characteristic::{
     product_characteristic_enum_value, product_characteristic_float_value,
     product_characteristic_string_value,
 };
-use crate::dto::parsed_product::{AdditionalParsedProductInfo, InternationalParsedProduct};
+use crate::dto::parsed_product::{
+    AdditionalParsedProductInfo, InternationalParsedProduct, ProductIdentifiers,
+};
+use crate::parse::util::variant::split_variant;
+use crate::service::product_matching::{
+    match_product, remember_identifiers, remember_title, ProductMatch,
+};
 
 pub fn add_image_to_product_details(existent_product_id: i32, file_path: &str) {
     let connection = &db::establish_connection();
@@ -32,13 +47,94 @@ pub fn add_image_to_product_details(existent_product_id: i32, file_path: &str) {
     // TODO enable?
 }
 
-pub fn update_details(existent_product_id: i32, additional_info: &AdditionalParsedProductInfo) {
+pub fn update_details(
+    existent_product_id: i32,
+    source: SourceName,
+    additional_info: &AdditionalParsedProductInfo,
+) {
     use lib::schema::product::dsl::{description, enabled, id, images, product};
 
     let connection = &db::establish_connection();
     let target = product.filter(id.eq(existent_product_id));
+    let source_id = get_source(source).id;
+    let (bool_characteristics, characteristics) = split_bool_characteristics(additional_info);
+
+    create_many_if_not_exists(&get_product_characteristics(
+        existent_product_id,
+        source_id,
+        &characteristics,
+    ));
+    replace_bool_values(&get_product_characteristics(
+        existent_product_id,
+        source_id,
+        &bool_characteristics,
+    ));
+    remember_model(existent_product_id, additional_info);
+
+    // TODO no sense to pull product at all
+    let existent_product = get_product_by_id(existent_product_id).unwrap();
 
-    let product_characteristics = additional_info.characteristics.iter().map(|tc| {
+    diesel::update(target)
+        .set((
+            description.eq(&additional_info.description),
+            images.eq(&additional_info.image_urls),
+            enabled.eq((existent_product.enabled || additional_info.available)
+                && !additional_info.image_urls.is_empty()
+                && !additional_info.description.is_empty()),
+        ))
+        .execute(connection)
+        .expect("Failed to update product price");
+}
+
+/// Details parsed again from the archived page: images and availability are outdated there,
+/// so only characteristics, identifiers and description are updated.
+/// Characteristics of the source are replaced, so values which the parser doesn't give anymore are removed.
+pub fn update_reparsed_details(
+    existent_product_id: i32,
+    source: SourceName,
+    additional_info: &AdditionalParsedProductInfo,
+) {
+    use lib::schema::product::dsl::{description, id, product};
+
+    let source_id = get_source(source).id;
+    let (bool_characteristics, characteristics) = split_bool_characteristics(additional_info);
+    let bool_values =
+        get_product_characteristics(existent_product_id, source_id, &bool_characteristics);
+    let mut values = get_product_characteristics(existent_product_id, source_id, &characteristics);
+    values.extend(bool_values.iter().cloned());
+
+    replace_for_source(existent_product_id, source_id, &values);
+    replace_bool_values(&bool_values);
+    remember_model(existent_product_id, additional_info);
+
+    if additional_info.description.is_empty() {
+        return;
+    }
+
+    let connection = &db::establish_connection();
+
+    diesel::update(product.filter(id.eq(existent_product_id)))
+        .set(description.eq(&additional_info.description))
+        .execute(connection)
+        .expect("Failed to update product description");
+}
+
+/// Bool values replace the opposite ones, other values are added next to the values of the other sources
+fn split_bool_characteristics(
+    additional_info: &AdditionalParsedProductInfo,
+) -> (Vec<&TypedCharacteristic>, Vec<&TypedCharacteristic>) {
+    additional_info
+        .characteristics
+        .iter()
+        .partition(|tc| matches!(tc, TypedCharacteristic::Bool(_)))
+}
+
+fn get_product_characteristics(
+    existent_product_id: i32,
+    source_id: i32,
+    characteristics: &[&TypedCharacteristic],
+) -> Vec<NewProductCharacteristic> {
+    let product_characteristics = characteristics.iter().map(|&tc| {
         let characteristic_id = get_characteristic_id(tc);
 
         let value_id = match tc {
@@ -65,53 +161,83 @@ pub fn update_details(existent_product_id: i32, additional_info: &AdditionalPars
 
                 Some(product_value.id)
             }
+            TypedCharacteristic::Bool(v) => {
+                // Stored as 1 or 0 directly in value_id like int values
+                Some(i32::from(v.value()))
+            }
         };
 
         value_id.map(|v| NewProductCharacteristic {
             product_id: existent_product_id,
             characteristic_id,
             value_id: v,
+            source_id,
         })
     });
 
-    create_many_if_not_exists(
-        &product_characteristics
-            .into_iter()
-            .flatten()
-            .collect::<Vec<NewProductCharacteristic>>(),
-    );
-
-    // TODO no sense to pull product at all
-    let existent_product = get_product_by_id(existent_product_id).unwrap();
+    product_characteristics.into_iter().flatten().collect()
+}
 
-    diesel::update(target)
-        .set((
-            description.eq(&additional_info.description),
-            images.eq(&additional_info.image_urls),
-            enabled.eq((existent_product.enabled || additional_info.available)
-                && !additional_info.image_urls.is_empty()
-                && !additional_info.description.is_empty()),
-        ))
-        .execute(connection)
-        .expect("Failed to update product price");
+fn remember_model(existent_product_id: i32, additional_info: &AdditionalParsedProductInfo) {
+    remember_identifiers(
+        existent_product_id,
+        &ProductIdentifiers {
+            gtin: None,
+            model: get_model(&additional_info.characteristics),
+        },
+    );
 }
 
 pub fn create_if_not_exists(
     parsed_product: &InternationalParsedProduct,
     product_category: CategorySlug,
+    source: SourceName,
 ) -> Product {
-    let existed_product = get_product_by_title(parsed_product.title.as_str());
+    let category = get_category(product_category);
+    // offer stays with its product, so manual merges and splits are not undone by the next crawl
+    let existed_product =
+        get_by_source_and_external_id(source, parsed_product.external_id.as_str())
+            .and_then(|source_product| get_product_by_id(source_product.product_id))
+            .or_else(|| get_product_by_title(parsed_product.title.as_str()))
+            .or_else(|| adopt_product_with_variant_title(&parsed_product.title, category.id));
+
+    let product = match existed_product {
+        Some(current_product) => enable_if_available(current_product, parsed_product),
+        None => match match_product(parsed_product, category.id) {
+            ProductMatch::Found(product_id) => {
+                enable_if_available(get_product_by_id(product_id).unwrap(), parsed_product)
+            }
+            ProductMatch::Ambiguous(candidates) => {
+                let new_product = create(parsed_product, category.id);
+                create_many_candidates(new_product.id, &candidates, MatchReason::Title);
+                remember_title(category.id, new_product.id, &new_product.title);
 
-    existed_product.map_or_else(
-        || create(parsed_product, product_category),
-        |current_product| {
-            if parsed_product.available && !current_product.enabled {
-                enable_product(current_product.id);
+                new_product
             }
+            ProductMatch::NotFound => {
+                let new_product = create(parsed_product, category.id);
+                remember_title(category.id, new_product.id, &new_product.title);
 
-            current_product
+                new_product
+            }
         },
-    )
+    };
+    remember_identifiers(product.id, &parsed_product.identifiers);
+
+    product
+}
+
+/// Titles of all products of the category, to compare them with a new one
+pub fn get_titles_by_category(category_id: i32) -> Vec<(i32, String)> {
+    use lib::schema::product::dsl::{category, id, product, title};
+
+    let connection = &db::establish_connection();
+
+    product
+        .filter(category.eq(category_id))
+        .select((id, title))
+        .load::<(i32, String)>(connection)
+        .expect("Error loading product titles")
 }
 
 pub fn update_price_range_if_needed(product_id: i32, new_price: f64) {
@@ -152,13 +278,12 @@ pub fn update_price_range_if_needed(product_id: i32, new_price: f64) {
     }
 }
 
-fn create(parsed_product: &InternationalParsedProduct, product_category: CategorySlug) -> Product {
+fn create(parsed_product: &InternationalParsedProduct, category_id: i32) -> Product {
     let connection = &db::establish_connection();
     let now = Utc::now();
-    let category = get_category(product_category);
 
     let new_product = NewProduct {
-        category: category.id,
+        category: category_id,
         title: &parsed_product.title,
         enabled: false,
         lowest_price: BigDecimal::from(parsed_product.price),
@@ -178,6 +303,17 @@ fn create(parsed_product: &InternationalParsedProduct, product_category: Categor
     }
 }
 
+fn enable_if_available(
+    current_product: Product,
+    parsed_product: &InternationalParsedProduct,
+) -> Product {
+    if parsed_product.available && !current_product.enabled {
+        enable_product(current_product.id);
+    }
+
+    current_product
+}
+
 fn enable_product(product_id: i32) {
     use lib::schema::product::dsl::{enabled, id, product, updated_at};
     let connection = &db::establish_connection();
@@ -191,6 +327,7 @@ fn enable_product(product_id: i32) {
         .expect("Failed to enable product");
 }
 
+/// Product with the title or the one which the product with the title was merged into
 fn get_product_by_title(product_title: &str) -> Option<Product> {
     use lib::schema::product::dsl::{product, title};
 
@@ -202,7 +339,44 @@ fn get_product_by_title(product_title: &str) -> Option<Product> {
         .load::<Product>(connection)
         .expect("Error loading product");
 
-    results.into_iter().next()
+    results
+        .into_iter()
+        .next()
+        .or_else(|| get_redirect_target_by_title(product_title).and_then(get_product_by_id))
+}
+
+/// Products saved before variants were split off the titles keep the full listed title,
+/// e.g. `Redmi Note 10 Pro 8/128GB (Серый)`. The oldest of them is renamed to the title without the variant.
+fn adopt_product_with_variant_title(base_title: &str, category_id: i32) -> Option<Product> {
+    use lib::schema::product::dsl::{category, id, product, title};
+
+    let connection = &db::establish_connection();
+    let pattern = format!("{}%", escape_like(base_title));
+
+    let old_product = product
+        .filter(category.eq(category_id))
+        .filter(title.like(pattern))
+        .order(id)
+        .load::<Product>(connection)
+        .expect("Error loading products with variant titles")
+        .into_iter()
+        .find(|candidate| split_variant(&candidate.title).0 == base_title)?;
+
+    let renamed = diesel::update(product.filter(id.eq(old_product.id)))
+        .set(title.eq(base_title))
+        .execute(connection);
+
+    match renamed {
+        Ok(_) => get_product_by_id(old_product.id),
+        // Another one has been renamed concurrently
+        Err(_) => get_product_by_title(base_title),
+    }
+}
+
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
 
 fn get_product_by_id(product_id: i32) -> Option<Product> {
@@ -218,3 +392,10 @@ fn get_product_by_id(product_id: i32) -> Option<Product> {
 
     results.into_iter().next()
 }
+
+fn get_model(characteristics: &[TypedCharacteristic]) -> Option<String> {
+    characteristics.iter().find_map(|characteristic| match characteristic {
+        TypedCharacteristic::String(StringCharacteristic::Model(model)) => Some(model.clone()),
+        _ => None,
+    })
+}
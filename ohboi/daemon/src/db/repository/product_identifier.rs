@@ -0,0 +1,38 @@
+use lib::db;
+use lib::diesel::prelude::*;
+use lib::diesel::{QueryDsl, RunQueryDsl};
+use lib::schema::product_identifier;
+
+use crate::db::entity::product_identifier::{IdentifierKind, NewProductIdentifier};
+
+pub fn get_product_id(kind: IdentifierKind, expected_value: &str) -> Option<i32> {
+    use lib::schema::product_identifier::dsl::{product_id, value};
+
+    let connection = &db::establish_connection();
+
+    product_identifier::table
+        .filter(product_identifier::kind.eq(kind.to_string()))
+        .filter(value.eq(expected_value))
+        .select(product_id)
+        .first::<i32>(connection)
+        .optional()
+        .expect("Error loading product identifier")
+}
+
+/// Identifier belongs to the first product which had it, the owner is returned
+pub fn create_if_not_exists(product_id: i32, kind: IdentifierKind, value: &str) -> i32 {
+    let connection = &db::establish_connection();
+
+    diesel::insert_into(product_identifier::table)
+        .values(&NewProductIdentifier {
+            product_id,
+            kind: kind.to_string(),
+            value,
+        })
+        .on_conflict((product_identifier::kind, product_identifier::value))
+        .do_nothing()
+        .execute(connection)
+        .expect("Error saving product identifier");
+
+    get_product_id(kind, value).unwrap()
+}
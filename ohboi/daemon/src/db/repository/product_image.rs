@@ -0,0 +1,107 @@
+use lib::db;
+use lib::diesel::prelude::*;
+use lib::diesel::result::Error;
+use lib::diesel::{QueryDsl, RunQueryDsl};
+use lib::schema::{product, product_image, product_image_variant};
+
+use crate::db::entity::product_image::{NewProductImage, NewProductImageVariant};
+
+/// Difference hashes of the photos of the product
+pub fn get_hashes(product_id: i32) -> Vec<u64> {
+    let connection = &db::establish_connection();
+
+    product_image::table
+        .filter(product_image::product_id.eq(product_id))
+        .select(product_image::hash)
+        .load::<i64>(connection)
+        .expect("Error loading product image hashes")
+        .into_iter()
+        .map(from_db_hash)
+        .collect()
+}
+
+/// Images of the product stored before the photos were hashed, none of the variants is such an image
+pub fn get_unhashed_images(product_id: i32) -> Vec<String> {
+    let connection = &db::establish_connection();
+
+    let images = product::table
+        .find(product_id)
+        .select(product::images)
+        .first::<Option<Vec<String>>>(connection)
+        .optional()
+        .expect("Error loading product images")
+        .flatten()
+        .unwrap_or_default();
+    let variants = product_image_variant::table
+        .inner_join(product_image::table)
+        .filter(product_image::product_id.eq(product_id))
+        .select(product_image_variant::file_path)
+        .load::<String>(connection)
+        .expect("Error loading product image variants");
+
+    images
+        .into_iter()
+        .filter(|file_path| !variants.contains(file_path))
+        .collect()
+}
+
+/// Saves the photo with its variants unless the product already has the same photo.
+/// Product is locked, so concurrent uploads of the same photo are not saved twice.
+/// Returns the id of the saved image.
+pub fn create_unless_duplicate<F>(
+    new_image: &NewProductImage,
+    variants: Vec<NewProductImageVariant>,
+    is_duplicate: F,
+) -> Option<i32>
+where
+    F: Fn(u64) -> bool,
+{
+    let connection = &db::establish_connection();
+
+    connection
+        .transaction::<_, Error, _>(|| {
+            product::table
+                .find(new_image.product_id)
+                .select(product::id)
+                .for_update()
+                .first::<i32>(connection)?;
+
+            let duplicate = product_image::table
+                .filter(product_image::product_id.eq(new_image.product_id))
+                .select(product_image::hash)
+                .load::<i64>(connection)?
+                .into_iter()
+                .any(|hash| is_duplicate(from_db_hash(hash)));
+            if duplicate {
+                return Ok(None);
+            }
+
+            let image_id = diesel::insert_into(product_image::table)
+                .values(new_image)
+                .returning(product_image::id)
+                .get_result::<i32>(connection)?;
+
+            let variants: Vec<NewProductImageVariant> = variants
+                .into_iter()
+                .map(|variant| NewProductImageVariant {
+                    image_id,
+                    ..variant
+                })
+                .collect();
+            diesel::insert_into(product_image_variant::table)
+                .values(&variants)
+                .execute(connection)?;
+
+            Ok(Some(image_id))
+        })
+        .expect("Error saving product image")
+}
+
+/// Hash is stored as signed bigint, bits are kept as they are
+pub fn to_db_hash(hash: u64) -> i64 {
+    i64::from_ne_bytes(hash.to_ne_bytes())
+}
+
+fn from_db_hash(hash: i64) -> u64 {
+    u64::from_ne_bytes(hash.to_ne_bytes())
+}
@@ -0,0 +1,46 @@
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+
+use lib::db;
+use lib::diesel::prelude::*;
+use lib::diesel::RunQueryDsl;
+use lib::schema::product_match_candidate;
+
+use crate::db::entity::product_match_candidate::{MatchReason, NewProductMatchCandidate};
+use crate::service::product_matching::MatchCandidate;
+
+/// Puts products which may be the same as the given one to the review queue
+pub fn create_many_if_not_exists(
+    product_id: i32,
+    candidates: &[MatchCandidate],
+    reason: MatchReason,
+) {
+    let connection = &db::establish_connection();
+    let now = Utc::now().naive_utc();
+
+    let new_candidates: Vec<NewProductMatchCandidate> = candidates
+        .iter()
+        .filter(|candidate| candidate.product_id != product_id)
+        .map(|candidate| NewProductMatchCandidate {
+            product_id,
+            candidate_product_id: candidate.product_id,
+            score: BigDecimal::from(candidate.score),
+            reason: reason.to_string(),
+            updated_at: &now,
+        })
+        .collect();
+
+    if new_candidates.is_empty() {
+        return;
+    }
+
+    diesel::insert_into(product_match_candidate::table)
+        .values(&new_candidates)
+        .on_conflict((
+            product_match_candidate::product_id,
+            product_match_candidate::candidate_product_id,
+        ))
+        .do_nothing()
+        .execute(connection)
+        .expect("Error saving product match candidates");
+}
@@ -0,0 +1,133 @@
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::Utc;
+
+use lib::db;
+use lib::diesel::prelude::*;
+use lib::diesel::{QueryDsl, RunQueryDsl};
+use lib::schema::product_variant;
+
+use crate::db::entity::product::Product;
+use crate::db::entity::product_variant::{NewProductVariant, ProductVariant};
+use crate::dto::parsed_product::{InternationalParsedProduct, ParsedVariant};
+
+pub fn create_if_not_exists(
+    product: &Product,
+    parsed_product: &InternationalParsedProduct,
+) -> ProductVariant {
+    let existed_variant = get_variant(product.id, &parsed_product.variant);
+
+    existed_variant.map_or_else(
+        || create(product, parsed_product),
+        |current_variant| {
+            if parsed_product.available && !current_variant.enabled {
+                enable_variant(current_variant.id);
+            }
+
+            current_variant
+        },
+    )
+}
+
+pub fn update_price_range_if_needed(variant: &ProductVariant, new_price: f64) {
+    use lib::schema::product_variant::dsl::{highest_price, id, lowest_price, updated_at};
+
+    let connection = &db::establish_connection();
+    let now = Utc::now();
+    let current_lowest_price = variant.lowest_price.to_f64().unwrap();
+    let current_highest_price = variant.highest_price.to_f64().unwrap();
+
+    let should_update_lowest_price =
+        new_price.lt(&current_lowest_price) || current_lowest_price.eq(&0.0);
+    let should_update_highest_price = new_price.gt(&current_highest_price);
+
+    if !should_update_lowest_price && !should_update_highest_price {
+        return;
+    }
+
+    let new_lowest_price = if should_update_lowest_price {
+        new_price
+    } else {
+        current_lowest_price
+    };
+    let new_highest_price = if should_update_highest_price {
+        new_price
+    } else {
+        current_highest_price
+    };
+
+    diesel::update(product_variant::table.filter(id.eq(variant.id)))
+        .set((
+            lowest_price.eq(BigDecimal::from(new_lowest_price)),
+            highest_price.eq(BigDecimal::from(new_highest_price)),
+            updated_at.eq(&now.naive_utc()),
+        ))
+        .execute(connection)
+        .expect("Failed to update variant price");
+}
+
+/// Variant without some attribute is a different variant than the one with it
+fn get_variant(expected_product_id: i32, variant: &ParsedVariant) -> Option<ProductVariant> {
+    use lib::schema::product_variant::dsl::{built_in_memory_gb, color, product_id, ram_gb};
+
+    let connection = &db::establish_connection();
+
+    let mut target = product_variant::table
+        .filter(product_id.eq(expected_product_id))
+        .into_boxed();
+    target = match &variant.color {
+        Some(v) => target.filter(color.eq(v.as_str())),
+        None => target.filter(color.is_null()),
+    };
+    target = match variant.built_in_memory_gb {
+        Some(v) => target.filter(built_in_memory_gb.eq(v)),
+        None => target.filter(built_in_memory_gb.is_null()),
+    };
+    target = match variant.ram_gb {
+        Some(v) => target.filter(ram_gb.eq(v)),
+        None => target.filter(ram_gb.is_null()),
+    };
+
+    target
+        .first::<ProductVariant>(connection)
+        .optional()
+        .expect("Error loading product variant")
+}
+
+fn create(product: &Product, parsed_product: &InternationalParsedProduct) -> ProductVariant {
+    let connection = &db::establish_connection();
+    let now = Utc::now();
+
+    let new_variant = NewProductVariant {
+        product_id: product.id,
+        color: parsed_product.variant.color.as_deref(),
+        built_in_memory_gb: parsed_product.variant.built_in_memory_gb,
+        ram_gb: parsed_product.variant.ram_gb,
+        lowest_price: BigDecimal::from(parsed_product.price),
+        highest_price: BigDecimal::from(parsed_product.price),
+        enabled: parsed_product.available,
+        updated_at: &now.naive_utc(),
+    };
+
+    let insert_result = diesel::insert_into(product_variant::table)
+        .values(&new_variant)
+        .get_result(connection);
+
+    if let Ok(variant) = insert_result {
+        variant
+    } else {
+        // Created concurrently by another parser
+        get_variant(product.id, &parsed_product.variant).unwrap()
+    }
+}
+
+fn enable_variant(variant_id: i32) {
+    use lib::schema::product_variant::dsl::{enabled, id, updated_at};
+
+    let connection = &db::establish_connection();
+    let now = Utc::now();
+
+    diesel::update(product_variant::table.filter(id.eq(variant_id)))
+        .set((enabled.eq(true), updated_at.eq(&now.naive_utc())))
+        .execute(connection)
+        .expect("Failed to enable product variant");
+}
@@ -0,0 +1,58 @@
+use lib::db;
+use lib::diesel::prelude::*;
+use lib::diesel::{QueryDsl, RunQueryDsl};
+use lib::schema::raw_page;
+
+use crate::db::entity::raw_page::{NewRawPage, RawPageKind};
+
+pub fn create(new_page: &NewRawPage) {
+    let connection = &db::establish_connection();
+
+    diesel::insert_into(raw_page::table)
+        .values(new_page)
+        .execute(connection)
+        .expect("Error saving raw page");
+}
+
+/// `(external_id, blob_key)` of the latest archived details page of every offer of the source
+pub fn get_latest_details_pages(source_id: i32, limit: i64) -> Vec<(String, String)> {
+    let connection = &db::establish_connection();
+
+    raw_page::table
+        .filter(raw_page::source_id.eq(source_id))
+        .filter(raw_page::kind.eq(RawPageKind::Details.to_string()))
+        .distinct_on(raw_page::external_id)
+        .order((raw_page::external_id, raw_page::created_at.desc()))
+        .select((raw_page::external_id, raw_page::blob_key))
+        .limit(limit)
+        .load::<(String, String)>(connection)
+        .expect("Error loading raw pages")
+}
+
+/// `(id, blob_key)` of the archived pages of the offer except the latest `keep` ones
+pub fn get_outdated_pages(
+    source_id: i32,
+    kind: RawPageKind,
+    external_id: &str,
+    keep: i64,
+) -> Vec<(i32, String)> {
+    let connection = &db::establish_connection();
+
+    raw_page::table
+        .filter(raw_page::source_id.eq(source_id))
+        .filter(raw_page::kind.eq(kind.to_string()))
+        .filter(raw_page::external_id.eq(external_id))
+        .order(raw_page::created_at.desc())
+        .offset(keep)
+        .select((raw_page::id, raw_page::blob_key))
+        .load::<(i32, String)>(connection)
+        .expect("Error loading raw pages")
+}
+
+pub fn delete_many(ids: &[i32]) {
+    let connection = &db::establish_connection();
+
+    diesel::delete(raw_page::table.filter(raw_page::id.eq_any(ids)))
+        .execute(connection)
+        .expect("Error deleting raw pages");
+}
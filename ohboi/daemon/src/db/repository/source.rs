@@ -1,8 +1,11 @@
+use chrono::Utc;
 use inflector::Inflector;
 
 use lib::db;
 use lib::diesel::prelude::*;
-use crate::db::entity::source::{Source, SourceName};
+use lib::my_enum::CurrencyEnum;
+
+use crate::db::entity::source::{NewSource, Source, SourceName};
 
 pub fn get_source(name: SourceName) -> Source {
     use lib::schema::source::dsl::{site_name, source};
@@ -16,4 +19,33 @@ pub fn get_source(name: SourceName) -> Source {
         .expect("Cannot load source");
 
     results.into_iter().next().unwrap()
-}
\ No newline at end of file
+}
+
+pub fn create_if_not_exists(name: SourceName, source_logo: &str, source_currency: CurrencyEnum) {
+    use lib::schema::source::dsl::{site_name, source};
+    let connection = &db::establish_connection();
+    let name = name.to_string().to_snake_case();
+
+    let exists: i64 = source
+        .filter(site_name.eq(&name))
+        .count()
+        .get_result(connection)
+        .expect("Cannot count sources");
+
+    if exists == 0 {
+        let now = Utc::now();
+        let new_source = NewSource {
+            site_name: &name,
+            logo: source_logo,
+            currency: source_currency,
+            enabled: true,
+            created_at: &now.naive_utc(),
+            updated_at: &now.naive_utc(),
+        };
+
+        diesel::insert_into(source)
+            .values(&new_source)
+            .execute(connection)
+            .expect("Failed to create source");
+    }
+}
@@ -3,14 +3,17 @@ use chrono::Utc;
 use lib::diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl};
 
 use lib::db;
+use lib::db::repository::price_range::disable_variants_without_live_offers;
 use crate::db::entity::product::Product;
+use crate::db::entity::product_variant::ProductVariant;
 use crate::db::entity::source::SourceName;
 use crate::db::entity::source_product::{NewSourceProduct, SourceProduct};
 use crate::db::repository::product::update_price_range_if_needed;
+use crate::db::repository::product_variant;
 use crate::db::repository::source::get_source;
 use crate::db::repository::source_product_price_history::add_to_history_if_not_exists;
 use crate::dto::parsed_product::InternationalParsedProduct;
-use lib::schema::source_product;
+use lib::schema::{product, source_product};
 
 pub fn get_by_source_and_external_id(source: SourceName, expected_external_id: &str) -> Option<SourceProduct> {
     use lib::schema::source_product::dsl::{external_id, source_id, source_product};
@@ -31,7 +34,49 @@ pub fn get_by_source_and_external_id(source: SourceName, expected_external_id: &
     results.into_iter().next()
 }
 
-pub fn link_to_product(product: &Product, parsed_product: &InternationalParsedProduct, source: SourceName) {
+/// Price in the source currency, selected by the column since `SourceProduct` is queried by position
+fn get_original_price(source_product_id: i32) -> BigDecimal {
+    let connection = &db::establish_connection();
+
+    source_product::table
+        .find(source_product_id)
+        .select(source_product::original_price)
+        .first::<BigDecimal>(connection)
+        .expect("Error loading original price")
+}
+
+/// What linking has changed in the offer
+pub enum LinkOutcome {
+    New,
+    /// Price in the source currency, availability or product of the offer has changed
+    Updated,
+    Unchanged,
+}
+
+pub fn link_to_product(
+    product: &Product,
+    variant: &ProductVariant,
+    parsed_product: &InternationalParsedProduct,
+    source: SourceName,
+) -> LinkOutcome {
+    let existing_link = get_by_source_and_external_id(source, &parsed_product.external_id);
+    // Variant which the offer leaves may have no live offers anymore
+    let left_variant_product_id = existing_link
+        .as_ref()
+        .filter(|existing| existing.enabled && (existing.variant_id != variant.id || !parsed_product.available))
+        .map(|existing| existing.product_id);
+    let outcome = match existing_link {
+        None => LinkOutcome::New,
+        Some(existing)
+            if existing.product_id == product.id
+                && existing.variant_id == variant.id
+                && existing.enabled == parsed_product.available
+                && get_original_price(existing.id) == BigDecimal::from(parsed_product.original_price) =>
+        {
+            LinkOutcome::Unchanged
+        }
+        Some(_) => LinkOutcome::Updated,
+    };
     let source = get_source(source);
 
     let now = Utc::now();
@@ -43,12 +88,73 @@ pub fn link_to_product(product: &Product, parsed_product: &InternationalParsedPr
         price: BigDecimal::from(parsed_product.price),
         updated_at: &now.naive_utc(),
         external_id: &parsed_product.external_id,
+        variant_id: variant.id,
     };
 
     create_if_not_exists(&new_link);
+    if let Some(left_product_id) = left_variant_product_id {
+        disable_variants_without_live_offers(&db::establish_connection(), left_product_id)
+            .expect("Failed to disable variants without offers");
+    }
     update_price_range_if_needed(product.id, parsed_product.price);
+    product_variant::update_price_range_if_needed(variant, parsed_product.price);
 
     add_to_history_if_not_exists(&new_link);
+
+    outcome
+}
+
+/// Enabled offers of the source which are linked to the products of the category
+pub fn count_enabled_in_category(source: SourceName, category_id: i32) -> i64 {
+    let connection = &db::establish_connection();
+    let source = get_source(source);
+
+    source_product::table
+        .filter(source_product::source_id.eq(source.id))
+        .filter(source_product::enabled.eq(true))
+        .filter(
+            source_product::product_id.eq_any(
+                product::table
+                    .select(product::id)
+                    .filter(product::category.eq(category_id)),
+            ),
+        )
+        .count()
+        .get_result(connection)
+        .expect("Error counting source products")
+}
+
+/// Disables enabled offers of the category which are not among the seen ones, returns ids of their products
+pub fn disable_unseen_in_category(
+    source: SourceName,
+    category_id: i32,
+    seen_external_ids: &[&str],
+) -> Vec<i32> {
+    let connection = &db::establish_connection();
+    let source = get_source(source);
+
+    let mut product_ids: Vec<i32> = diesel::update(
+        source_product::table
+            .filter(source_product::source_id.eq(source.id))
+            .filter(source_product::enabled.eq(true))
+            .filter(
+                source_product::product_id.eq_any(
+                    product::table
+                        .select(product::id)
+                        .filter(product::category.eq(category_id)),
+                ),
+            )
+            .filter(source_product::external_id.ne_all(seen_external_ids.to_vec())),
+    )
+    .set(source_product::enabled.eq(false))
+    .returning(source_product::product_id)
+    .get_results(connection)
+    .expect("Error disabling unseen source products");
+
+    product_ids.sort_unstable();
+    product_ids.dedup();
+
+    product_ids
 }
 
 fn create_if_not_exists(new_product: &NewSourceProduct) {
@@ -61,7 +167,8 @@ fn create_if_not_exists(new_product: &NewSourceProduct) {
         .set((
             source_product::price.eq(&new_product.price),
             source_product::updated_at.eq(&new_product.updated_at),
-            source_product::enabled.eq(&new_product.enabled)
+            source_product::enabled.eq(&new_product.enabled),
+            source_product::variant_id.eq(&new_product.variant_id),
         ))
         .execute(connection)
         .expect("Error saving new source product");
@@ -1,11 +1,38 @@
+use serde::{Deserialize, Serialize};
+
 use lib::dto::characteristic::TypedCharacteristic;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct LocalParsedProduct {
     pub title: String,
     pub price: f64,
     pub available: bool,
     pub external_id: String,
+    #[serde(default)]
+    pub variant: ParsedVariant,
+    #[serde(default, skip_serializing_if = "ProductIdentifiers::is_empty")]
+    pub identifiers: ProductIdentifiers,
+}
+
+/// Attributes which tell apart offers of the same product, e.g. colors or memory sizes.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParsedVariant {
+    pub color: Option<String>,
+    pub built_in_memory_gb: Option<i32>,
+    pub ram_gb: Option<i32>,
+}
+
+/// Codes of the product which don't depend on the title, used to match offers of different sources.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProductIdentifiers {
+    pub gtin: Option<String>,
+    pub model: Option<String>,
+}
+
+impl ProductIdentifiers {
+    pub fn is_empty(&self) -> bool {
+        self.gtin.is_none() && self.model.is_none()
+    }
 }
 
 #[derive(Debug)]
@@ -15,6 +42,8 @@ pub struct InternationalParsedProduct {
     pub original_price: f64,
     pub available: bool,
     pub external_id: String,
+    pub variant: ParsedVariant,
+    pub identifiers: ProductIdentifiers,
 }
 
 #[derive(Debug)]
@@ -14,11 +14,19 @@ extern crate diesel;
 extern crate lazy_static;
 
 use clap::arg_enum;
+use lib::db::repository::product_admin::{merge_products, split_source_product};
 use structopt::StructOpt;
 
 use crate::db::repository::sync_characteristic_enum;
+use crate::parse::crawler::{
+    get_crawler_by_site_name, load_characteristic_dictionaries, register_declarative_sources,
+};
+use crate::parse::fixture::{record_fixture, rerecord_fixture};
+use crate::parse::reparse_archived_pages;
 use crate::queue::declare::declare_all_queues;
 use crate::queue::launch::{launch_consumer, launch_producer};
+use crate::service::crawl_run::print_crawl_runs;
+use crate::service::unknown_characteristic::print_unknown_characteristics;
 use crate::settings::Settings;
 
 mod db;
@@ -28,14 +36,46 @@ mod queue;
 mod service;
 mod settings;
 
+/// Recorded in the product audit for the operations made from the command line
+const ADMIN_CLI_ACTOR: &str = "cli";
+
 #[derive(StructOpt, Debug)]
 struct Cli {
-    #[structopt(possible_values = & ["consumer", "producer", "queue_config", "characteristic_enum_sync"], case_insensitive = true)]
+    #[structopt(possible_values = & ["consumer", "producer", "queue_config", "characteristic_enum_sync", "record_fixture", "merge_products", "split_source_product", "report", "reparse", "unknown_characteristics"], case_insensitive = true)]
     worker_type: String,
     #[structopt(short, possible_values = & ConsumerName::variants(), case_insensitive = true, required_if("worker-type", "consumer"))]
     consumer_name: Option<ConsumerName>,
     #[structopt(short, possible_values = & ProducerName::variants(), case_insensitive = true, required_if("worker-type", "producer"))]
     producer_name: Option<ProducerName>,
+    /// `site_name` of the source, `report` and `unknown_characteristics` show all sources without it
+    #[structopt(
+        long,
+        required_if("worker-type", "record_fixture"),
+        required_if("worker-type", "reparse")
+    )]
+    source: Option<String>,
+    /// `record_fixture` records the existing fixture again from its url without `url` and `fixture_kind`
+    #[structopt(long, requires("fixture-kind"))]
+    url: Option<String>,
+    #[structopt(long, required_if("worker-type", "record_fixture"))]
+    fixture_name: Option<String>,
+    #[structopt(long, possible_values = & FixtureKind::variants(), case_insensitive = true, requires("url"))]
+    fixture_kind: Option<FixtureKind>,
+    /// Product which stays after the merge
+    #[structopt(long, required_if("worker-type", "merge_products"))]
+    target_product_id: Option<i32>,
+    /// Product which is merged into the target one and removed
+    #[structopt(long, required_if("worker-type", "merge_products"))]
+    merged_product_id: Option<i32>,
+    #[structopt(long, required_if("worker-type", "split_source_product"))]
+    source_product_id: Option<i32>,
+    /// Title of the new product
+    #[structopt(long, required_if("worker-type", "split_source_product"))]
+    title: Option<String>,
+    /// Amount of the latest crawl runs in the report or of the unknown characteristics (20 by default),
+    /// or of the archived pages to reparse (all by default)
+    #[structopt(long)]
+    limit: Option<i64>,
 }
 arg_enum! {
     #[derive(Debug, Copy, Clone)]
@@ -56,6 +96,14 @@ arg_enum! {
     }
 }
 
+arg_enum! {
+    #[derive(Debug, Copy, Clone)]
+    pub enum FixtureKind {
+        Listing,
+        Details,
+    }
+}
+
 #[tokio::main]
 async fn main() {
     std::env::set_var("RUST_LOG", "daemon");
@@ -73,6 +121,73 @@ async fn main() {
         return;
     }
 
+    if args.worker_type == "record_fixture" {
+        let source = args.source.expect("Failed to get source.");
+        let fixture_name = args.fixture_name.expect("Failed to get fixture name.");
+        let recorded = match (args.url, args.fixture_kind) {
+            (Some(url), Some(kind)) => record_fixture(&source, &url, &fixture_name, kind).await,
+            _ => rerecord_fixture(&source, &fixture_name).await,
+        };
+        let path = recorded.expect("Failed to record fixture.");
+        log::info!("Fixture recorded to {:?}", path);
+        return;
+    }
+
+    if args.worker_type == "merge_products" {
+        let target_id = args.target_product_id.expect("Failed to get target product id.");
+        let merged_id = args.merged_product_id.expect("Failed to get merged product id.");
+
+        match merge_products(target_id, merged_id, ADMIN_CLI_ACTOR) {
+            Ok(()) => log::info!("Product {} merged into {}", merged_id, target_id),
+            Err(e) => log::error!("Failed to merge products: {}", e),
+        }
+        return;
+    }
+
+    if args.worker_type == "split_source_product" {
+        let source_product_id = args.source_product_id.expect("Failed to get source product id.");
+        let title = args.title.expect("Failed to get title.");
+
+        match split_source_product(source_product_id, &title, ADMIN_CLI_ACTOR) {
+            Ok(product_id) => log::info!(
+                "Source product {} split off into product {}",
+                source_product_id,
+                product_id
+            ),
+            Err(e) => log::error!("Failed to split source product: {}", e),
+        }
+        return;
+    }
+
+    if args.worker_type == "report" {
+        print_crawl_runs(args.source.as_deref(), args.limit.unwrap_or(20));
+        return;
+    }
+
+    if args.worker_type == "unknown_characteristics" {
+        print_unknown_characteristics(args.source.as_deref(), args.limit.unwrap_or(20));
+        return;
+    }
+
+    register_declarative_sources();
+    load_characteristic_dictionaries();
+
+    if args.worker_type == "reparse" {
+        let site_name = args.source.expect("Failed to get source.");
+        let crawler =
+            get_crawler_by_site_name(&site_name).expect(&format!("Unknown source: {}", site_name));
+
+        let summary = reparse_archived_pages(crawler, args.limit.unwrap_or(i64::MAX)).await;
+        log::info!(
+            "[{}] Reparsed pages: {} updated, {} skipped, {} failed",
+            site_name,
+            summary.updated,
+            summary.skipped,
+            summary.failed
+        );
+        return;
+    }
+
     if args.worker_type == "producer" {
         let name = args.producer_name.expect("Failed to daemon producer name.");
 
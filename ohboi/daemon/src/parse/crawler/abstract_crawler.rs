@@ -1,6 +1,6 @@
 use std::sync::Mutex;
 
-use futures::future::{err, join_all, ok};
+use futures::future::{join_all, ready};
 use futures::FutureExt;
 use inflector::Inflector;
 use maplit::btreemap;
@@ -14,10 +14,16 @@ use lib::my_enum::CurrencyEnum;
 
 use crate::db::entity::category::CategorySlug;
 use crate::db::entity::source::SourceName;
-use crate::dto::parsed_product::{AdditionalParsedProductInfo, LocalParsedProduct};
+use crate::dto::parsed_product::{
+    AdditionalParsedProductInfo, LocalParsedProduct, ProductIdentifiers,
+};
+use crate::parse::crawler::structured_data::{extract_structured_product, StructuredProduct};
+use crate::parse::util::variant::split_variant;
 use crate::queue::postpone::postpone_image_parsing;
-use crate::service::cloud::upload_image_to_cloud;
+use crate::service::cloud::{upload_image_to_cloud, ImageUpload};
+use crate::service::crawl_run::record;
 use crate::service::html_cleaner::clean_html;
+use crate::service::request::RequestBudget;
 use crate::ConsumerName;
 
 struct UploadImageLaterMessage(String, String, String, SourceName);
@@ -43,6 +49,60 @@ pub trait Crawler: Sync + Send {
         external_id: &str,
     ) -> Option<AdditionalParsedProductInfo>;
 
+    /// Amount of category pages requested at once, they are still processed one by one.
+    fn get_concurrent_pages(&self) -> usize {
+        1
+    }
+
+    /// Limits requests to the site, Crawl-delay of its robots.txt can make it even stricter.
+    fn get_request_budget(&self) -> RequestBudget {
+        RequestBudget::default()
+    }
+
+    /// Names of the characteristic dictionaries, the first one which knows the title is used.
+    fn get_characteristic_dictionaries(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Sitemap (or sitemap index) which is used to discover products instead of category pages.
+    fn get_sitemap_url(&self) -> Option<String> {
+        None
+    }
+
+    /// Urls of the category products in the sitemap, category is parsed page by page without it.
+    fn get_sitemap_product_pattern(&self, _category: CategorySlug) -> Option<Regex> {
+        None
+    }
+
+    fn get_external_id_from_url(&self, url: &str) -> String {
+        url.strip_prefix(&self.get_site_base())
+            .unwrap_or(url)
+            .to_string()
+    }
+
+    /// Basic info of the product which was found without category page (e.g. in sitemap).
+    fn extract_product_from_details(
+        &self,
+        document: &Html,
+        external_id: &str,
+    ) -> Option<LocalParsedProduct> {
+        let product =
+            extract_structured_product(document, &self.get_additional_info_url(external_id))?;
+        let (title, variant) = split_variant(&product.title?);
+
+        Some(LocalParsedProduct {
+            title,
+            price: product.price?,
+            available: product.available.unwrap_or(false),
+            external_id: external_id.to_string(),
+            variant,
+            identifiers: ProductIdentifiers {
+                gtin: product.gtin,
+                model: product.mpn,
+            },
+        })
+    }
+
     fn abstract_extract_image_urls(
         &self,
         image_nodes: Select,
@@ -91,6 +151,7 @@ pub trait Crawler: Sync + Send {
         let description_node = document.select(&selector).next();
 
         if description_node.is_none() {
+            record(|run| run.missing_description += 1);
             let message = format!(
                 "description_node not found! [{source}]",
                 source = self.get_source()
@@ -153,10 +214,54 @@ pub trait Crawler: Sync + Send {
 
         Some(buy_button_node.is_some() && unavailable_button_node.is_none())
     }
+
+    /// Description from schema.org data of the page, falls back to the selector.
+    fn structured_or_abstract_description(
+        &self,
+        document: &Html,
+        structured: Option<&StructuredProduct>,
+        selector: Selector,
+        re: &Regex,
+    ) -> Option<String> {
+        structured
+            .and_then(StructuredProduct::description_html)
+            .or_else(|| self.abstract_extract_description(document, selector, re))
+    }
+
+    /// Availability from schema.org data of the page, falls back to the selectors.
+    fn structured_or_abstract_availability(
+        &self,
+        document: &Html,
+        structured: Option<&StructuredProduct>,
+        available_selector: Selector,
+        unavailable_selector: Selector,
+    ) -> Option<bool> {
+        structured
+            .and_then(|product| product.available)
+            .or_else(|| {
+                self.abstract_parse_availability(document, available_selector, unavailable_selector)
+            })
+    }
+
+    /// Images from schema.org data of the page, falls back to the selector.
+    fn structured_or_abstract_image_urls(
+        &self,
+        document: &Html,
+        structured: Option<&StructuredProduct>,
+        images_selector: &Selector,
+        lazy_attribute: &str,
+    ) -> Vec<String> {
+        match structured {
+            Some(product) if !product.image_urls.is_empty() => product.image_urls.clone(),
+            _ => self.abstract_extract_image_urls(document.select(images_selector), lazy_attribute),
+        }
+    }
 }
 
+/// Returns paths of the uploaded images, photos which the product already has are skipped
 pub async fn upload_extracted_images(
     source: SourceName,
+    product_id: i32,
     image_urls: Vec<String>,
     external_id: &str,
     base: &str,
@@ -174,7 +279,6 @@ pub async fn upload_extracted_images(
         },
     );
 
-    let mut uploaded_urls: Vec<String> = vec![];
     let mut uploads: Vec<_> = vec![];
 
     let upload_later = Mutex::new(vec![]);
@@ -189,29 +293,27 @@ pub async fn upload_extracted_images(
         let url: String = [base, &image_url].concat();
 
         uploads.push(
-            upload_image_to_cloud(file_path.clone(), url.clone()).then(|success| {
-                if success {
-                    ok(file_path)
-                } else {
-                    upload_later.lock().unwrap().push(UploadImageLaterMessage(
-                        file_path.clone(),
-                        url,
-                        external_id.to_string(),
-                        source,
-                    ));
-                    err(file_path)
-                }
-            }),
+            upload_image_to_cloud(product_id, source, file_path.clone(), url.clone()).then(
+                |result| match result {
+                    Ok(ImageUpload::Stored(uploaded_path)) => ready(Some(uploaded_path)),
+                    Ok(ImageUpload::Duplicate) => ready(None),
+                    // Not an image or not found, there is no sense to try again
+                    Err(e) if e.is_permanent() => ready(None),
+                    Err(_) => {
+                        upload_later.lock().unwrap().push(UploadImageLaterMessage(
+                            file_path,
+                            url,
+                            external_id.to_string(),
+                            source,
+                        ));
+                        ready(None)
+                    }
+                },
+            ),
         );
     }
 
-    let uploaded_images = join_all(uploads).await;
-
-    for filename in uploaded_images {
-        if filename.is_ok() {
-            uploaded_urls.push(filename.unwrap());
-        }
-    }
+    let uploaded_urls: Vec<String> = join_all(uploads).await.into_iter().flatten().collect();
 
     for message in upload_later.lock().unwrap().iter() {
         postpone_image_parsing(
@@ -242,6 +344,7 @@ pub fn get_html_nodes<'result>(
     let available_node = element.select(&selectors.available).next();
     let unavailable_node = element.select(&selectors.unavailable).next();
     let mut valid = true;
+    record(|run| run.items += 1);
     let context = ReportingContext {
         executor: &ConsumerName::ParseCategory,
         action: "get_html_nodes",
@@ -250,17 +353,20 @@ pub fn get_html_nodes<'result>(
     if id_node.is_none() {
         let message = format!("id_node not found! [{source}]", source = source);
         error_reporting::warning(message.as_str(), &context);
+        record(|run| run.missing_id += 1);
         valid = false;
     }
 
     if title_node.is_none() {
         let message = format!("title_node not found! [{source}]", source = source);
         error_reporting::warning(message.as_str(), &context);
+        record(|run| run.missing_title += 1);
         valid = false;
     }
     if price_node.is_none() {
         let message = format!("price_node not found! [{source}]", source = source);
         error_reporting::warning(message.as_str(), &context);
+        record(|run| run.missing_price += 1);
         valid = false;
     }
 
@@ -270,9 +376,14 @@ pub fn get_html_nodes<'result>(
             source = source
         );
         error_reporting::warning(message.as_str(), &context);
+        record(|run| run.missing_availability += 1);
         valid = false;
     }
 
+    if !valid {
+        record(|run| run.warnings += 1);
+    }
+
     if valid {
         Some(ProductHtmlNodes {
             id: id_node.unwrap(),
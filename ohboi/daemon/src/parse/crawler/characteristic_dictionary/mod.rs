@@ -0,0 +1,359 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use lib::db::entity::unknown_characteristic::UnknownCharacteristicKind;
+use lib::dto::characteristic::TypedCharacteristic;
+
+use crate::db::entity::source::SourceName;
+use crate::parse::crawler::characteristic_dictionary::value::{parse_value, validate, ValueParser};
+use crate::parse::crawler::characteristic_parser::CharacteristicParsingContext;
+use crate::parse::crawler::{get_all_crawlers, Crawler};
+use crate::service::crawl_run::record;
+use crate::service::unknown_characteristic::report_unknown_characteristic;
+#[cfg(not(test))]
+use crate::SETTINGS;
+
+mod value;
+
+lazy_static! {
+    static ref DICTIONARIES: HashMap<String, CharacteristicDictionary> =
+        load_dictionaries(&get_dictionaries_dir()).unwrap_or_else(|e| panic!("{}", e));
+}
+
+#[derive(Debug, Deserialize)]
+struct DictionarySpec {
+    /// Titles which are known, but not needed
+    #[serde(default)]
+    ignored: Vec<String>,
+    #[serde(default, rename = "characteristic")]
+    characteristics: Vec<CharacteristicSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CharacteristicSpec {
+    /// Name of the characteristic, e.g. `Width_mm`
+    slug: String,
+    parser: ValueParser,
+    titles: Vec<String>,
+}
+
+struct DictionaryEntry {
+    slug: String,
+    parser: ValueParser,
+}
+
+/// Titles of the characteristics table of some language or source mapped to our characteristics,
+/// see `docs/parse_architecture.md`.
+pub struct CharacteristicDictionary {
+    entries: HashMap<String, DictionaryEntry>,
+    ignored: HashSet<String>,
+}
+
+impl CharacteristicDictionary {
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let spec: DictionarySpec = toml::from_str(content).map_err(|e| e.to_string())?;
+        let mut entries: HashMap<String, DictionaryEntry> = HashMap::new();
+
+        for characteristic in spec.characteristics {
+            validate(&characteristic.slug, characteristic.parser)?;
+
+            for title in characteristic.titles {
+                if entries.contains_key(&title) {
+                    return Err(format!("Duplicated title '{}'", title));
+                }
+
+                entries.insert(
+                    title,
+                    DictionaryEntry {
+                        slug: characteristic.slug.clone(),
+                        parser: characteristic.parser,
+                    },
+                );
+            }
+        }
+
+        let ignored: HashSet<String> = spec.ignored.into_iter().collect();
+        if let Some(title) = ignored.iter().find(|title| entries.contains_key(*title)) {
+            return Err(format!("Title '{}' is both known and ignored", title));
+        }
+
+        Ok(CharacteristicDictionary { entries, ignored })
+    }
+
+    fn knows(&self, title: &str) -> bool {
+        self.entries.contains_key(title) || self.ignored.contains(title)
+    }
+}
+
+/// Loads the dictionaries at the start of the daemon, so an invalid dictionary or the one
+/// which some crawler needs, but which doesn't exist, stops it instead of every parsed page.
+pub fn load_characteristic_dictionaries() {
+    lazy_static::initialize(&DICTIONARIES);
+
+    for crawler in get_all_crawlers() {
+        if let Some(name) = crawler
+            .get_characteristic_dictionaries()
+            .into_iter()
+            .find(|name| !has_dictionary(name))
+        {
+            panic!(
+                "[{}] Unknown characteristic dictionary '{}'",
+                crawler.get_source(),
+                name
+            );
+        }
+    }
+}
+
+/// Parses the rows of the characteristics table with the first of the dictionaries of the crawler which knows the title.
+/// Titles unknown to all of them are saved to the unknown characteristics, so they can be added to the dictionary.
+pub fn parse_characteristics(
+    crawler: &dyn Crawler,
+    characteristics: &[(String, String)],
+    external_id: &str,
+) -> Vec<TypedCharacteristic> {
+    let names = crawler.get_characteristic_dictionaries();
+    let dictionaries: Vec<&CharacteristicDictionary> = names
+        .iter()
+        .map(|name| {
+            DICTIONARIES
+                .get(name)
+                .unwrap_or_else(|| panic!("Unknown characteristic dictionary '{}'", name))
+        })
+        .collect();
+
+    parse_with_dictionaries(
+        crawler.get_source(),
+        &dictionaries,
+        characteristics,
+        external_id,
+    )
+}
+
+pub fn has_dictionary(name: &str) -> bool {
+    DICTIONARIES.contains_key(name)
+}
+
+fn parse_with_dictionaries(
+    source: SourceName,
+    dictionaries: &[&CharacteristicDictionary],
+    characteristics: &[(String, String)],
+    external_id: &str,
+) -> Vec<TypedCharacteristic> {
+    let mut parsed_characteristics: Vec<TypedCharacteristic> = vec![];
+
+    for (title, value) in characteristics {
+        let dictionary = dictionaries.iter().find(|d| d.knows(title));
+
+        match dictionary.map(|d| d.entries.get(title.as_str())) {
+            Some(Some(entry)) => {
+                let context = CharacteristicParsingContext {
+                    title,
+                    external_id,
+                    source,
+                };
+
+                parsed_characteristics.extend(parse_value(
+                    &entry.slug,
+                    entry.parser,
+                    &context,
+                    value,
+                ));
+            }
+            Some(None) => {}
+            None => {
+                record(|run| run.unknown_characteristics += 1);
+                report_unknown_characteristic(
+                    UnknownCharacteristicKind::Title,
+                    title,
+                    value,
+                    external_id,
+                );
+            }
+        }
+    }
+
+    parsed_characteristics
+}
+
+fn load_dictionaries(dir: &str) -> Result<HashMap<String, CharacteristicDictionary>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| {
+        format!(
+            "Characteristic dictionaries can't be read from {}: {}",
+            dir, e
+        )
+    })?;
+
+    let paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "toml"))
+        .collect();
+
+    let mut dictionaries = HashMap::new();
+    for path in paths {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let dictionary = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| CharacteristicDictionary::from_toml(&content))
+            .map_err(|e| {
+                format!(
+                    "Invalid characteristic dictionary {path:?}: {error}",
+                    path = path,
+                    error = e
+                )
+            })?;
+
+        log::info!("Characteristic dictionary {} loaded.", name);
+        dictionaries.insert(name, dictionary);
+    }
+
+    Ok(dictionaries)
+}
+
+#[cfg(not(test))]
+fn get_dictionaries_dir() -> String {
+    SETTINGS.crawlers.dictionaries_dir.clone()
+}
+
+/// Tests run in the directory of the crate, dictionaries are in the root of the repository
+#[cfg(test)]
+fn get_dictionaries_dir() -> String {
+    concat!(env!("CARGO_MANIFEST_DIR"), "/../../characteristics").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::entity::source::SourceName;
+    use crate::parse::crawler::characteristic_dictionary::{
+        has_dictionary, load_dictionaries, parse_with_dictionaries, CharacteristicDictionary,
+    };
+
+    static RU: &str = r#"
+        ignored = ["Цвет"]
+
+        [[characteristic]]
+        slug = "Weight_gr"
+        parser = "quantity"
+        titles = ["Вес", "Вес (г)"]
+
+        [[characteristic]]
+        slug = "NFC"
+        parser = "bool"
+        titles = ["NFC"]
+
+        [[characteristic]]
+        slug = "Autofocus"
+        parser = "bool"
+        titles = ["Автофокус"]
+
+        [[characteristic]]
+        slug = "WifiStandard"
+        parser = "enum_list"
+        titles = ["Wi-Fi"]
+    "#;
+
+    static SHOP: &str = r#"
+        ignored = ["Вес"]
+
+        [[characteristic]]
+        slug = "Model"
+        parser = "string"
+        titles = ["Модель"]
+    "#;
+
+    fn parse(characteristics: &[(&str, &str)]) -> Vec<String> {
+        let ru = CharacteristicDictionary::from_toml(RU).unwrap();
+        let shop = CharacteristicDictionary::from_toml(SHOP).unwrap();
+        let characteristics: Vec<(String, String)> = characteristics
+            .iter()
+            .map(|(title, value)| (title.to_string(), value.to_string()))
+            .collect();
+
+        parse_with_dictionaries(SourceName::MiShopCom, &[&shop, &ru], &characteristics, "_")
+            .iter()
+            .map(|characteristic| format!("{:?}", characteristic))
+            .collect()
+    }
+
+    #[test]
+    fn it_parses_known_titles() {
+        assert_eq!(
+            parse(&[
+                ("Модель", "M2101K6G"),
+                ("Вес (г)", "0,193 кг"),
+                ("NFC", "Да"),
+                ("Автофокус", "Нет"),
+                ("Wi-Fi", "b, g"),
+            ]),
+            vec![
+                "String(Model(\"M2101K6G\"))",
+                "Float(Weight_gr(193.0))",
+                "Bool(NFC(true))",
+                "Bool(Autofocus(false))",
+                "Enum(WifiStandard(B))",
+                "Enum(WifiStandard(G))",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_uses_first_dictionary_which_knows_title() {
+        assert!(parse(&[("Вес", "193"), ("Цвет", "Черный"), ("Объем", "1 л")]).is_empty());
+    }
+
+    #[test]
+    fn it_refuses_invalid_dictionaries() {
+        let wrong_parser = r#"
+            [[characteristic]]
+            slug = "Weight_gr"
+            parser = "months"
+            titles = ["Вес"]
+        "#;
+        let unknown_characteristic = r#"
+            [[characteristic]]
+            slug = "Smell"
+            parser = "string"
+            titles = ["Запах"]
+        "#;
+        let duplicated_title = r#"
+            ignored = ["Вес"]
+
+            [[characteristic]]
+            slug = "Weight_gr"
+            parser = "quantity"
+            titles = ["Вес"]
+        "#;
+
+        assert!(CharacteristicDictionary::from_toml(wrong_parser).is_err());
+        assert!(CharacteristicDictionary::from_toml(unknown_characteristic).is_err());
+        assert!(CharacteristicDictionary::from_toml(duplicated_title).is_err());
+    }
+
+    #[test]
+    fn it_loads_dictionaries_of_repository() {
+        assert!(has_dictionary("ru"));
+        assert!(has_dictionary("uk"));
+        assert!(has_dictionary("mi_shop_com"));
+    }
+
+    #[test]
+    fn it_fails_on_missing_or_invalid_dictionaries() {
+        let dir = std::env::temp_dir().join("ohboi_invalid_dictionaries_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ru.toml"), "[[characteristic]]\nslug = \"Smell\"").unwrap();
+
+        let missing = load_dictionaries(dir.join("missing").to_str().unwrap());
+        let invalid = load_dictionaries(dir.to_str().unwrap());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(missing.is_err());
+        assert!(invalid.is_err());
+    }
+}
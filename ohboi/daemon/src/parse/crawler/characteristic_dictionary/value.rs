@@ -0,0 +1,288 @@
+use regex::Regex;
+use serde::Deserialize;
+use strum::VariantNames;
+
+use lib::dto::characteristic::bool_characteristic::BoolCharacteristic;
+use lib::dto::characteristic::enum_characteristic::EnumCharacteristic;
+use lib::dto::characteristic::float_characteristic::FloatCharacteristic;
+use lib::dto::characteristic::int_characteristic::IntCharacteristic;
+use lib::dto::characteristic::string_characteristic::StringCharacteristic;
+use lib::dto::characteristic::TypedCharacteristic;
+
+use crate::parse::crawler::characteristic_parser::{
+    bool_value, enum_audio_jack_value, enum_battery_type_value, enum_charging_connector_type_value,
+    enum_country_value, enum_display_type_value, enum_internet_connection_technology_value,
+    enum_material_value, enum_memory_card_slot_value, enum_satellite_navigation_value,
+    enum_sim_card_value, enum_wifi_standard_value, float_android_version_value,
+    float_aperture_value, float_diagonal_value, float_ghz_value, float_miui_version_value,
+    float_quantity_value, float_value, float_version_value, int_fps_value, int_guarantee_value,
+    int_hz_value, int_ma_h_value, int_max_memory_card_size_value, int_memory_value, int_mp_value,
+    int_nit_value, int_quantity_value, int_value, multiple_int_value, multiple_parse_and_capture,
+    multiple_string_media_format_value, parse_and_capture, pix_int_value, string_value,
+    CharacteristicParsingContext, Unit,
+};
+
+lazy_static! {
+    static ref NO_DESCRIPTION_RE: Regex = Regex::new(r"(?ms)[A-Za-z./ 0-9\-+–]{2,}").unwrap();
+}
+
+/// How the value of the characteristic is read, one parser of `characteristic_parser` each.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueParser {
+    Float,
+    Version,
+    AndroidVersion,
+    MiuiVersion,
+    Aperture,
+    /// Value with an optional unit, converted to the unit of the characteristic
+    Quantity,
+    Int,
+    IntList,
+    Megapixels,
+    Pixels,
+    Fps,
+    Nits,
+    Hertz,
+    Months,
+    String,
+    Enum,
+    EnumList,
+    /// `Да`/`Нет`, both answers are saved
+    Bool,
+}
+
+/// Checks that the parser produces values of the characteristic
+pub fn validate(slug: &str, parser: ValueParser) -> Result<(), String> {
+    let is_valid = match parser {
+        ValueParser::Float
+        | ValueParser::Version
+        | ValueParser::AndroidVersion
+        | ValueParser::MiuiVersion
+        | ValueParser::Aperture => is_float(slug),
+        ValueParser::Quantity => get_unit(slug).is_some(),
+        ValueParser::Int
+        | ValueParser::IntList
+        | ValueParser::Megapixels
+        | ValueParser::Pixels
+        | ValueParser::Fps
+        | ValueParser::Nits
+        | ValueParser::Hertz
+        | ValueParser::Months => IntCharacteristic::from_name(slug, 0).is_some(),
+        ValueParser::String => StringCharacteristic::from_name(slug, String::new()).is_some(),
+        ValueParser::Enum | ValueParser::EnumList => EnumCharacteristic::VARIANTS.contains(&slug),
+        ValueParser::Bool => BoolCharacteristic::from_name(slug, false).is_some(),
+    };
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Parser {:?} can't be used for characteristic '{}'",
+            parser, slug
+        ))
+    }
+}
+
+pub fn parse_value(
+    slug: &str,
+    parser: ValueParser,
+    context: &CharacteristicParsingContext,
+    value: &str,
+) -> Vec<TypedCharacteristic> {
+    match parser {
+        ValueParser::Float => float(slug, float_value(context, value)),
+        ValueParser::Version => float(slug, float_version_value(context, value)),
+        ValueParser::AndroidVersion => float(slug, float_android_version_value(context, value)),
+        ValueParser::MiuiVersion => float(slug, float_miui_version_value(context, value)),
+        ValueParser::Aperture => float(slug, float_aperture_value(context, value)),
+        ValueParser::Quantity => match slug {
+            "ScreenDiagonal" => float(slug, float_diagonal_value(context, value)),
+            "CPUFrequency_Ghz" => float(slug, float_ghz_value(context, value)),
+            "BatteryCapacity_mA_h" => int(slug, int_ma_h_value(context, value)),
+            "MaxMemoryCardSize_GB" => int(slug, int_max_memory_card_size_value(context, value)),
+            "BuiltInMemory_GB" | "Ram_GB" => int(slug, int_memory_value(context, value)),
+            _ => match get_unit(slug) {
+                Some(unit) if is_float(slug) => {
+                    float(slug, float_quantity_value(context, value, unit))
+                }
+                Some(unit) => int(slug, int_quantity_value(context, value, unit)),
+                None => vec![],
+            },
+        },
+        ValueParser::Int => int(slug, int_value(context, value)),
+        ValueParser::IntList => multiple_int_value(context, value)
+            .into_iter()
+            .flat_map(|v| int(slug, Some(v)))
+            .collect(),
+        ValueParser::Megapixels => int(slug, int_mp_value(context, value)),
+        ValueParser::Pixels => int(slug, pix_int_value(context, value)),
+        ValueParser::Fps => int(slug, int_fps_value(context, value)),
+        ValueParser::Nits => int(slug, int_nit_value(context, value)),
+        ValueParser::Hertz => int(slug, int_hz_value(context, value)),
+        ValueParser::Months => int(slug, int_guarantee_value(context, value)),
+        ValueParser::String => StringCharacteristic::from_name(slug, string_value(value))
+            .map(TypedCharacteristic::String)
+            .into_iter()
+            .collect(),
+        ValueParser::Enum | ValueParser::EnumList => {
+            parse_enum(slug, parser == ValueParser::EnumList, context, value)
+                .into_iter()
+                .map(TypedCharacteristic::Enum)
+                .collect()
+        }
+        ValueParser::Bool => bool_value(context, value)
+            .and_then(|v| BoolCharacteristic::from_name(slug, v))
+            .map(TypedCharacteristic::Bool)
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn parse_enum(
+    slug: &str,
+    is_list: bool,
+    context: &CharacteristicParsingContext,
+    value: &str,
+) -> Vec<EnumCharacteristic> {
+    match slug {
+        "ChargingConnectorType" => capture(
+            is_list,
+            context,
+            value,
+            enum_charging_connector_type_value,
+            EnumCharacteristic::ChargingConnectorType,
+        ),
+        "BatteryType" => capture(
+            is_list,
+            context,
+            value,
+            enum_battery_type_value,
+            EnumCharacteristic::BatteryType,
+        ),
+        "SimCard" => capture(
+            is_list,
+            context,
+            value,
+            enum_sim_card_value,
+            EnumCharacteristic::SimCard,
+        ),
+        "Material" => capture(
+            is_list,
+            context,
+            value,
+            enum_material_value,
+            EnumCharacteristic::Material,
+        ),
+        "DisplayType" => capture(
+            is_list,
+            context,
+            value,
+            enum_display_type_value,
+            EnumCharacteristic::DisplayType,
+        ),
+        "InternetConnectionTechnology" => capture(
+            is_list,
+            context,
+            value,
+            enum_internet_connection_technology_value,
+            EnumCharacteristic::InternetConnectionTechnology,
+        ),
+        "SatelliteNavigation" => capture(
+            is_list,
+            context,
+            value,
+            enum_satellite_navigation_value,
+            EnumCharacteristic::SatelliteNavigation,
+        ),
+        "WifiStandard" => capture(
+            is_list,
+            context,
+            value,
+            enum_wifi_standard_value,
+            EnumCharacteristic::WifiStandard,
+        ),
+        // Description is cut off, `3.5 мм` -> `3.5`
+        "AudioJack" => NO_DESCRIPTION_RE
+            .find(value)
+            .map_or_else(Vec::new, |value| {
+                capture(
+                    is_list,
+                    context,
+                    value.as_str(),
+                    enum_audio_jack_value,
+                    EnumCharacteristic::AudioJack,
+                )
+            }),
+        "ProducingCountry" => capture(
+            is_list,
+            context,
+            value,
+            enum_country_value,
+            EnumCharacteristic::ProducingCountry,
+        ),
+        "MemoryCardSlot" => capture(
+            is_list,
+            context,
+            value,
+            enum_memory_card_slot_value,
+            EnumCharacteristic::MemoryCardSlot,
+        ),
+        "SupportedMediaFormat" => multiple_string_media_format_value(context, value)
+            .into_iter()
+            .map(EnumCharacteristic::SupportedMediaFormat)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn capture<SomeEnum>(
+    is_list: bool,
+    context: &CharacteristicParsingContext,
+    value: &str,
+    parser: fn(&str) -> Option<SomeEnum>,
+    characteristic: fn(SomeEnum) -> EnumCharacteristic,
+) -> Vec<EnumCharacteristic> {
+    let values = if is_list {
+        multiple_parse_and_capture(context, value, parser)
+    } else {
+        parse_and_capture(context, value, parser)
+            .into_iter()
+            .collect()
+    };
+
+    values.into_iter().map(characteristic).collect()
+}
+
+fn float(slug: &str, value: Option<f32>) -> Vec<TypedCharacteristic> {
+    value
+        .and_then(|v| FloatCharacteristic::from_name(slug, v))
+        .map(TypedCharacteristic::Float)
+        .into_iter()
+        .collect()
+}
+
+fn int(slug: &str, value: Option<i32>) -> Vec<TypedCharacteristic> {
+    value
+        .and_then(|v| IntCharacteristic::from_name(slug, v))
+        .map(TypedCharacteristic::Int)
+        .into_iter()
+        .collect()
+}
+
+fn is_float(slug: &str) -> bool {
+    FloatCharacteristic::from_name(slug, 0.0).is_some()
+}
+
+/// Unit in which the characteristic is stored
+fn get_unit(slug: &str) -> Option<Unit> {
+    match slug {
+        "Width_mm" | "Height_mm" | "Thickness_mm" => Some(Unit::Millimeter),
+        "ScreenDiagonal" => Some(Unit::Inch),
+        "Weight_gr" => Some(Unit::Gram),
+        "CPUFrequency_Ghz" => Some(Unit::Gigahertz),
+        "BatteryCapacity_mA_h" => Some(Unit::MilliampereHour),
+        "BuiltInMemory_GB" | "Ram_GB" | "MaxMemoryCardSize_GB" => Some(Unit::Gigabyte),
+        _ => None,
+    }
+}
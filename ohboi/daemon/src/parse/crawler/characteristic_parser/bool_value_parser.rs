@@ -6,8 +6,8 @@ use crate::ConsumerName;
 
 pub fn bool_value(context: &CharacteristicParsingContext, value: &str) -> Option<bool> {
     match value.trim() {
-        "Да" => Some(true),
-        "Нет" => Some(false),
+        "Да" | "Так" => Some(true),
+        "Нет" | "Ні" => Some(false),
         _ => {
             error_reporting::warning(
                 format!(
@@ -64,7 +64,7 @@ pub fn enum_satellite_navigation_value(value: &str) -> Option<SatelliteNavigatio
     match string_value(value).as_str() {
         "GPS" => Some(SatelliteNavigation::GPS),
         "A-GPS" => Some(SatelliteNavigation::A_GPS),
-        "ГЛОНАСС" => Some(SatelliteNavigation::GLONASS),
+        "ГЛОНАСС" | "GLONASS" => Some(SatelliteNavigation::GLONASS),
         "Galileo" => Some(SatelliteNavigation::Galileo),
         "BeiDou" => Some(SatelliteNavigation::BeiDou),
         _ => None,
@@ -85,11 +85,11 @@ pub fn enum_wifi_standard_value(value: &str) -> Option<WifiStandard> {
 }
 pub fn enum_material_value(value: &str) -> Option<Material> {
     match string_value(value).as_str() {
-        "Металл" => Some(Material::Metal),
-        "Стекло" => Some(Material::Glass),
+        "Металл" | "Метал" => Some(Material::Metal),
+        "Стекло" | "Скло" => Some(Material::Glass),
         "Пластик" => Some(Material::Plastic),
-        "Алюминий" => Some(Material::Aluminum),
-        "Керамика" => Some(Material::Ceramics),
+        "Алюминий" | "Алюміній" => Some(Material::Aluminum),
+        "Керамика" | "Кераміка" => Some(Material::Ceramics),
         _ => None,
     }
 }
@@ -131,14 +131,14 @@ pub fn enum_audio_jack_value(value: &str) -> Option<AudioJack> {
 }
 pub fn enum_battery_type_value(value: &str) -> Option<BatteryType> {
     match string_value(value).as_str() {
-        "Литий-полимерный" => Some(BatteryType::LithiumPolymer),
-        "Литий-ионный" => Some(BatteryType::LithiumIon),
+        "Литий-полимерный" | "Літій-полімерний" => Some(BatteryType::LithiumPolymer),
+        "Литий-ионный" | "Літій-іонний" => Some(BatteryType::LithiumIon),
         _ => None,
     }
 }
 pub fn enum_display_type_value(value: &str) -> Option<DisplayType> {
     match string_value(value).as_str() {
-        "AMOLED" => Some(DisplayType::Amoled),
+        "AMOLED" | "Super AMOLED" => Some(DisplayType::Amoled),
         "OLED" => Some(DisplayType::Oled),
         "IPS" => Some(DisplayType::IPS),
         _ => None,
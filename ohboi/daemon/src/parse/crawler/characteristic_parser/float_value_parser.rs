@@ -1,10 +1,9 @@
 use bigdecimal::Num;
 
-use lib::error_reporting;
-use lib::error_reporting::ReportingContext;
-
-use crate::parse::crawler::characteristic_parser::CharacteristicParsingContext;
-use crate::ConsumerName;
+use crate::parse::crawler::characteristic_parser::quantity_parser::{parse_quantity, Unit};
+use crate::parse::crawler::characteristic_parser::{
+    report_unknown_value, CharacteristicParsingContext,
+};
 
 pub fn float_android_version_value(
     context: &CharacteristicParsingContext,
@@ -29,23 +28,10 @@ pub fn float_version_value(context: &CharacteristicParsingContext, mut value: &s
 }
 
 pub fn float_ghz_value(context: &CharacteristicParsingContext, value: &str) -> Option<f32> {
-    let value = value.to_lowercase();
-    let was_in_mgz = value.contains("мгц") || value.contains("mhz");
-
-    float_value(
-        context,
-        value
-            .to_lowercase()
-            .replace("ггц", "")
-            .replace("мгц", "")
-            .replace("mhz", "")
-            .replace("ghz", "")
-            .as_str(),
-    )
-    .map(|v| if was_in_mgz { v / 1000.0 } else { v })
+    float_quantity_value(context, value, Unit::Gigahertz)
 }
 pub fn float_diagonal_value(context: &CharacteristicParsingContext, value: &str) -> Option<f32> {
-    float_value(context, value.replace('"', "").as_str())
+    float_quantity_value(context, value, Unit::Inch)
 }
 
 /// `f/1,79 + f/2,4 + f/2,4` -> `1.79`
@@ -64,25 +50,26 @@ pub fn float_aperture_value(context: &CharacteristicParsingContext, value: &str)
     )
 }
 
+/// `150 г` | `0,15 kg` as [`Unit::Gram`] -> `150`
+pub fn float_quantity_value(
+    context: &CharacteristicParsingContext,
+    value: &str,
+    unit: Unit,
+) -> Option<f32> {
+    match parse_quantity(value, unit) {
+        Ok(v) => Some(v),
+        Err(_) => {
+            report_unknown_value(context, value);
+            None
+        }
+    }
+}
+
 pub fn float_value(context: &CharacteristicParsingContext, value: &str) -> Option<f32> {
     match f32::from_str_radix(value.replace(",", ".").trim(), 10) {
         Ok(v) => Some(v),
-        Err(e) => {
-            error_reporting::warning(
-                format!(
-                    "[{source}] Can't parse float characteristic ({title}) with value ({value}) for [{external_id}]: {error:?}",
-                    source = context.source,
-                    title = context.title,
-                    value = value,
-                    external_id = context.external_id,
-                    error = e,
-                )
-                    .as_str(),
-                &ReportingContext {
-                    executor: &ConsumerName::ParseDetails,
-                    action: "parse_float"
-                }
-            );
+        Err(_) => {
+            report_unknown_value(context, value);
             None
         }
     }
@@ -91,8 +78,9 @@ pub fn float_value(context: &CharacteristicParsingContext, value: &str) -> Optio
 #[cfg(test)]
 mod tests {
     use crate::parse::crawler::characteristic_parser::{
-        float_android_version_value, float_ghz_value, float_miui_version_value, float_value,
-        float_version_value, CharacteristicParsingContext, SourceName,
+        float_android_version_value, float_diagonal_value, float_ghz_value,
+        float_miui_version_value, float_quantity_value, float_value, float_version_value,
+        CharacteristicParsingContext, SourceName, Unit,
     };
 
     fn get_context() -> CharacteristicParsingContext<'static> {
@@ -155,4 +143,26 @@ mod tests {
         assert_eq!(float_ghz_value(&get_context(), "2.2GHz"), Some(2.2));
         assert_eq!(float_ghz_value(&get_context(), "2200 МГц"), Some(2.2));
     }
+
+    #[test]
+    fn it_parses_diagonal_float() {
+        assert_eq!(float_diagonal_value(&get_context(), "6.67\""), Some(6.67));
+        assert_eq!(float_diagonal_value(&get_context(), "6,5 дюйма"), Some(6.5));
+    }
+
+    #[test]
+    fn it_parses_quantity_float() {
+        assert_eq!(
+            float_quantity_value(&get_context(), "0,2 кг", Unit::Gram),
+            Some(200.0)
+        );
+        assert_eq!(
+            float_quantity_value(&get_context(), "8.9", Unit::Millimeter),
+            Some(8.9)
+        );
+        assert_eq!(
+            float_quantity_value(&get_context(), "8.9 кг", Unit::Millimeter),
+            None
+        );
+    }
 }
@@ -1,8 +1,7 @@
-use lib::error_reporting;
-use lib::error_reporting::ReportingContext;
-
-use crate::parse::crawler::characteristic_parser::CharacteristicParsingContext;
-use crate::ConsumerName;
+use crate::parse::crawler::characteristic_parser::quantity_parser::Unit;
+use crate::parse::crawler::characteristic_parser::{
+    float_quantity_value, report_unknown_value, CharacteristicParsingContext,
+};
 
 /// It skips additional cameras
 /// `64Мп + 8Мп + 6Мп` will result in just `64`
@@ -18,7 +17,7 @@ pub fn int_mp_value(context: &CharacteristicParsingContext, value: &str) -> Opti
     )
 }
 pub fn int_ma_h_value(context: &CharacteristicParsingContext, value: &str) -> Option<i32> {
-    int_value(context, &value.replace("мАч", ""))
+    int_quantity_value(context, value, Unit::MilliampereHour)
 }
 pub fn int_nit_value(context: &CharacteristicParsingContext, value: &str) -> Option<i32> {
     int_value(context, &value.replace("нит", ""))
@@ -27,27 +26,23 @@ pub fn int_max_memory_card_size_value(
     context: &CharacteristicParsingContext,
     value: &str,
 ) -> Option<i32> {
-    int_value(context, &value.replace("до", "").replace("ГБ", ""))
+    int_quantity_value(context, value, Unit::Gigabyte)
 }
 
 pub fn int_guarantee_value(context: &CharacteristicParsingContext, value: &str) -> Option<i32> {
     int_value(
         context,
-        &value.replace("месяцев", "").replace("Месяцев.", ""),
+        &value
+            .replace("месяцев", "")
+            .replace("Месяцев.", "")
+            .replace("місяців", ""),
     )
 }
 pub fn int_hz_value(context: &CharacteristicParsingContext, value: &str) -> Option<i32> {
     int_value(context, &value.replace("Гц", ""))
 }
 pub fn int_memory_value(context: &CharacteristicParsingContext, value: &str) -> Option<i32> {
-    let is_tb = value.contains("ТБ");
-    int_value(context, &value.replace("ГБ", "").replace("до", "")).map(|v| {
-        if is_tb {
-            v * 1000
-        } else {
-            v
-        }
-    })
+    int_quantity_value(context, value, Unit::Gigabyte)
 }
 pub fn int_fps_value(context: &CharacteristicParsingContext, value: &str) -> Option<i32> {
     int_value(context, &value.replace("fps", "").replace("кадров/с", ""))
@@ -89,26 +84,60 @@ pub fn multiple_int_value(context: &CharacteristicParsingContext, value: &str) -
 
     int_values
 }
+/// Same as [`float_quantity_value`], rounded to the nearest integer
+#[allow(clippy::cast_possible_truncation)]
+pub fn int_quantity_value(
+    context: &CharacteristicParsingContext,
+    value: &str,
+    unit: Unit,
+) -> Option<i32> {
+    float_quantity_value(context, value, unit).map(|v| v.round() as i32)
+}
+
 pub fn int_value(context: &CharacteristicParsingContext, value: &str) -> Option<i32> {
     match value.trim().parse::<i32>() {
         Ok(v) => Some(v),
-        Err(e) => {
-            error_reporting::warning(
-                format!(
-                    "[{source}] Can't parse int characteristic ({title}) with value ({value}) for [{external_id}]: {error:?}",
-                    source = context.source,
-                    title = context.title,
-                    value = value,
-                    external_id = context.external_id,
-                    error = e,
-                )
-                    .as_str(),
-                &ReportingContext {
-                    executor: &ConsumerName::ParseDetails,
-                    action: "parse_int"
-                }
-            );
+        Err(_) => {
+            report_unknown_value(context, value);
             None
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::crawler::characteristic_parser::{
+        int_ma_h_value, int_memory_value, int_quantity_value, CharacteristicParsingContext,
+        SourceName, Unit,
+    };
+
+    fn get_context() -> CharacteristicParsingContext<'static> {
+        CharacteristicParsingContext {
+            title: "_",
+            external_id: "_",
+            source: SourceName::MiShopCom,
+        }
+    }
+
+    #[test]
+    fn it_parses_battery_capacity() {
+        assert_eq!(int_ma_h_value(&get_context(), "5000 мАч"), Some(5000));
+        assert_eq!(int_ma_h_value(&get_context(), "4500мАгод"), Some(4500));
+        assert_eq!(int_ma_h_value(&get_context(), "4 410 mAh"), Some(4410));
+    }
+
+    #[test]
+    fn it_parses_memory() {
+        assert_eq!(int_memory_value(&get_context(), "до 512 ГБ"), Some(512));
+        assert_eq!(int_memory_value(&get_context(), "до 1 ТБ"), Some(1000));
+        assert_eq!(int_memory_value(&get_context(), "256GB"), Some(256));
+    }
+
+    #[test]
+    fn it_rounds_quantity() {
+        assert_eq!(
+            int_quantity_value(&get_context(), "4,4 Wh", Unit::MilliampereHour),
+            Some(1143)
+        );
+    }
+}
@@ -109,7 +109,7 @@ mod tests {
 
     use crate::db::entity::source::SourceName;
     use crate::parse::crawler::characteristic_parser::CharacteristicParsingContext;
-    use crate::parse::crawler::mi_shop_com::crawler::media_format_parser::multiple_string_media_format_value;
+    use crate::parse::crawler::characteristic_parser::multiple_string_media_format_value;
 
     fn get_context() -> CharacteristicParsingContext<'static> {
         CharacteristicParsingContext {
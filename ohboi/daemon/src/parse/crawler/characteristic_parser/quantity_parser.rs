@@ -0,0 +1,259 @@
+use std::fmt;
+
+/// Words in front of the value which don't change it, e.g. `до 1 ТБ`
+const PREFIXES: [&str; 3] = ["до", "up to", "max"];
+
+const RANGE_SEPARATORS: [char; 5] = ['-', '–', '—', '~', '…'];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unit {
+    Millimeter,
+    Centimeter,
+    Inch,
+    Gram,
+    Kilogram,
+    Ounce,
+    MilliampereHour,
+    WattHour,
+    Megahertz,
+    Gigahertz,
+    Gigabyte,
+    Terabyte,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dimension {
+    Length,
+    Mass,
+    BatteryCapacity,
+    /// Watt-hours can't be converted into milliampere-hours without the voltage, which shops don't state
+    Energy,
+    Frequency,
+    Memory,
+}
+
+/// Spellings are compared after lowercasing and removing of spaces, dots and `*`
+const SPELLINGS: [(&str, Unit); 36] = [
+    ("мм", Unit::Millimeter),
+    ("mm", Unit::Millimeter),
+    ("см", Unit::Centimeter),
+    ("cm", Unit::Centimeter),
+    ("\"", Unit::Inch),
+    ("″", Unit::Inch),
+    ("''", Unit::Inch),
+    ("in", Unit::Inch),
+    ("inch", Unit::Inch),
+    ("inches", Unit::Inch),
+    ("дюйм", Unit::Inch),
+    ("дюйма", Unit::Inch),
+    ("дюймов", Unit::Inch),
+    ("дюймів", Unit::Inch),
+    ("г", Unit::Gram),
+    ("гр", Unit::Gram),
+    ("g", Unit::Gram),
+    ("gr", Unit::Gram),
+    ("кг", Unit::Kilogram),
+    ("kg", Unit::Kilogram),
+    ("oz", Unit::Ounce),
+    ("унц", Unit::Ounce),
+    ("мач", Unit::MilliampereHour),
+    ("магод", Unit::MilliampereHour),
+    ("mah", Unit::MilliampereHour),
+    ("втч", Unit::WattHour),
+    ("втгод", Unit::WattHour),
+    ("wh", Unit::WattHour),
+    ("мгц", Unit::Megahertz),
+    ("mhz", Unit::Megahertz),
+    ("ггц", Unit::Gigahertz),
+    ("ghz", Unit::Gigahertz),
+    ("гб", Unit::Gigabyte),
+    ("gb", Unit::Gigabyte),
+    ("тб", Unit::Terabyte),
+    ("tb", Unit::Terabyte),
+];
+
+impl Unit {
+    fn dimension(self) -> Dimension {
+        match self {
+            Unit::Millimeter | Unit::Centimeter | Unit::Inch => Dimension::Length,
+            Unit::Gram | Unit::Kilogram | Unit::Ounce => Dimension::Mass,
+            Unit::MilliampereHour => Dimension::BatteryCapacity,
+            Unit::WattHour => Dimension::Energy,
+            Unit::Megahertz | Unit::Gigahertz => Dimension::Frequency,
+            Unit::Gigabyte | Unit::Terabyte => Dimension::Memory,
+        }
+    }
+
+    /// Amount of the smallest unit of the dimension in this one
+    fn factor(self) -> f32 {
+        match self {
+            Unit::Millimeter
+            | Unit::Gram
+            | Unit::MilliampereHour
+            | Unit::WattHour
+            | Unit::Megahertz
+            | Unit::Gigabyte => 1.0,
+            Unit::Centimeter => 10.0,
+            Unit::Inch => 25.4,
+            Unit::Kilogram | Unit::Gigahertz | Unit::Terabyte => 1000.0,
+            Unit::Ounce => 28.349_523,
+        }
+    }
+
+    fn from_spelling(spelling: &str) -> Option<Unit> {
+        SPELLINGS
+            .iter()
+            .find(|(known, _)| *known == spelling)
+            .map(|(_, unit)| *unit)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum QuantityError {
+    NotANumber,
+    UnknownUnit(String),
+    IncompatibleUnit(Unit),
+}
+
+impl fmt::Display for QuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuantityError::NotANumber => write!(f, "Not a number"),
+            QuantityError::UnknownUnit(unit) => write!(f, "Unknown unit ({})", unit),
+            QuantityError::IncompatibleUnit(unit) => write!(f, "Incompatible unit ({:?})", unit),
+        }
+    }
+}
+
+/// Parses the value with an optional unit and converts it to `canonical`.
+/// Value without a unit is expected to be in `canonical` already, ranges result in the upper bound.
+///
+/// `2200 МГц` as [`Unit::Gigahertz`] -> `2.2`
+/// `1,5 кг` as [`Unit::Gram`] -> `1500`
+/// `150-160 г` as [`Unit::Gram`] -> `160`
+pub fn parse_quantity(value: &str, canonical: Unit) -> Result<f32, QuantityError> {
+    let mut value = value.trim().to_lowercase();
+    for prefix in &PREFIXES {
+        if value.starts_with(prefix) {
+            value = value[prefix.len()..].to_string();
+        }
+    }
+    let value: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == ',' { '.' } else { c })
+        .collect();
+
+    let mut parts = value
+        .split(|c| RANGE_SEPARATORS.contains(&c))
+        .map(split_number_and_unit)
+        .collect::<Result<Vec<(f32, Option<Unit>)>, QuantityError>>()?;
+
+    // `150-160 г` has the unit only after the last value
+    let range_unit = parts.last().and_then(|(_, unit)| *unit);
+    for part in &mut parts {
+        part.1 = part.1.or(range_unit);
+    }
+
+    let amounts = parts
+        .into_iter()
+        .map(|(amount, unit)| convert(amount, unit.unwrap_or(canonical), canonical))
+        .collect::<Result<Vec<f32>, QuantityError>>()?;
+
+    Ok(amounts.into_iter().fold(f32::MIN, f32::max))
+}
+
+fn split_number_and_unit(value: &str) -> Result<(f32, Option<Unit>), QuantityError> {
+    let number_end = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let amount = value[..number_end]
+        .parse::<f32>()
+        .map_err(|_| QuantityError::NotANumber)?;
+
+    let spelling: String = value[number_end..]
+        .chars()
+        .filter(|&c| c != '.' && c != '*' && c != '·')
+        .collect();
+    if spelling.is_empty() {
+        return Ok((amount, None));
+    }
+
+    Unit::from_spelling(&spelling)
+        .map(|unit| (amount, Some(unit)))
+        .ok_or(QuantityError::UnknownUnit(spelling))
+}
+
+fn convert(amount: f32, from: Unit, to: Unit) -> Result<f32, QuantityError> {
+    if from.dimension() != to.dimension() {
+        return Err(QuantityError::IncompatibleUnit(from));
+    }
+    if from == to {
+        return Ok(amount);
+    }
+
+    Ok(amount * from.factor() / to.factor())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::crawler::characteristic_parser::quantity_parser::{
+        parse_quantity, QuantityError, Unit,
+    };
+
+    #[test]
+    fn it_parses_value_without_unit_as_canonical() {
+        assert_eq!(parse_quantity("128", Unit::Gigabyte), Ok(128.0));
+        assert_eq!(parse_quantity(" 8,9 ", Unit::Millimeter), Ok(8.9));
+    }
+
+    #[test]
+    fn it_converts_units() {
+        assert_eq!(parse_quantity("2200 МГц", Unit::Gigahertz), Ok(2.2));
+        assert_eq!(parse_quantity("2.4GHz", Unit::Gigahertz), Ok(2.4));
+        assert_eq!(parse_quantity("1,5 кг", Unit::Gram), Ok(1500.0));
+        assert_eq!(parse_quantity("1 см", Unit::Millimeter), Ok(10.0));
+        assert_eq!(parse_quantity("до 1 ТБ", Unit::Gigabyte), Ok(1000.0));
+        assert_eq!(parse_quantity("512 GB", Unit::Gigabyte), Ok(512.0));
+        assert_eq!(
+            parse_quantity("5 000 мА*ч", Unit::MilliampereHour),
+            Ok(5000.0)
+        );
+        assert_eq!(
+            parse_quantity("4500 мА·год", Unit::MilliampereHour),
+            Ok(4500.0)
+        );
+
+        let inch = parse_quantity("6.1 in", Unit::Millimeter).unwrap();
+        assert!((inch - 154.94).abs() < 0.01);
+        let ounce = parse_quantity("7 oz", Unit::Gram).unwrap();
+        assert!((ounce - 198.45).abs() < 0.01);
+    }
+
+    #[test]
+    fn it_takes_upper_bound_of_range() {
+        assert_eq!(parse_quantity("150-160 г", Unit::Gram), Ok(160.0));
+        assert_eq!(parse_quantity("1.8 ~ 2,4 ГГц", Unit::Gigahertz), Ok(2.4));
+        assert_eq!(parse_quantity("800 МГц – 1 ГГц", Unit::Gigahertz), Ok(1.0));
+    }
+
+    #[test]
+    fn it_refuses_unknown_and_incompatible_values() {
+        assert_eq!(
+            parse_quantity("abc", Unit::Gram),
+            Err(QuantityError::NotANumber)
+        );
+        assert_eq!(
+            parse_quantity("5 parsec", Unit::Millimeter),
+            Err(QuantityError::UnknownUnit(String::from("parsec")))
+        );
+        assert_eq!(
+            parse_quantity("5 кг", Unit::Millimeter),
+            Err(QuantityError::IncompatibleUnit(Unit::Kilogram))
+        );
+        assert_eq!(
+            parse_quantity("15,4 Вт*ч", Unit::MilliampereHour),
+            Err(QuantityError::IncompatibleUnit(Unit::WattHour))
+        );
+    }
+}
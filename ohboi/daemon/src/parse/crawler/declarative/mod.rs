@@ -0,0 +1,794 @@
+use std::fs;
+use std::path::PathBuf;
+
+use inflector::Inflector;
+use regex::Regex;
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+
+use lib::dto::characteristic::TypedCharacteristic;
+use lib::error_reporting;
+use lib::error_reporting::ReportingContext;
+use lib::my_enum::CurrencyEnum;
+
+use crate::db::entity::category::CategorySlug;
+use crate::db::entity::source::{DeclarativeSource, SourceName};
+use crate::db::repository::source::create_if_not_exists;
+use crate::dto::parsed_product::{
+    AdditionalParsedProductInfo, LocalParsedProduct, ProductIdentifiers,
+};
+use crate::parse::crawler::characteristic_dictionary::{has_dictionary, parse_characteristics};
+use crate::parse::crawler::declarative::spec::{
+    CrawlerSpec, DetailsSpec, ListingSpec, DEFAULT_PRICE_REGEX,
+};
+use crate::parse::crawler::structured_data::StructuredProduct;
+use crate::parse::crawler::{
+    extract_structured_product, extract_structured_products, get_hand_written_crawlers,
+    get_html_nodes, Crawler, ProductHtmlSelectors,
+};
+use crate::parse::util::variant::split_variant;
+use crate::service::html_cleaner::inner_text;
+use crate::service::request::RequestBudget;
+use crate::{ConsumerName, SETTINGS};
+
+mod spec;
+
+lazy_static! {
+    static ref DECLARATIVE_CRAWLERS: Vec<DeclarativeCrawler> =
+        load_crawlers(&SETTINGS.crawlers.specs_dir);
+}
+
+/// Generic crawler driven by a spec file, see `docs/parse_architecture.md`.
+pub struct DeclarativeCrawler {
+    source: DeclarativeSource,
+    spec: CrawlerSpec,
+    site_base: Url,
+    price_re: Regex,
+    title_re: Option<Regex>,
+    description_re: Option<Regex>,
+    sitemap_patterns: Vec<(CategorySlug, Regex)>,
+}
+
+impl Crawler for DeclarativeCrawler {
+    fn get_site_base(&self) -> String {
+        self.spec.site_base.clone()
+    }
+
+    fn get_source(&self) -> SourceName {
+        SourceName::Declarative(self.source)
+    }
+
+    fn get_currency(&self) -> CurrencyEnum {
+        self.spec.currency
+    }
+
+    fn get_categories(&self) -> Vec<CategorySlug> {
+        self.spec.categories.iter().map(|c| c.slug).collect()
+    }
+
+    fn get_characteristic_dictionaries(&self) -> Vec<String> {
+        self.spec.characteristic_dictionaries.clone()
+    }
+
+    fn get_next_page_urls(&self, category: CategorySlug) -> Vec<String> {
+        self.spec
+            .categories
+            .iter()
+            .filter(|c| c.slug == category)
+            .flat_map(|c| c.urls.iter())
+            .map(|url| [&self.spec.site_base, url.as_str(), &self.spec.pagination].concat())
+            .collect()
+    }
+
+    fn extract_products(&self, document: &Html) -> Vec<LocalParsedProduct> {
+        match &self.spec.listing {
+            Some(listing) => self.extract_described_products(document, listing),
+            None => self.extract_structured_listed_products(document),
+        }
+    }
+
+    fn get_additional_info_url(&self, external_id: &str) -> String {
+        // Paths are kept relative to the site base as before, absolute and protocol-relative hrefs replace it
+        let relative = match external_id.strip_prefix('/') {
+            Some(path) if !path.starts_with('/') => path,
+            _ => external_id,
+        };
+
+        self.site_base.join(relative).map_or_else(
+            |_| format!("{}{}", self.spec.site_base, external_id),
+            String::from,
+        )
+    }
+
+    fn get_concurrent_pages(&self) -> usize {
+        self.spec.concurrent_pages
+    }
+
+    fn get_request_budget(&self) -> RequestBudget {
+        let default = RequestBudget::default();
+
+        RequestBudget {
+            requests_per_second: self.spec.requests_per_second.unwrap_or(default.requests_per_second),
+            burst: self.spec.request_burst.unwrap_or(default.burst),
+        }
+    }
+
+    fn get_sitemap_url(&self) -> Option<String> {
+        self.spec.sitemap.clone()
+    }
+
+    fn get_sitemap_product_pattern(&self, category: CategorySlug) -> Option<Regex> {
+        self.sitemap_patterns
+            .iter()
+            .find(|(slug, _)| *slug == category)
+            .map(|(_, pattern)| pattern.clone())
+    }
+
+    fn extract_additional_info(
+        &self,
+        document: &Html,
+        external_id: &str,
+    ) -> Option<AdditionalParsedProductInfo> {
+        let structured =
+            extract_structured_product(document, &self.get_additional_info_url(external_id));
+
+        let (description, available) = match (&self.spec.details, &self.description_re) {
+            (Some(details), Some(description_re)) => (
+                self.structured_or_abstract_description(
+                    document,
+                    structured.as_ref(),
+                    selector(&details.description),
+                    description_re,
+                ),
+                self.structured_or_abstract_availability(
+                    document,
+                    structured.as_ref(),
+                    selector(&details.available),
+                    selector(&details.unavailable),
+                ),
+            ),
+            _ => (
+                structured
+                    .as_ref()
+                    .and_then(StructuredProduct::description_html),
+                structured.as_ref().and_then(|p| p.available),
+            ),
+        };
+
+        if let (Some(description), Some(available)) = (description, available) {
+            let image_urls = match &self.spec.details {
+                Some(details) => self.structured_or_abstract_image_urls(
+                    document,
+                    structured.as_ref(),
+                    &selector(&details.images),
+                    &details.image_lazy_attribute,
+                ),
+                None => structured.map(|p| p.image_urls).unwrap_or_default(),
+            };
+
+            Some(AdditionalParsedProductInfo {
+                image_urls,
+                description,
+                available,
+                characteristics: self.extract_described_characteristics(document, external_id),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl DeclarativeCrawler {
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let spec: CrawlerSpec = toml::from_str(content).map_err(|e| e.to_string())?;
+
+        Self::from_spec(spec)
+    }
+
+    fn from_spec(spec: CrawlerSpec) -> Result<Self, String> {
+        // Sources are looked up by the snake_case site_name
+        if spec.name.is_empty() || spec.name.to_snake_case() != spec.name {
+            return Err(format!("Name '{}' should be in snake_case", spec.name));
+        }
+        if get_hand_written_crawlers()
+            .iter()
+            .any(|crawler| crawler.get_source().to_string().to_snake_case() == spec.name)
+        {
+            return Err(format!("Name '{}' is taken by a built-in source", spec.name));
+        }
+
+        let site_base = Url::parse(&format!("{}/", spec.site_base.trim_end_matches('/')))
+            .map_err(|e| format!("Invalid site base '{}': {}", spec.site_base, e))?;
+
+        let mut selectors: Vec<&String> = vec![];
+        if let Some(listing) = &spec.listing {
+            selectors.extend(&[
+                &listing.item,
+                &listing.id,
+                &listing.title,
+                &listing.price,
+                &listing.available,
+                &listing.unavailable,
+            ]);
+        }
+        if let Some(details) = &spec.details {
+            selectors.extend(&[
+                &details.description,
+                &details.available,
+                &details.unavailable,
+                &details.images,
+            ]);
+
+            match (&details.characteristic_title, &details.characteristic_value) {
+                (Some(title), Some(value)) => selectors.extend(&[title, value]),
+                (None, None) => {}
+                _ => {
+                    return Err(
+                        "Characteristic title and value should be described together".to_string(),
+                    )
+                }
+            }
+        }
+        for raw_selector in selectors {
+            Selector::parse(raw_selector)
+                .map_err(|e| format!("Invalid selector '{}': {:?}", raw_selector, e))?;
+        }
+
+        if let Some(name) = spec
+            .characteristic_dictionaries
+            .iter()
+            .find(|name| !has_dictionary(name))
+        {
+            return Err(format!("Unknown characteristic dictionary '{}'", name));
+        }
+
+        let budget_is_valid = spec.requests_per_second.map_or(true, |rps| rps > 0.0)
+            && spec.request_burst.map_or(true, |burst| burst >= 1.0);
+        if !budget_is_valid {
+            return Err("Requests per second must be positive and burst at least 1".to_string());
+        }
+
+        let price_re = compile_regex(
+            spec.listing
+                .as_ref()
+                .map_or(DEFAULT_PRICE_REGEX, |listing| listing.price_regex.as_str()),
+        )?;
+        let title_re = match spec.listing.as_ref().and_then(|l| l.title_regex.as_ref()) {
+            Some(re) => Some(compile_regex(re)?),
+            None => None,
+        };
+        let description_re = match &spec.details {
+            Some(details) => Some(compile_regex(&details.description_regex)?),
+            None => None,
+        };
+        let mut sitemap_patterns = vec![];
+        for category in &spec.categories {
+            if let Some(pattern) = &category.sitemap_pattern {
+                sitemap_patterns.push((category.slug, compile_regex(pattern)?));
+            }
+        }
+
+        // Crawlers live until the end of the process, as well as the names of their sources.
+        let name: &'static str = Box::leak(spec.name.clone().into_boxed_str());
+
+        Ok(DeclarativeCrawler {
+            source: DeclarativeSource(name),
+            spec,
+            site_base,
+            price_re,
+            title_re,
+            description_re,
+            sitemap_patterns,
+        })
+    }
+
+    fn extract_described_products(
+        &self,
+        document: &Html,
+        listing: &ListingSpec,
+    ) -> Vec<LocalParsedProduct> {
+        let mut parsed_products = vec![];
+        let items_selector = selector(&listing.item);
+
+        let selectors = ProductHtmlSelectors {
+            id: selector(&listing.id),
+            title: selector(&listing.title),
+            price: selector(&listing.price),
+            available: selector(&listing.available),
+            unavailable: selector(&listing.unavailable),
+        };
+        let context = ReportingContext {
+            executor: &ConsumerName::ParseCategory,
+            action: "extract_products",
+        };
+
+        for element in document.select(&items_selector) {
+            let nodes = get_html_nodes(&selectors, &element, self.get_source());
+
+            if nodes.is_none() {
+                continue;
+            }
+
+            let product_nodes = nodes.unwrap();
+            let (title, variant) = split_variant(&self.parse_title(&product_nodes.title));
+            let price = self.parse_price(&product_nodes.price);
+
+            if price.is_none() {
+                let message = format!(
+                    "[{source}] price_text({html}) can't be parsed!",
+                    html = product_nodes.price.inner_html(),
+                    source = self.get_source(),
+                );
+                error_reporting::warning(message.as_str(), &context);
+                continue;
+            }
+
+            let available =
+                product_nodes.available.is_some() && product_nodes.unavailable.is_none();
+            let external_id = product_nodes
+                .id
+                .value()
+                .attr(&listing.id_attribute)
+                .unwrap_or_default()
+                .to_string();
+
+            if title.is_empty() || external_id.is_empty() {
+                let message = format!(
+                    "[{source}] Some param is invalid: title - {title}, external_id - {id}",
+                    source = self.get_source(),
+                    title = title,
+                    id = external_id,
+                );
+                error_reporting::warning(message.as_str(), &context);
+                continue;
+            }
+
+            parsed_products.push(LocalParsedProduct {
+                title,
+                price: price.unwrap(),
+                available,
+                external_id,
+                variant,
+                identifiers: ProductIdentifiers::default(),
+            });
+        }
+
+        parsed_products
+    }
+
+    fn extract_structured_listed_products(&self, document: &Html) -> Vec<LocalParsedProduct> {
+        extract_structured_products(document)
+            .into_iter()
+            .filter_map(|product| {
+                let (title, variant) = split_variant(&product.title?);
+
+                Some(LocalParsedProduct {
+                    external_id: self.get_external_id_from_url(&product.url?),
+                    title,
+                    price: product.price?,
+                    // Lists rarely have availability, it is specified later on the product page
+                    available: product.available.unwrap_or(true),
+                    variant,
+                    identifiers: ProductIdentifiers {
+                        gtin: product.gtin,
+                        model: product.mpn,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    fn extract_described_characteristics(
+        &self,
+        document: &Html,
+        external_id: &str,
+    ) -> Vec<TypedCharacteristic> {
+        let (title_selector, value_selector) = match &self.spec.details {
+            Some(DetailsSpec {
+                characteristic_title: Some(title),
+                characteristic_value: Some(value),
+                ..
+            }) => (selector(title), selector(value)),
+            _ => return vec![],
+        };
+
+        let characteristics: Vec<(String, String)> = document
+            .select(&title_selector)
+            .zip(document.select(&value_selector))
+            .map(|(title, value)| {
+                (
+                    inner_text(&title.inner_html()).replace(":", ""),
+                    inner_text(&value.inner_html()),
+                )
+            })
+            .collect();
+        parse_characteristics(self, &characteristics, external_id)
+    }
+
+    fn parse_title(&self, node: &ElementRef) -> String {
+        let text = node
+            .text()
+            .flat_map(str::split_whitespace)
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        match &self.title_re {
+            Some(re) => re
+                .find(&text)
+                .map_or_else(String::new, |m| m.as_str().trim().to_string()),
+            None => text,
+        }
+    }
+
+    fn parse_price(&self, node: &ElementRef) -> Option<f64> {
+        let price_html = node.text().collect::<String>();
+        let price_text = self.price_re.find(&price_html)?.as_str();
+
+        price_text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+    }
+}
+
+pub fn get_declarative_crawler(source: DeclarativeSource) -> &'static DeclarativeCrawler {
+    DECLARATIVE_CRAWLERS
+        .iter()
+        .find(|crawler| crawler.source.0 == source.0)
+        .expect(&format!("Declarative crawler {} is not loaded.", source.0))
+}
+
+pub fn get_declarative_crawlers() -> &'static [DeclarativeCrawler] {
+    &DECLARATIVE_CRAWLERS
+}
+
+pub fn find_declarative_source(name: &str) -> Option<DeclarativeSource> {
+    DECLARATIVE_CRAWLERS
+        .iter()
+        .find(|crawler| crawler.source.0 == name)
+        .map(|crawler| crawler.source)
+}
+
+/// Every declarative crawler needs its own row in the `source` table.
+pub fn register_declarative_sources() {
+    for crawler in DECLARATIVE_CRAWLERS.iter() {
+        create_if_not_exists(crawler.get_source(), &crawler.spec.logo, crawler.spec.currency);
+    }
+}
+
+fn load_crawlers(dir: &str) -> Vec<DeclarativeCrawler> {
+    let context = ReportingContext {
+        executor: &ConsumerName::ParseCategory,
+        action: "load_crawlers",
+    };
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("No declarative crawlers loaded from {}: {:?}", dir, e);
+            return vec![];
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    let mut crawlers: Vec<DeclarativeCrawler> = vec![];
+    for path in paths {
+        let crawler = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| DeclarativeCrawler::from_toml(&content));
+
+        match crawler {
+            Ok(crawler) if crawlers.iter().any(|c| c.source.0 == crawler.source.0) => {
+                let message = format!(
+                    "Duplicated declarative crawler {name} in {path:?}",
+                    name = crawler.source.0,
+                    path = path
+                );
+                error_reporting::error(message.as_str(), &context);
+            }
+            Ok(crawler) => {
+                log::info!("Declarative crawler {} loaded.", crawler.source.0);
+                crawlers.push(crawler);
+            }
+            Err(e) => {
+                let message = format!(
+                    "Invalid crawler spec {path:?}: {error}",
+                    path = path,
+                    error = e
+                );
+                error_reporting::error(message.as_str(), &context);
+            }
+        }
+    }
+
+    crawlers
+}
+
+fn selector(raw: &str) -> Selector {
+    // All the selectors are validated when the spec is loaded.
+    Selector::parse(raw).unwrap()
+}
+
+fn compile_regex(raw: &str) -> Result<Regex, String> {
+    Regex::new(raw).map_err(|e| format!("Invalid regex '{}': {}", raw, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+
+    use crate::db::entity::category::CategorySlug;
+    use crate::parse::crawler::declarative::DeclarativeCrawler;
+    use crate::parse::crawler::Crawler;
+    use crate::service::request::RequestBudget;
+
+    static SPEC: &str = r#"
+        name = "test_shop_com"
+        site_base = "https://test-shop.com"
+        currency = "UAH"
+        pagination = "?page={page}"
+
+        [[category]]
+        slug = "Smartphone"
+        urls = ["/phones", "/refurbished-phones"]
+
+        [listing]
+        item = ".card"
+        id = "a.card__link[href]"
+        title = ".card__title"
+        title_regex = "[a-zA-Z0-9 ]{2,}"
+        price = ".card__price"
+        available = ".card__buy"
+        unavailable = ".card__buy.disabled"
+
+        [details]
+        description = ".description"
+        available = ".buy"
+        unavailable = ".sold-out"
+        images = ".gallery img"
+    "#;
+
+    fn crawler() -> DeclarativeCrawler {
+        DeclarativeCrawler::from_toml(SPEC).unwrap()
+    }
+
+    #[test]
+    fn it_builds_page_urls_for_category() {
+        assert_eq!(
+            crawler().get_next_page_urls(CategorySlug::Smartphone),
+            vec![
+                "https://test-shop.com/phones?page={page}".to_string(),
+                "https://test-shop.com/refurbished-phones?page={page}".to_string(),
+            ]
+        );
+        assert!(crawler().get_next_page_urls(CategorySlug::Watches).is_empty());
+    }
+
+    #[test]
+    fn it_extracts_products() {
+        let document = Html::parse_document(
+            r#"<div>
+                <div class="card">
+                    <a class="card__link" href="/phone-1"></a>
+                    <span class="card__title">Смартфон Phone 1 Pro</span>
+                    <span class="card__price">12 499,50 грн</span>
+                    <button class="card__buy"></button>
+                </div>
+                <div class="card">
+                    <a class="card__link" href="/phone-2"></a>
+                    <span class="card__title">Phone 2 4/64GB</span>
+                    <span class="card__price">999</span>
+                    <button class="card__buy disabled"></button>
+                </div>
+            </div>"#,
+        );
+
+        let products = crawler().extract_products(&document);
+
+        assert_eq!(products.len(), 2);
+        assert_eq!(products[0].title, "Phone 1 Pro");
+        assert!((products[0].price - 12499.5).abs() < f64::EPSILON);
+        assert!(products[0].available);
+        assert_eq!(products[0].external_id, "/phone-1");
+        assert_eq!(products[1].title, "Phone 2");
+        assert_eq!(products[1].variant.ram_gb, Some(4));
+        assert_eq!(products[1].variant.built_in_memory_gb, Some(64));
+        assert!(!products[1].available);
+    }
+
+    #[test]
+    fn it_extracts_additional_info() {
+        let document = Html::parse_document(
+            r#"<div>
+                <div class="description"><p>Good phone</p></div>
+                <button class="buy"></button>
+                <div class="gallery"><img data-src="/1.jpg"><img src="/2.jpg"></div>
+            </div>"#,
+        );
+
+        let info = crawler().extract_additional_info(&document, "/phone-1").unwrap();
+
+        assert_eq!(info.description, "<p>Good phone</p>");
+        assert!(info.available);
+        assert_eq!(info.image_urls, vec!["/1.jpg".to_string(), "/2.jpg".to_string()]);
+    }
+
+    #[test]
+    fn it_extracts_described_characteristics() {
+        let spec = SPEC
+            .replace(
+                r#"pagination = "?page={page}""#,
+                r#"pagination = "?page={page}"
+                characteristic_dictionaries = ["uk"]"#,
+            )
+            .replace(
+                r#"images = ".gallery img""#,
+                r#"images = ".gallery img"
+                characteristic_title = ".specs dt"
+                characteristic_value = ".specs dd""#,
+            );
+        let document = Html::parse_document(
+            r#"<div>
+                <div class="description"><p>Good phone</p></div>
+                <button class="buy"></button>
+                <dl class="specs">
+                    <dt>Вага (г):</dt><dd>0,19 кг</dd>
+                    <dt>NFC:</dt><dd>Так</dd>
+                </dl>
+            </div>"#,
+        );
+
+        let info = DeclarativeCrawler::from_toml(&spec)
+            .unwrap()
+            .extract_additional_info(&document, "/phone-1")
+            .unwrap();
+
+        assert_eq!(
+            info.characteristics
+                .iter()
+                .map(|characteristic| format!("{:?}", characteristic))
+                .collect::<Vec<String>>(),
+            vec!["Float(Weight_gr(190.0))", "Bool(NFC(true))"]
+        );
+        assert!(DeclarativeCrawler::from_toml(&spec.replace(r#"["uk"]"#, r#"["xx"]"#)).is_err());
+    }
+
+    static ZERO_CONFIG_SPEC: &str = r#"
+        name = "zero_shop_com"
+        site_base = "https://zero-shop.com"
+        currency = "UAH"
+        pagination = "?page={page}"
+
+        [[category]]
+        slug = "Watches"
+        urls = ["/watches"]
+    "#;
+
+    #[test]
+    fn it_extracts_products_from_structured_data() {
+        let crawler = DeclarativeCrawler::from_toml(ZERO_CONFIG_SPEC).unwrap();
+        let document = Html::parse_document(
+            r#"<script type="application/ld+json">
+            {"@type": "ItemList", "itemListElement": [
+                {"@type": "ListItem", "item": {
+                    "@type": "Product", "name": "Watch 1", "url": "https://zero-shop.com/watch-1",
+                    "offers": {"@type": "Offer", "price": "1999.99"}
+                }},
+                {"@type": "ListItem", "item": {"@type": "Product", "name": "No price", "url": "/watch-2"}}
+            ]}
+            </script>"#,
+        );
+
+        let products = crawler.extract_products(&document);
+
+        assert_eq!(products.len(), 1);
+        assert_eq!(products[0].title, "Watch 1");
+        assert_eq!(products[0].external_id, "/watch-1");
+        assert!((products[0].price - 1999.99).abs() < f64::EPSILON);
+        assert!(products[0].available);
+    }
+
+    #[test]
+    fn it_extracts_additional_info_from_structured_data() {
+        let crawler = DeclarativeCrawler::from_toml(ZERO_CONFIG_SPEC).unwrap();
+        let document = Html::parse_document(
+            r#"<script type="application/ld+json">
+            {"@type": "Product", "name": "Watch 1", "description": "Smart watch",
+             "image": "https://zero-shop.com/watch-1.jpg",
+             "offers": {"@type": "Offer", "price": 1999, "availability": "https://schema.org/OutOfStock"}}
+            </script>"#,
+        );
+
+        let info = crawler.extract_additional_info(&document, "/watch-1").unwrap();
+
+        assert_eq!(info.description, "<p>Smart watch</p>");
+        assert!(!info.available);
+        assert_eq!(info.image_urls, vec!["https://zero-shop.com/watch-1.jpg".to_string()]);
+    }
+
+    #[test]
+    fn it_has_sitemap_pattern_only_for_described_categories() {
+        let spec = ZERO_CONFIG_SPEC.replace(
+            r#"urls = ["/watches"]"#,
+            r#"urls = ["/watches"]
+            sitemap_pattern = "^https://zero-shop.com/watches/[^/]+$""#,
+        );
+        let crawler = DeclarativeCrawler::from_toml(&spec).unwrap();
+        let pattern = crawler
+            .get_sitemap_product_pattern(CategorySlug::Watches)
+            .unwrap();
+
+        assert!(pattern.is_match("https://zero-shop.com/watches/watch-1"));
+        assert!(!pattern.is_match("https://zero-shop.com/watches/watch-1/reviews"));
+        assert!(crawler
+            .get_sitemap_product_pattern(CategorySlug::Smartphone)
+            .is_none());
+    }
+
+    #[test]
+    fn it_overrides_only_configured_budget() {
+        let spec = ZERO_CONFIG_SPEC.replace(
+            r#"pagination = "?page={page}""#,
+            r#"pagination = "?page={page}"
+            requests_per_second = 0.5"#,
+        );
+        let budget = DeclarativeCrawler::from_toml(&spec)
+            .unwrap()
+            .get_request_budget();
+
+        assert_eq!(
+            budget,
+            RequestBudget {
+                requests_per_second: 0.5,
+                ..RequestBudget::default()
+            }
+        );
+        assert!(DeclarativeCrawler::from_toml(&spec.replace("0.5", "0")).is_err());
+    }
+
+    #[test]
+    fn it_builds_additional_info_url_from_href() {
+        let crawler = crawler();
+
+        assert_eq!(
+            crawler.get_additional_info_url("/phone-1"),
+            "https://test-shop.com/phone-1"
+        );
+        assert_eq!(
+            crawler.get_additional_info_url("https://cdn.test-shop.com/phone-1"),
+            "https://cdn.test-shop.com/phone-1"
+        );
+        assert_eq!(
+            crawler.get_additional_info_url("//m.test-shop.com/phone-1"),
+            "https://m.test-shop.com/phone-1"
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_selectors() {
+        let spec = SPEC.replace(".card__price", "..card__price");
+
+        assert!(DeclarativeCrawler::from_toml(&spec).is_err());
+    }
+
+    #[test]
+    fn it_rejects_names_which_can_not_be_looked_up() {
+        for name in &["Test-Shop", "testShop", "", "mi_shop_com"] {
+            let spec = SPEC.replace("\"test_shop_com\"", &format!("\"{}\"", name));
+
+            assert!(
+                DeclarativeCrawler::from_toml(&spec).is_err(),
+                "{} is accepted",
+                name
+            );
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use serde::Deserialize;
+
+use lib::my_enum::CurrencyEnum;
+
+use crate::db::entity::category::CategorySlug;
+
+#[derive(Debug, Deserialize)]
+pub struct CrawlerSpec {
+    /// `site_name` of the source, snake_case as in the `source` table.
+    pub name: String,
+    pub site_base: String,
+    pub currency: CurrencyEnum,
+    #[serde(default)]
+    pub logo: String,
+    /// Appended to every category url, `{page}` is replaced with the page number.
+    pub pagination: String,
+    #[serde(default = "default_concurrent_pages")]
+    pub concurrent_pages: usize,
+    /// Overrides the default request budget of the crawler.
+    pub requests_per_second: Option<f64>,
+    pub request_burst: Option<f64>,
+    /// Products are discovered via sitemap instead of pagination for the categories with `sitemap_pattern`.
+    pub sitemap: Option<String>,
+    #[serde(rename = "category")]
+    pub categories: Vec<CategorySpec>,
+    /// Products are taken from schema.org data of the page when listing is not described.
+    pub listing: Option<ListingSpec>,
+    /// Details are taken from schema.org data of the page when they are not described.
+    pub details: Option<DetailsSpec>,
+    /// Names of the files in `CHARACTERISTIC_DICTIONARIES_DIR`, the first one which knows the title is used.
+    #[serde(default)]
+    pub characteristic_dictionaries: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CategorySpec {
+    pub slug: CategorySlug,
+    /// Paths relative to the site base.
+    pub urls: Vec<String>,
+    /// Regex for the product urls of the category in the sitemap.
+    pub sitemap_pattern: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListingSpec {
+    pub item: String,
+    pub id: String,
+    #[serde(default = "default_id_attribute")]
+    pub id_attribute: String,
+    pub title: String,
+    /// Optional regex to pick the useful part of the title (e.g. only latin words).
+    pub title_regex: Option<String>,
+    pub price: String,
+    #[serde(default = "default_price_regex")]
+    pub price_regex: String,
+    pub available: String,
+    pub unavailable: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DetailsSpec {
+    pub description: String,
+    #[serde(default = "default_description_regex")]
+    pub description_regex: String,
+    pub available: String,
+    pub unavailable: String,
+    pub images: String,
+    #[serde(default = "default_image_lazy_attribute")]
+    pub image_lazy_attribute: String,
+    /// Title and value cells of the characteristics table, matched by their order.
+    pub characteristic_title: Option<String>,
+    pub characteristic_value: Option<String>,
+}
+
+fn default_concurrent_pages() -> usize {
+    1
+}
+
+fn default_id_attribute() -> String {
+    "href".to_string()
+}
+
+pub static DEFAULT_PRICE_REGEX: &str = r"[0-9][0-9\s]*([.,][0-9]+)?";
+
+fn default_price_regex() -> String {
+    DEFAULT_PRICE_REGEX.to_string()
+}
+
+fn default_description_regex() -> String {
+    r"(?ms)<p>.*?</p>|<h2>.*?</h2>|<ul>.*?</ul>".to_string()
+}
+
+fn default_image_lazy_attribute() -> String {
+    "data-src".to_string()
+}
@@ -0,0 +1,39 @@
+use scraper::{ElementRef, Html, Selector};
+
+use lib::dto::characteristic::TypedCharacteristic;
+
+use crate::parse::crawler::characteristic_dictionary::parse_characteristics;
+use crate::parse::crawler::characteristic_parser::combine_titles_and_values;
+use crate::parse::crawler::mi_shop_com::crawler::MiShopComCrawler;
+use crate::service::html_cleaner::inner_text;
+
+pub fn extract_characteristics(
+    crawler: &MiShopComCrawler,
+    document: &Html,
+    external_id: &str,
+) -> Vec<TypedCharacteristic> {
+    let characteristic_title_selector =
+        Selector::parse(".detail__table tr td.detail__table-one").unwrap();
+    let characteristic_value_selector =
+        Selector::parse(".detail__table tr td.detail__table-two").unwrap();
+    let characteristic_title_nodes = document.select(&characteristic_title_selector);
+    let characteristic_value_nodes = document.select(&characteristic_value_selector);
+
+    let titles: Vec<String> = characteristic_title_nodes
+        .into_iter()
+        .collect::<Vec<ElementRef>>()
+        .into_iter()
+        .map(|title| inner_text(&title.inner_html()).replace(":", ""))
+        .collect();
+
+    let values: Vec<String> = characteristic_value_nodes
+        .into_iter()
+        .collect::<Vec<ElementRef>>()
+        .into_iter()
+        .map(|title| inner_text(&title.inner_html()))
+        .collect();
+
+    let characteristics = combine_titles_and_values(&titles, &values);
+
+    parse_characteristics(crawler, &characteristics, external_id)
+}
@@ -9,13 +9,15 @@ use lib::my_enum::CurrencyEnum;
 
 use crate::db::entity::category::CategorySlug;
 use crate::db::entity::source::SourceName;
-use crate::dto::parsed_product::{AdditionalParsedProductInfo, LocalParsedProduct};
+use crate::dto::parsed_product::{
+    AdditionalParsedProductInfo, LocalParsedProduct, ProductIdentifiers,
+};
 use crate::parse::crawler::mi_shop_com::crawler::characteristics::extract_characteristics;
 use crate::parse::crawler::{get_html_nodes, Crawler, ProductHtmlSelectors};
+use crate::parse::util::variant::split_variant;
 use crate::ConsumerName;
 
 mod characteristics;
-mod media_format_parser;
 
 static SITE_BASE: &str = "https://mi-shop.com";
 
@@ -49,6 +51,10 @@ impl Crawler for MiShopComCrawler {
         ]
     }
 
+    fn get_characteristic_dictionaries(&self) -> Vec<String> {
+        vec![String::from("mi_shop_com"), String::from("ru")]
+    }
+
     fn get_next_page_urls(&self, category: CategorySlug) -> Vec<String> {
         let base = [SITE_BASE, "/ru/catalog/"].concat();
         let pagination = "/page/{page}/";
@@ -94,15 +100,8 @@ impl Crawler for MiShopComCrawler {
 
             let product_nodes = nodes.unwrap();
 
-            let title: String = {
-                let mut title_value = product_nodes.title.inner_html();
-                if title_value.contains('(') {
-                    // removing color information from title
-                    title_value = title_value.split('(').next().unwrap().trim().to_string();
-                }
-
-                title_value
-            };
+            // color and memory go to the variant, so the title is the same for all of them
+            let (title, variant) = split_variant(&product_nodes.title.inner_html());
 
             let price: f64 = {
                 let price_html = product_nodes.price.inner_html();
@@ -145,6 +144,8 @@ impl Crawler for MiShopComCrawler {
                 price,
                 available,
                 external_id,
+                variant,
+                identifiers: ProductIdentifiers::default(),
             });
         }
 
@@ -1,18 +1,51 @@
 pub use abstract_crawler::*;
+pub use characteristic_dictionary::load_characteristic_dictionaries;
+pub use declarative::{find_declarative_source, register_declarative_sources};
 pub use mi_shop_com::crawler::MiShopComCrawler;
 pub use samsung_shop_com_ua::SamsungShopComUaCrawler;
 pub use samsung_shop_com_ua::*;
+pub use structured_data::{extract_structured_product, extract_structured_products};
+
+use inflector::Inflector;
 
 use crate::db::entity::source::SourceName;
+use crate::parse::crawler::declarative::{get_declarative_crawler, get_declarative_crawlers};
 
+mod characteristic_dictionary;
 mod characteristic_parser;
 mod abstract_crawler;
+mod declarative;
 mod mi_shop_com;
 mod samsung_shop_com_ua;
+mod structured_data;
 
 pub fn get_crawler(source: &SourceName) -> &dyn Crawler {
     match source {
         SourceName::MiShopCom => &MiShopComCrawler {},
         SourceName::SamsungShopComUa => &SamsungShopComUaCrawler {},
+        SourceName::Declarative(source) => get_declarative_crawler(*source),
     }
 }
+
+pub fn get_all_crawlers() -> Vec<&'static dyn Crawler> {
+    get_hand_written_crawlers()
+        .iter()
+        .copied()
+        .chain(get_declarative_crawlers().iter().map(|c| c as &dyn Crawler))
+        .collect()
+}
+
+pub fn get_crawler_by_site_name(site_name: &str) -> Option<&'static dyn Crawler> {
+    get_hand_written_crawlers()
+        .iter()
+        .copied()
+        .find(|c| c.get_source().to_string().to_snake_case() == site_name)
+        .or_else(|| {
+            find_declarative_source(site_name)
+                .map(|source| get_declarative_crawler(source) as &dyn Crawler)
+        })
+}
+
+fn get_hand_written_crawlers() -> [&'static dyn Crawler; 2] {
+    [&MiShopComCrawler {}, &SamsungShopComUaCrawler {}]
+}
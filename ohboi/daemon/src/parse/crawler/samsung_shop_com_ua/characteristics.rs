@@ -0,0 +1,32 @@
+use scraper::{Html, Selector};
+
+use lib::dto::characteristic::TypedCharacteristic;
+
+use crate::parse::crawler::characteristic_dictionary::parse_characteristics;
+use crate::parse::crawler::characteristic_parser::combine_titles_and_values;
+use crate::parse::crawler::samsung_shop_com_ua::SamsungShopComUaCrawler;
+use crate::service::html_cleaner::inner_text;
+
+pub fn extract_characteristics(
+    crawler: &SamsungShopComUaCrawler,
+    document: &Html,
+    external_id: &str,
+) -> Vec<TypedCharacteristic> {
+    let characteristic_title_selector =
+        Selector::parse(".specifications .specifications-item_title").unwrap();
+    let characteristic_value_selector =
+        Selector::parse(".specifications .specifications-item_value").unwrap();
+
+    let titles: Vec<String> = document
+        .select(&characteristic_title_selector)
+        .map(|title| inner_text(&title.inner_html()).replace(":", ""))
+        .collect();
+    let values: Vec<String> = document
+        .select(&characteristic_value_selector)
+        .map(|value| inner_text(&value.inner_html()))
+        .collect();
+
+    let characteristics = combine_titles_and_values(&titles, &values);
+
+    parse_characteristics(crawler, &characteristics, external_id)
+}
@@ -7,10 +7,16 @@ use lib::my_enum::CurrencyEnum;
 
 use crate::db::entity::category::CategorySlug;
 use crate::db::entity::source::SourceName;
-use crate::dto::parsed_product::{AdditionalParsedProductInfo, LocalParsedProduct};
+use crate::dto::parsed_product::{
+    AdditionalParsedProductInfo, LocalParsedProduct, ProductIdentifiers,
+};
+use crate::parse::crawler::samsung_shop_com_ua::characteristics::extract_characteristics;
 use crate::parse::crawler::{get_html_nodes, Crawler, ProductHtmlSelectors};
+use crate::parse::util::variant::split_variant;
 use crate::ConsumerName;
 
+mod characteristics;
+
 static SITE_BASE: &str = "https://samsungshop.com.ua";
 
 #[derive(Clone)]
@@ -32,6 +38,11 @@ impl Crawler for SamsungShopComUaCrawler {
         vec![CategorySlug::Watches]
     }
 
+    /// Spec table is the same for `/ru/` and `/ua/` pages, so titles are looked up in both dictionaries.
+    fn get_characteristic_dictionaries(&self) -> Vec<String> {
+        vec![String::from("ru"), String::from("uk")]
+    }
+
     fn get_next_page_urls(&self, category: CategorySlug) -> Vec<String> {
         let base = [SITE_BASE, "/ru/"].concat();
         let pagination = "?page={page}";
@@ -77,11 +88,11 @@ impl Crawler for SamsungShopComUaCrawler {
 
             let product_nodes = nodes.unwrap();
 
-            let title: String = {
+            let (title, variant) = {
                 let title_value = product_nodes.title.inner_html();
                 let english_text = title_re.find(title_value.as_str()).unwrap();
 
-                english_text.as_str().trim().to_string()
+                split_variant(english_text.as_str())
             };
 
             let price: f64 = {
@@ -130,6 +141,8 @@ impl Crawler for SamsungShopComUaCrawler {
                 price,
                 available,
                 external_id,
+                variant,
+                identifiers: ProductIdentifiers::default(),
             });
         }
 
@@ -158,11 +171,12 @@ impl Crawler for SamsungShopComUaCrawler {
 
         if let (Some(description), Some(available)) = (description, available) {
             let image_urls = self.extract_images(document, external_id);
+            let characteristics = extract_characteristics(self, document, external_id);
             Some(AdditionalParsedProductInfo {
                 image_urls,
                 description,
                 available,
-                characteristics: vec![],
+                characteristics,
             })
         } else { None }
     }
@@ -0,0 +1,122 @@
+use scraper::{Html, Selector};
+use serde_json::Value;
+
+use crate::parse::crawler::structured_data::{parse_availability, parse_price, StructuredProduct};
+
+lazy_static! {
+    static ref SCRIPT_SELECTOR: Selector =
+        Selector::parse(r#"script[type="application/ld+json"]"#).unwrap();
+}
+
+pub fn extract_products(document: &Html) -> Vec<StructuredProduct> {
+    let mut products = vec![];
+
+    for script in document.select(&SCRIPT_SELECTOR) {
+        let content = script.text().collect::<String>();
+
+        // Broken blocks are quite common, they should not break the whole page.
+        if let Ok(value) = serde_json::from_str::<Value>(&content) {
+            collect_products(&value, false, &mut products);
+        }
+    }
+
+    products
+}
+
+/// `main_entity` marks products which are the `mainEntity` of the page they are found in
+fn collect_products(value: &Value, main_entity: bool, products: &mut Vec<StructuredProduct>) {
+    match value {
+        Value::Array(values) => {
+            for value in values {
+                collect_products(value, main_entity, products);
+            }
+        }
+        Value::Object(object) => {
+            if let Some(graph) = object.get("@graph") {
+                collect_products(graph, false, products);
+            }
+
+            if has_type(value, "Product") {
+                products.push(parse_product(
+                    value,
+                    main_entity || object.contains_key("mainEntityOfPage"),
+                ));
+            } else if has_type(value, "ItemList") {
+                if let Some(elements) = object.get("itemListElement") {
+                    collect_products(elements, false, products);
+                }
+            } else if has_type(value, "ListItem") {
+                if let Some(item) = object.get("item") {
+                    collect_products(item, false, products);
+                }
+            } else if let Some(entity) = object.get("mainEntity") {
+                collect_products(entity, true, products);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_product(product: &Value, main_entity: bool) -> StructuredProduct {
+    let offer = get_offer(product);
+
+    StructuredProduct {
+        main_entity,
+        title: get_string(product, "name"),
+        url: get_string(product, "url").or_else(|| offer.and_then(|o| get_string(o, "url"))),
+        description: get_string(product, "description"),
+        price: offer.and_then(|o| {
+            o.get("price")
+                .or_else(|| o.get("lowPrice"))
+                .and_then(parse_price)
+        }),
+        currency: offer.and_then(|o| get_string(o, "priceCurrency")),
+        available: offer
+            .and_then(|o| get_string(o, "availability"))
+            .and_then(|a| parse_availability(&a)),
+        image_urls: get_image_urls(product.get("image")),
+        gtin: ["gtin13", "gtin", "gtin14", "gtin12", "gtin8"]
+            .iter()
+            .find_map(|key| get_string(product, key)),
+        mpn: get_string(product, "mpn"),
+    }
+}
+
+/// First offer of the product, `offers` can be a single object or an array of them.
+fn get_offer(product: &Value) -> Option<&Value> {
+    match product.get("offers")? {
+        Value::Array(offers) => offers.first(),
+        offer => Some(offer),
+    }
+}
+
+fn get_image_urls(image: Option<&Value>) -> Vec<String> {
+    match image {
+        Some(Value::String(url)) => vec![url.clone()],
+        Some(Value::Array(images)) => images
+            .iter()
+            .flat_map(|image| get_image_urls(Some(image)))
+            .collect(),
+        Some(image @ Value::Object(_)) => get_string(image, "url")
+            .or_else(|| get_string(image, "contentUrl"))
+            .into_iter()
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn has_type(value: &Value, expected_type: &str) -> bool {
+    match value.get("@type") {
+        Some(Value::String(value_type)) => value_type == expected_type,
+        Some(Value::Array(types)) => types.iter().any(|t| t == expected_type),
+        _ => false,
+    }
+}
+
+fn get_string(value: &Value, key: &str) -> Option<String> {
+    match value.get(key)? {
+        Value::String(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,102 @@
+use scraper::{ElementRef, Html, Selector};
+use serde_json::Value;
+
+use crate::parse::crawler::structured_data::{parse_availability, parse_price, StructuredProduct};
+
+lazy_static! {
+    static ref PRODUCT_SELECTOR: Selector =
+        Selector::parse(r#"[itemscope][itemtype*="schema.org/Product"]"#).unwrap();
+}
+
+pub fn extract_products(document: &Html) -> Vec<StructuredProduct> {
+    document.select(&PRODUCT_SELECTOR).map(parse_product).collect()
+}
+
+fn parse_product(product: ElementRef) -> StructuredProduct {
+    let offer = find_properties(product, "offers").into_iter().next();
+
+    StructuredProduct {
+        main_entity: has_property_name(product, "mainEntity"),
+        title: get_property(product, "name"),
+        url: get_property(product, "url"),
+        description: get_html_property(product, "description"),
+        price: offer
+            .and_then(|o| get_property(o, "price").or_else(|| get_property(o, "lowPrice")))
+            .and_then(|price| parse_price(&Value::String(price))),
+        currency: offer.and_then(|o| get_property(o, "priceCurrency")),
+        available: offer
+            .and_then(|o| get_property(o, "availability"))
+            .and_then(|a| parse_availability(&a)),
+        image_urls: find_properties(product, "image")
+            .into_iter()
+            .filter_map(get_value)
+            .collect(),
+        gtin: ["gtin13", "gtin", "gtin14", "gtin12", "gtin8"]
+            .iter()
+            .find_map(|name| get_property(product, name)),
+        mpn: get_property(product, "mpn"),
+    }
+}
+
+fn get_property(scope: ElementRef, name: &str) -> Option<String> {
+    find_properties(scope, name)
+        .into_iter()
+        .next()
+        .and_then(get_value)
+}
+
+fn get_html_property(scope: ElementRef, name: &str) -> Option<String> {
+    let node = find_properties(scope, name).into_iter().next()?;
+    let value = node
+        .value()
+        .attr("content")
+        .map_or_else(|| node.inner_html(), ToString::to_string);
+    let value = value.trim();
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Properties of the item itself, properties of the nested items (e.g. related products) belong to them
+fn find_properties<'a>(scope: ElementRef<'a>, name: &str) -> Vec<ElementRef<'a>> {
+    let mut properties = vec![];
+
+    for node in scope.children().filter_map(ElementRef::wrap) {
+        if has_property_name(node, name) {
+            properties.push(node);
+        }
+        if node.value().attr("itemscope").is_none() {
+            properties.extend(find_properties(node, name));
+        }
+    }
+
+    properties
+}
+
+/// `itemprop` can hold a few space separated names
+fn has_property_name(node: ElementRef, name: &str) -> bool {
+    node.value()
+        .attr("itemprop")
+        .map_or(false, |names| names.split_whitespace().any(|n| n == name))
+}
+
+/// Microdata keeps values in attributes (`content`, `href`, `src`) or in the text of the node.
+fn get_value(node: ElementRef) -> Option<String> {
+    let element = node.value();
+    let value = element
+        .attr("content")
+        .or_else(|| element.attr("href"))
+        .or_else(|| element.attr("src"))
+        .map(ToString::to_string)
+        .unwrap_or_else(|| node.text().collect::<String>());
+    let value = value.trim();
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
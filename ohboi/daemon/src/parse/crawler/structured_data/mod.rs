@@ -0,0 +1,345 @@
+use scraper::Html;
+use serde_json::Value;
+
+use crate::service::html_cleaner::clean_html;
+
+mod json_ld;
+mod microdata;
+
+/// Product described with schema.org vocabulary, either in JSON-LD or microdata.
+#[derive(Debug, Default, PartialEq)]
+pub struct StructuredProduct {
+    /// Product is the `mainEntity` of the page, other products of the page are related ones
+    pub main_entity: bool,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub available: Option<bool>,
+    pub image_urls: Vec<String>,
+    pub gtin: Option<String>,
+    pub mpn: Option<String>,
+}
+
+impl StructuredProduct {
+    /// Description as html, in the same form as `abstract_extract_description` returns it.
+    pub fn description_html(&self) -> Option<String> {
+        self.description.as_ref().map(|description| {
+            let description = description.trim();
+
+            if description.contains('<') {
+                clean_html(description)
+            } else {
+                clean_html(&["<p>", description, "</p>"].concat())
+            }
+        })
+    }
+}
+
+/// All products found on the page, JSON-LD has priority over microdata.
+pub fn extract_structured_products(document: &Html) -> Vec<StructuredProduct> {
+    let products = json_ld::extract_products(document);
+
+    if products.is_empty() {
+        microdata::extract_products(document)
+    } else {
+        products
+    }
+}
+
+/// Product which the page is about: the main entity of the page or the product with the url of the page.
+/// Pages describe related products too, so without these hints only a single product is trusted.
+pub fn extract_structured_product(document: &Html, page_url: &str) -> Option<StructuredProduct> {
+    let mut products = extract_structured_products(document);
+    let page_path = get_path(page_url);
+
+    let position = products
+        .iter()
+        .position(|product| product.main_entity)
+        .or_else(|| {
+            products
+                .iter()
+                .position(|product| product.url.as_deref().map(get_path) == Some(page_path))
+        })
+        .or_else(|| {
+            let mut without_url = products
+                .iter()
+                .enumerate()
+                .filter(|(_, product)| products.len() == 1 || product.url.is_none())
+                .map(|(position, _)| position);
+
+            match (without_url.next(), without_url.next()) {
+                (Some(position), None) => Some(position),
+                _ => None,
+            }
+        })?;
+
+    Some(products.swap_remove(position))
+}
+
+/// `https://shop.com/phone-1/?color=red` | `/phone-1` -> `/phone-1`
+fn get_path(url: &str) -> &str {
+    let path = match url.find("//") {
+        Some(scheme_end) => {
+            let host_start = scheme_end + 2;
+            url[host_start..]
+                .find('/')
+                .map_or("", |path_start| &url[host_start + path_start..])
+        }
+        None => url,
+    };
+
+    path.split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('/')
+}
+
+/// `https://schema.org/InStock` | `InStock` -> `true`
+fn parse_availability(value: &str) -> Option<bool> {
+    let availability = value.rsplit('/').next().unwrap_or(value).trim();
+
+    match availability {
+        "InStock" | "LimitedAvailability" | "OnlineOnly" | "InStoreOnly" | "PreOrder"
+        | "PreSale" => Some(true),
+        "OutOfStock" | "SoldOut" | "Discontinued" | "BackOrder" => Some(false),
+        _ => None,
+    }
+}
+
+/// `"12 999,00"` | `12999` -> `12999.0`
+fn parse_price(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => parse_price_text(text),
+        _ => None,
+    }
+}
+
+/// The last separator is the decimal one, the others group thousands, e.g. `1,299.00` or `1.299,00`
+fn parse_price_text(text: &str) -> Option<f64> {
+    let price: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let is_separator = |c: char| c == ',' || c == '.';
+
+    match price.rfind(is_separator) {
+        Some(position) => format!(
+            "{}.{}",
+            price[..position].replace(is_separator, ""),
+            &price[position + 1..]
+        )
+        .parse()
+        .ok(),
+        None => price.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+    use serde_json::Value;
+
+    use crate::parse::crawler::structured_data::{
+        extract_structured_product, extract_structured_products, parse_price, StructuredProduct,
+    };
+
+    static PAGE_URL: &str = "https://shop.com/redmi-note-10-pro";
+
+    #[test]
+    fn it_extracts_json_ld_product() {
+        let document = Html::parse_document(
+            r#"<html><head>
+            <script type="application/ld+json">
+            {
+                "@context": "https://schema.org/",
+                "@type": "Product",
+                "name": "Redmi Note 10 Pro",
+                "image": ["https://shop.com/1.jpg", {"@type": "ImageObject", "url": "https://shop.com/2.jpg"}],
+                "description": "Good phone",
+                "gtin13": "6934177734335",
+                "mpn": "M2101K6G",
+                "offers": {
+                    "@type": "Offer",
+                    "url": "https://shop.com/redmi-note-10-pro",
+                    "priceCurrency": "UAH",
+                    "price": "8 999,50",
+                    "availability": "https://schema.org/InStock"
+                }
+            }
+            </script>
+            </head><body></body></html>"#,
+        );
+
+        assert_eq!(
+            extract_structured_product(&document, PAGE_URL),
+            Some(StructuredProduct {
+                main_entity: false,
+                title: Some("Redmi Note 10 Pro".to_string()),
+                url: Some("https://shop.com/redmi-note-10-pro".to_string()),
+                description: Some("Good phone".to_string()),
+                price: Some(8999.5),
+                currency: Some("UAH".to_string()),
+                available: Some(true),
+                image_urls: vec![
+                    "https://shop.com/1.jpg".to_string(),
+                    "https://shop.com/2.jpg".to_string()
+                ],
+                gtin: Some("6934177734335".to_string()),
+                mpn: Some("M2101K6G".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn it_extracts_products_from_graph_and_item_list() {
+        let document = Html::parse_document(
+            r#"<script type="application/ld+json">
+            {"@graph": [
+                {"@type": "Organization", "name": "Shop"},
+                {"@type": "ItemList", "itemListElement": [
+                    {"@type": "ListItem", "position": 1, "item": {
+                        "@type": "Product", "name": "Phone 1", "url": "/phone-1",
+                        "offers": {"@type": "AggregateOffer", "lowPrice": 100, "highPrice": 120}
+                    }},
+                    {"@type": "ListItem", "position": 2, "item": {
+                        "@type": ["Product", "Thing"], "name": "Phone 2", "url": "/phone-2",
+                        "offers": [{"@type": "Offer", "price": 200, "availability": "OutOfStock"}]
+                    }}
+                ]}
+            ]}
+            </script>"#,
+        );
+
+        let products = extract_structured_products(&document);
+
+        assert_eq!(products.len(), 2);
+        assert_eq!(products[0].title, Some("Phone 1".to_string()));
+        assert_eq!(products[0].price, Some(100.0));
+        assert_eq!(products[0].available, None);
+        assert_eq!(products[1].url, Some("/phone-2".to_string()));
+        assert_eq!(products[1].available, Some(false));
+    }
+
+    #[test]
+    fn it_skips_broken_json_ld() {
+        let document = Html::parse_document(
+            r#"<script type="application/ld+json">{"@type": "Product", </script>"#,
+        );
+
+        assert_eq!(extract_structured_product(&document, PAGE_URL), None);
+    }
+
+    #[test]
+    fn it_extracts_microdata_product() {
+        let document = Html::parse_document(
+            r#"<div itemscope itemtype="https://schema.org/Product">
+                <h1 itemprop="name"> Galaxy Watch 4 </h1>
+                <img itemprop="image" src="/watch.jpg">
+                <meta itemprop="gtin" content="8806092570000">
+                <div itemprop="description"><p>Smart watch</p></div>
+                <div itemprop="offers" itemscope itemtype="https://schema.org/Offer">
+                    <span itemprop="price" content="7999">7 999 грн</span>
+                    <meta itemprop="priceCurrency" content="UAH">
+                    <link itemprop="availability" href="https://schema.org/OutOfStock">
+                </div>
+            </div>"#,
+        );
+
+        let product = extract_structured_product(&document, PAGE_URL).unwrap();
+
+        assert_eq!(product.title, Some("Galaxy Watch 4".to_string()));
+        assert_eq!(product.price, Some(7999.0));
+        assert_eq!(product.currency, Some("UAH".to_string()));
+        assert_eq!(product.available, Some(false));
+        assert_eq!(product.image_urls, vec!["/watch.jpg".to_string()]);
+        assert_eq!(product.gtin, Some("8806092570000".to_string()));
+        assert_eq!(product.description_html(), Some("<p>Smart watch</p>".to_string()));
+    }
+
+    #[test]
+    fn it_prefers_main_entity_over_related_products() {
+        let document = Html::parse_document(
+            r#"<script type="application/ld+json">
+            [
+                {"@type": "Product", "name": "Related phone", "url": "/related"},
+                {"@type": "ItemPage", "mainEntity": {"@type": "Product", "name": "Phone"}}
+            ]
+            </script>"#,
+        );
+
+        let product = extract_structured_product(&document, PAGE_URL).unwrap();
+
+        assert!(product.main_entity);
+        assert_eq!(product.title, Some("Phone".to_string()));
+    }
+
+    #[test]
+    fn it_picks_product_with_url_of_page() {
+        let document = Html::parse_document(
+            r#"<script type="application/ld+json">
+            [
+                {"@type": "Product", "name": "Related phone", "url": "/related"},
+                {"@type": "Product", "name": "Phone", "url": "/redmi-note-10-pro/"},
+                {"@type": "Product", "name": "Recommended phone"}
+            ]
+            </script>"#,
+        );
+
+        let product = extract_structured_product(&document, PAGE_URL).unwrap();
+
+        assert_eq!(product.title, Some("Phone".to_string()));
+    }
+
+    #[test]
+    fn it_does_not_guess_between_related_products() {
+        let document = Html::parse_document(
+            r#"<script type="application/ld+json">
+            [
+                {"@type": "Product", "name": "Related phone 1", "url": "/related-1"},
+                {"@type": "Product", "name": "Related phone 2", "url": "/related-2"}
+            ]
+            </script>"#,
+        );
+
+        assert_eq!(extract_structured_product(&document, PAGE_URL), None);
+    }
+
+    #[test]
+    fn it_skips_properties_of_nested_microdata_items() {
+        let document = Html::parse_document(
+            r#"<div itemscope itemtype="https://schema.org/Product">
+                <div itemprop="isRelatedTo" itemscope itemtype="https://schema.org/Product">
+                    <span itemprop="name">Related phone</span>
+                    <img itemprop="image" src="/related.jpg">
+                    <a itemprop="url" href="/related"></a>
+                </div>
+                <h1 itemprop="name">Phone</h1>
+                <img itemprop="image" src="/phone.jpg">
+            </div>"#,
+        );
+
+        let product = extract_structured_product(&document, PAGE_URL).unwrap();
+
+        assert_eq!(product.title, Some("Phone".to_string()));
+        assert_eq!(product.image_urls, vec!["/phone.jpg".to_string()]);
+    }
+
+    #[test]
+    fn it_returns_nothing_without_structured_data() {
+        let document = Html::parse_document("<div><h1>Phone</h1></div>");
+
+        assert!(extract_structured_products(&document).is_empty());
+    }
+
+    #[test]
+    fn it_parses_prices_with_separators() {
+        let price = |text: &str| parse_price(&Value::String(text.to_string()));
+
+        assert_eq!(price("1,299.00"), Some(1299.0));
+        assert_eq!(price("1.299,00"), Some(1299.0));
+        assert_eq!(price("24 990,50"), Some(24990.5));
+        assert_eq!(price("24990"), Some(24990.0));
+        assert_eq!(price("1,234,567.89"), Some(1_234_567.89));
+        assert_eq!(price("price"), None);
+    }
+}
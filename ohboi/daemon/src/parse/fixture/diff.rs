@@ -0,0 +1,104 @@
+use serde_json::Value;
+
+use crate::parse::fixture::Snapshot;
+
+/// Human readable list of differences between two snapshots, one line per changed value.
+pub fn diff_snapshots(expected: &Snapshot, actual: &Snapshot) -> Vec<String> {
+    let mut differences = vec![];
+
+    diff_values(
+        "",
+        &serde_json::to_value(expected).unwrap(),
+        &serde_json::to_value(actual).unwrap(),
+        &mut differences,
+    );
+
+    differences
+}
+
+fn diff_values(path: &str, expected: &Value, actual: &Value, differences: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                let key_path = format!("{}.{}", path, key);
+
+                match actual.get(key) {
+                    Some(actual_value) => {
+                        diff_values(&key_path, expected_value, actual_value, differences)
+                    }
+                    None => differences.push(format!("- {}: {}", key_path, expected_value)),
+                }
+            }
+            for (key, actual_value) in actual {
+                if !expected.contains_key(key) {
+                    differences.push(format!("+ {}.{}: {}", path, key, actual_value));
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            for index in 0..expected.len().max(actual.len()) {
+                let index_path = format!("{}[{}]", path, index);
+
+                match (expected.get(index), actual.get(index)) {
+                    (Some(e), Some(a)) => diff_values(&index_path, e, a, differences),
+                    (Some(e), None) => differences.push(format!("- {}: {}", index_path, e)),
+                    (None, Some(a)) => differences.push(format!("+ {}: {}", index_path, a)),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ => {
+            if expected != actual {
+                differences.push(format!("- {}: {}", path, expected));
+                differences.push(format!("+ {}: {}", path, actual));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::parsed_product::{LocalParsedProduct, ParsedVariant, ProductIdentifiers};
+    use crate::parse::fixture::{diff_snapshots, Snapshot};
+
+    fn listing(titles: &[&str]) -> Snapshot {
+        Snapshot::Listing {
+            products: titles
+                .iter()
+                .map(|title| LocalParsedProduct {
+                    title: (*title).to_string(),
+                    price: 10.0,
+                    available: true,
+                    external_id: "/id".to_string(),
+                    variant: ParsedVariant::default(),
+                    identifiers: ProductIdentifiers::default(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn it_finds_no_difference_in_same_snapshots() {
+        assert!(diff_snapshots(&listing(&["a"]), &listing(&["a"])).is_empty());
+    }
+
+    #[test]
+    fn it_shows_changed_values() {
+        assert_eq!(
+            diff_snapshots(&listing(&["a"]), &listing(&["b"])),
+            vec![
+                r#"- .products[0].title: "a""#.to_string(),
+                r#"+ .products[0].title: "b""#.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_shows_missing_and_new_items() {
+        assert_eq!(
+            diff_snapshots(&listing(&["a", "b"]), &listing(&["a"])).len(),
+            1
+        );
+        assert!(diff_snapshots(&listing(&["a"]), &listing(&["a", "b"]))[0].starts_with("+ .products[1]"));
+    }
+}
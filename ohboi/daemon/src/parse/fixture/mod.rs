@@ -0,0 +1,259 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::{Captures, Regex};
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+
+use crate::dto::parsed_product::{AdditionalParsedProductInfo, LocalParsedProduct};
+use crate::parse::crawler::{get_crawler_by_site_name, Crawler};
+use crate::service::request::get;
+use crate::FixtureKind;
+
+#[cfg(test)]
+mod diff;
+
+#[cfg(test)]
+pub use diff::diff_snapshots;
+
+pub static FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+static PAGE_FILE: &str = "page.html";
+static SNAPSHOT_FILE: &str = "snapshot.json";
+
+lazy_static! {
+    static ref NOT_PARSED_RE: Regex = Regex::new(concat!(
+        r"(?is)<script\b([^>]*)>.*?</script>",
+        r"|<style\b.*?</style>|<noscript\b.*?</noscript>|<!--.*?-->"
+    ))
+    .unwrap();
+    static ref BLANK_LINES_RE: Regex = Regex::new(r"\n\s*\n").unwrap();
+}
+
+/// Captured page together with the expected result of its parsing.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Fixture {
+    pub url: String,
+    pub expected: Snapshot,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Snapshot {
+    Listing {
+        products: Vec<LocalParsedProduct>,
+    },
+    Details {
+        external_id: String,
+        info: Option<DetailsSnapshot>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DetailsSnapshot {
+    pub image_urls: Vec<String>,
+    pub description: String,
+    pub available: bool,
+    /// Debug representation of every `TypedCharacteristic`, sorted.
+    pub characteristics: Vec<String>,
+}
+
+impl From<AdditionalParsedProductInfo> for DetailsSnapshot {
+    fn from(info: AdditionalParsedProductInfo) -> Self {
+        let mut characteristics: Vec<String> = info
+            .characteristics
+            .iter()
+            .map(|c| format!("{:?}", c))
+            .collect();
+        characteristics.sort();
+
+        DetailsSnapshot {
+            image_urls: info.image_urls,
+            description: info.description,
+            available: info.available,
+            characteristics,
+        }
+    }
+}
+
+/// Downloads the page and saves it with the current parsing result as a new fixture.
+pub async fn record_fixture(
+    site_name: &str,
+    url: &str,
+    name: &str,
+    kind: FixtureKind,
+) -> Result<PathBuf, String> {
+    let crawler = get_crawler_by_site_name(site_name)
+        .ok_or_else(|| format!("Unknown source: {}", site_name))?;
+    let html = trim_page(&get(url).await.map_err(|e| format!("{:?}", e))?);
+
+    let external_id = crawler.get_external_id_from_url(url);
+    let expected = match kind {
+        FixtureKind::Listing => parse_listing(crawler, &html),
+        FixtureKind::Details => parse_details(crawler, &html, external_id),
+    };
+
+    let dir = Path::new(FIXTURES_DIR).join(site_name).join(name);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    fs::write(dir.join(PAGE_FILE), &html).map_err(|e| e.to_string())?;
+    save_fixture(
+        &dir,
+        &Fixture {
+            url: url.to_string(),
+            expected,
+        },
+    )?;
+
+    Ok(dir)
+}
+
+/// Downloads the page of the existing fixture again from its url and saves it with the current parsing result.
+pub async fn rerecord_fixture(site_name: &str, name: &str) -> Result<PathBuf, String> {
+    let fixture = read_fixture(&Path::new(FIXTURES_DIR).join(site_name).join(name))?;
+    let kind = match fixture.expected {
+        Snapshot::Listing { .. } => FixtureKind::Listing,
+        Snapshot::Details { .. } => FixtureKind::Details,
+    };
+
+    record_fixture(site_name, &fixture.url, name, kind).await
+}
+
+/// Parses the stored page the same way as it was done while recording.
+#[cfg(test)]
+pub fn replay_fixture(crawler: &dyn Crawler, fixture: &Fixture, html: &str) -> Snapshot {
+    match &fixture.expected {
+        Snapshot::Listing { .. } => parse_listing(crawler, html),
+        Snapshot::Details { external_id, .. } => {
+            parse_details(crawler, html, external_id.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+pub fn load_fixture(dir: &Path) -> Result<(Fixture, String), String> {
+    let fixture = read_fixture(dir)?;
+    let html = fs::read_to_string(dir.join(PAGE_FILE)).map_err(|e| e.to_string())?;
+
+    Ok((fixture, html))
+}
+
+fn read_fixture(dir: &Path) -> Result<Fixture, String> {
+    let snapshot = fs::read_to_string(dir.join(SNAPSHOT_FILE)).map_err(|e| e.to_string())?;
+
+    serde_json::from_str(&snapshot).map_err(|e| e.to_string())
+}
+
+pub fn save_fixture(dir: &Path, fixture: &Fixture) -> Result<(), String> {
+    let snapshot = serde_json::to_string_pretty(fixture).map_err(|e| e.to_string())?;
+
+    fs::write(dir.join(SNAPSHOT_FILE), snapshot + "\n").map_err(|e| e.to_string())
+}
+
+/// Drops what crawlers never look at (scripts except structured data, styles, comments),
+/// so the captured page is small enough to be committed.
+fn trim_page(html: &str) -> String {
+    let trimmed = NOT_PARSED_RE.replace_all(html, |caps: &Captures| match caps.get(1) {
+        Some(attributes) if attributes.as_str().contains("ld+json") => caps[0].to_string(),
+        _ => String::new(),
+    });
+
+    BLANK_LINES_RE.replace_all(&trimmed, "\n").to_string()
+}
+
+fn parse_listing(crawler: &dyn Crawler, html: &str) -> Snapshot {
+    let document = Html::parse_document(html);
+
+    Snapshot::Listing {
+        products: crawler.extract_products(&document),
+    }
+}
+
+fn parse_details(crawler: &dyn Crawler, html: &str, external_id: String) -> Snapshot {
+    let document = Html::parse_document(html);
+    let info = crawler
+        .extract_additional_info(&document, &external_id)
+        .map(DetailsSnapshot::from);
+
+    Snapshot::Details { external_id, info }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::parse::crawler::get_crawler_by_site_name;
+    use crate::parse::fixture::{
+        diff_snapshots, load_fixture, replay_fixture, save_fixture, trim_page, Fixture,
+        FIXTURES_DIR,
+    };
+
+    /// Run with `UPDATE_FIXTURES=1` to accept the current parsing result as expected.
+    #[test]
+    fn it_parses_fixtures_as_recorded() {
+        let update = std::env::var("UPDATE_FIXTURES").is_ok();
+        let mut failures: Vec<String> = vec![];
+
+        for (site_name, dir) in fixture_dirs() {
+            let crawler = get_crawler_by_site_name(&site_name)
+                .expect(&format!("No crawler for fixtures of {}", site_name));
+            let (fixture, html) = load_fixture(&dir).unwrap();
+            let actual = replay_fixture(crawler, &fixture, &html);
+
+            if actual == fixture.expected {
+                continue;
+            }
+
+            if update {
+                save_fixture(
+                    &dir,
+                    &Fixture {
+                        url: fixture.url,
+                        expected: actual,
+                    },
+                )
+                .unwrap();
+            } else {
+                failures.push(format!(
+                    "{:?}:\n{}",
+                    dir,
+                    diff_snapshots(&fixture.expected, &actual).join("\n")
+                ));
+            }
+        }
+
+        assert!(failures.is_empty(), "\n{}", failures.join("\n\n"));
+    }
+
+    #[test]
+    fn it_trims_only_not_parsed_parts_of_page() {
+        let html = r#"<head><style>.a{}</style><script src="/app.js"></script>
+<!-- counter -->
+
+<script type="application/ld+json">{"@type":"Product"}</script></head>
+<body><div class="price">1 ₽</div><script>window.a = 1;</script></body>"#;
+
+        assert_eq!(
+            trim_page(html),
+            r#"<head>
+<script type="application/ld+json">{"@type":"Product"}</script></head>
+<body><div class="price">1 ₽</div></body>"#
+        );
+    }
+
+    fn fixture_dirs() -> Vec<(String, PathBuf)> {
+        let mut dirs = vec![];
+
+        for source_dir in fs::read_dir(FIXTURES_DIR).unwrap().flatten() {
+            let site_name = source_dir.file_name().to_string_lossy().to_string();
+
+            for fixture_dir in fs::read_dir(source_dir.path()).unwrap().flatten() {
+                if fixture_dir.path().is_dir() {
+                    dirs.push((site_name.clone(), fixture_dir.path()));
+                }
+            }
+        }
+        dirs.sort();
+
+        dirs
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::hash_map::DefaultHasher;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
+
+use chrono::{NaiveDateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use lib::error_reporting;
+use lib::error_reporting::ReportingContext;
+
+use crate::db::entity::raw_page::{NewRawPage, RawPageKind};
+use crate::db::entity::source::SourceName;
+use crate::db::repository::raw_page;
+use crate::db::repository::source::get_source;
+use crate::service::blob_store::{get_archive_store, BlobStore, BlobStoreError};
+use crate::{ConsumerName, SETTINGS};
+
+/// Longer ids are cut in the key, the hash keeps them distinct
+const MAX_KEY_ID_LENGTH: usize = 100;
+
+/// Saves compressed html of the details page to the archive by the external id of the offer,
+/// so it can be parsed again later. Only `ARCHIVE_KEEP_PAGES` latest pages of the offer are kept.
+/// Failure to archive doesn't fail the crawl.
+pub async fn archive_page(source: SourceName, external_id: &str, html: &str) {
+    let store = match get_archive_store() {
+        Some(store) => store,
+        None => return,
+    };
+
+    let kind = RawPageKind::Details;
+    let key = get_blob_key(source, kind, external_id, Utc::now().naive_utc());
+    let data = compress(html);
+    let size = data.len();
+
+    if let Err(e) = store.put(&key, data).await {
+        report_failure(&format!(
+            "[{source}] Failed to archive {kind} page {id}: {error}",
+            source = source,
+            kind = kind,
+            id = external_id,
+            error = e
+        ));
+        return;
+    }
+
+    let source_id = get_source(source).id;
+    raw_page::create(&NewRawPage {
+        source_id,
+        category_id: None,
+        kind: kind.to_string(),
+        external_id,
+        blob_key: &key,
+        size: i32::try_from(size).unwrap_or(i32::MAX),
+    });
+
+    prune_pages(store, source_id, kind, external_id).await;
+}
+
+/// Pages which failed to be deleted from the store keep their rows and are tried again next time
+async fn prune_pages(store: &dyn BlobStore, source_id: i32, kind: RawPageKind, external_id: &str) {
+    let mut deleted_ids = vec![];
+
+    // the page which is just archived is always kept
+    let keep = SETTINGS.archive.keep_pages.max(1);
+
+    for (id, blob_key) in raw_page::get_outdated_pages(source_id, kind, external_id, keep) {
+        match store.delete(&blob_key).await {
+            Ok(()) => deleted_ids.push(id),
+            Err(e) => report_failure(&format!(
+                "Failed to prune archived page {key}: {error}",
+                key = blob_key,
+                error = e
+            )),
+        }
+    }
+
+    if !deleted_ids.is_empty() {
+        raw_page::delete_many(&deleted_ids);
+    }
+}
+
+fn report_failure(message: &str) {
+    error_reporting::warning(
+        message,
+        &ReportingContext {
+            executor: &ConsumerName::ParseDetails,
+            action: "archive_page",
+        },
+    );
+}
+
+/// Reads html of the archived page back
+pub async fn read_archived_page(blob_key: &str) -> Result<String, BlobStoreError> {
+    let store = get_archive_store()
+        .ok_or_else(|| BlobStoreError::Io(String::from("Archive is turned off")))?;
+    let data = store.get(blob_key).await?;
+
+    let mut html = String::new();
+    GzDecoder::new(data.as_slice())
+        .read_to_string(&mut html)
+        .map_err(|e| BlobStoreError::Io(format!("Can't decompress {}: {}", blob_key, e)))?;
+
+    Ok(html)
+}
+
+fn compress(html: &str) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder
+        .write_all(html.as_bytes())
+        .expect("Failed to compress page");
+
+    encoder.finish().expect("Failed to compress page")
+}
+
+/// `{source}/{kind}/{readable id}-{hash of id}/{time}.html.gz`
+fn get_blob_key(
+    source: SourceName,
+    kind: RawPageKind,
+    external_id: &str,
+    created_at: NaiveDateTime,
+) -> String {
+    let mut hasher = DefaultHasher::new();
+    external_id.hash(&mut hasher);
+
+    let readable_id: String = external_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_KEY_ID_LENGTH)
+        .collect();
+
+    format!(
+        "{source}/{kind}/{id}-{hash:016x}/{time}.html.gz",
+        source = source,
+        kind = kind,
+        id = readable_id,
+        hash = hasher.finish(),
+        time = created_at.format("%Y%m%dT%H%M%S%.3f")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::db::entity::raw_page::RawPageKind;
+    use crate::db::entity::source::SourceName;
+    use crate::parse::layer::archive::get_blob_key;
+
+    #[test]
+    fn it_makes_path_safe_keys() {
+        let created_at = NaiveDate::from_ymd(2021, 11, 15).and_hms_milli(10, 20, 30, 400);
+
+        let key = get_blob_key(
+            SourceName::MiShopCom,
+            RawPageKind::Details,
+            "/ua/catalog/smartphones/redmi-note-10-pro/",
+            created_at,
+        );
+
+        assert!(key.starts_with(&format!(
+            "{}/details/_ua_catalog_smartphones_redmi_note_10_pro_-",
+            SourceName::MiShopCom
+        )));
+        assert!(key.ends_with("/20211115T102030.400.html.gz"));
+    }
+
+    #[test]
+    fn it_keeps_similar_ids_apart() {
+        let created_at = NaiveDate::from_ymd(2021, 11, 15).and_hms(10, 20, 30);
+
+        assert_ne!(
+            get_blob_key(
+                SourceName::MiShopCom,
+                RawPageKind::Details,
+                "a/b",
+                created_at
+            ),
+            get_blob_key(
+                SourceName::MiShopCom,
+                RawPageKind::Details,
+                "a_b",
+                created_at
+            ),
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use lib::db;
+use lib::db::repository::price_range::{disable_without_live_offers, recompute_price_range};
+use lib::diesel::result::Error;
+use lib::diesel::Connection;
+use lib::error_reporting;
+use lib::error_reporting::ReportingContext;
+
+use crate::db::entity::category::CategorySlug;
+use crate::db::entity::source::SourceName;
+use crate::db::repository::category::get_category;
+use crate::db::repository::source_product::{
+    count_enabled_in_category, disable_unseen_in_category,
+};
+use crate::{ConsumerName, SETTINGS};
+
+/// Disables offers of the category which the completed crawl hasn't seen,
+/// then recalculates their products from the offers which are left.
+pub fn delist_unseen_products(
+    source: SourceName,
+    category: CategorySlug,
+    seen_external_ids: &HashSet<String>,
+) {
+    let context = ReportingContext {
+        executor: &ConsumerName::ParseCategory,
+        action: "delist_unseen_products",
+    };
+    let category_id = get_category(category).id;
+    let live_offers = count_enabled_in_category(source, category_id);
+
+    if looks_truncated(
+        seen_external_ids.len(),
+        live_offers,
+        SETTINGS.delisting.safety_ratio,
+    ) {
+        let message = format!(
+            "[{source}] Delisting skipped ({category}): crawl has seen {seen} products of {live} live offers",
+            source = source,
+            category = category,
+            seen = seen_external_ids.len(),
+            live = live_offers
+        );
+        error_reporting::warning(message.as_str(), &context);
+        return;
+    }
+
+    let seen: Vec<&str> = seen_external_ids.iter().map(String::as_str).collect();
+    let product_ids = disable_unseen_in_category(source, category_id, &seen);
+    if product_ids.is_empty() {
+        return;
+    }
+
+    let connection = &db::establish_connection();
+    for product_id in &product_ids {
+        connection
+            .transaction::<_, Error, _>(|| {
+                disable_without_live_offers(connection, *product_id)?;
+                recompute_price_range(connection, *product_id)
+            })
+            .expect("Failed to recalculate delisted product");
+    }
+
+    let message = format!(
+        "[{source}] Offers of {amount} products delisted ({category})",
+        source = source,
+        amount = product_ids.len(),
+        category = category
+    );
+    error_reporting::info(message.as_str(), &context);
+}
+
+/// Empty or much shorter than usual crawl is more likely a broken page than a sold out category
+#[allow(clippy::cast_precision_loss)]
+fn looks_truncated(seen: usize, live_offers: i64, safety_ratio: f64) -> bool {
+    seen == 0 || (seen as f64) < live_offers as f64 * safety_ratio
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::layer::delist::looks_truncated;
+
+    #[test]
+    fn it_treats_empty_crawl_as_truncated() {
+        assert!(looks_truncated(0, 0, 0.5));
+        assert!(looks_truncated(0, 10, 0.5));
+    }
+
+    #[test]
+    fn it_treats_much_shorter_crawl_as_truncated() {
+        assert!(looks_truncated(4, 10, 0.5));
+        assert!(!looks_truncated(5, 10, 0.5));
+        assert!(!looks_truncated(12, 10, 0.5));
+    }
+
+    #[test]
+    fn it_allows_any_crawl_of_new_category() {
+        assert!(!looks_truncated(1, 0, 0.5));
+    }
+}
@@ -1 +1,3 @@
-pub mod save;
\ No newline at end of file
+pub mod archive;
+pub mod delist;
+pub mod save;
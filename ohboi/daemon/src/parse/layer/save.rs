@@ -7,9 +7,11 @@ use lib::service::currency_converter::convert_from_with_rate;
 use crate::db::entity::category::CategorySlug;
 use crate::db::entity::source::SourceName;
 use crate::db::repository::product::create_if_not_exists;
-use crate::db::repository::source_product::link_to_product;
+use crate::db::repository::product_variant;
+use crate::db::repository::source_product::{link_to_product, LinkOutcome};
 use crate::dto::parsed_product::{InternationalParsedProduct, LocalParsedProduct};
 use crate::queue::postpone::postpone_details_parsing;
+use crate::service::crawl_run::record;
 use crate::SETTINGS;
 
 pub async fn save_parsed_products(
@@ -39,14 +41,9 @@ async fn save_parsed_product(
     category: CategorySlug,
     rate: f64,
 ) {
-    let international_parsed_product = InternationalParsedProduct {
-        title: parsed_product.title,
-        price: convert_from_with_rate(parsed_product.price, rate),
-        original_price: parsed_product.price,
-        available: parsed_product.available,
-        external_id: parsed_product.external_id,
-    };
-    let product = create_if_not_exists(&international_parsed_product, category);
+    let international_parsed_product = to_international(parsed_product, rate);
+    let product = create_if_not_exists(&international_parsed_product, category, source);
+    let variant = product_variant::create_if_not_exists(&product, &international_parsed_product);
 
     if product.description.is_none() || product.images.is_none() {
         postpone_details_parsing(
@@ -58,5 +55,44 @@ async fn save_parsed_product(
             .expect("Can't postpone deatils parsing");
     }
 
-    link_to_product(&product, &international_parsed_product, source);
+    let outcome = link_to_product(&product, &variant, &international_parsed_product, source);
+    record_link(&outcome);
+}
+
+/// Saves the product extracted from its details page, details themselves are saved by the caller.
+pub fn save_discovered_product(
+    source: SourceName,
+    currency: CurrencyEnum,
+    parsed_product: LocalParsedProduct,
+    category: CategorySlug,
+) -> i32 {
+    let rate = try_get_exchange_rate_by_code(currency);
+    let international_parsed_product = to_international(parsed_product, rate);
+    let product = create_if_not_exists(&international_parsed_product, category, source);
+    let variant = product_variant::create_if_not_exists(&product, &international_parsed_product);
+
+    let outcome = link_to_product(&product, &variant, &international_parsed_product, source);
+    record_link(&outcome);
+
+    product.id
+}
+
+fn record_link(outcome: &LinkOutcome) {
+    match outcome {
+        LinkOutcome::New => record(|run| run.products_new += 1),
+        LinkOutcome::Updated => record(|run| run.products_updated += 1),
+        LinkOutcome::Unchanged => record(|run| run.products_unchanged += 1),
+    }
+}
+
+fn to_international(parsed_product: LocalParsedProduct, rate: f64) -> InternationalParsedProduct {
+    InternationalParsedProduct {
+        title: parsed_product.title,
+        price: convert_from_with_rate(parsed_product.price, rate),
+        original_price: parsed_product.price,
+        available: parsed_product.available,
+        external_id: parsed_product.external_id,
+        variant: parsed_product.variant,
+        identifiers: parsed_product.identifiers,
+    }
 }
@@ -1,6 +1,7 @@
 pub use pub_api::*;
 
 pub mod crawler;
+pub mod fixture;
 mod layer;
 mod pub_api;
-mod util;
+pub mod util;
@@ -1,7 +1,11 @@
 pub use parse_category::*;
 pub use parse_category_page::*;
 pub use parse_details::*;
+pub use parse_sitemap::*;
+pub use reparse::*;
 
 mod parse_category;
 mod parse_category_page;
 mod parse_details;
+mod parse_sitemap;
+mod reparse;
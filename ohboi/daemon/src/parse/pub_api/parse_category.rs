@@ -1,4 +1,6 @@
-use futures::future::join_all;
+use std::collections::HashSet;
+
+use futures::{stream, StreamExt};
 
 use lib::error_reporting;
 use lib::error_reporting::ReportingContext;
@@ -6,99 +8,391 @@ use lib::error_reporting::ReportingContext;
 use crate::db::entity::category::CategorySlug;
 use crate::db::entity::source::SourceName;
 use crate::dto::parsed_product::LocalParsedProduct;
-use crate::parse::crawler::get_crawler;
+use crate::parse::crawler::{get_crawler, Crawler};
+use crate::parse::layer::delist::delist_unseen_products;
 use crate::parse::layer::save::save_parsed_products;
-use crate::parse::util::dedup::dedup_products;
+use crate::parse::pub_api::parse_sitemap::parse_category_sitemap;
+use crate::parse::util::dedup::retain_new_products;
 use crate::parse::util::parse_html;
 use crate::queue::postpone::postpone_page_parsing;
-use crate::service::request::get_s;
+use crate::service::crawl_run::record;
+use crate::service::request::{get, RequestError};
 use crate::ConsumerName;
 
+/// Safety net for the sites which never return an empty page
+const MAX_PAGES: usize = 10000;
+
 pub async fn parse_category(
     source: SourceName,
     category: CategorySlug,
-) -> Result<(), reqwest::Error> {
-    let mut products: Vec<LocalParsedProduct> = vec![];
-    let concurrent_pages = 1; // TODO move to the db settings of specific crawler
-
+) -> Result<(), RequestError> {
     let crawler = get_crawler(&source);
 
+    if let (Some(sitemap_url), Some(product_pattern)) = (
+        crawler.get_sitemap_url(),
+        crawler.get_sitemap_product_pattern(category),
+    ) {
+        return parse_category_sitemap(crawler, category, sitemap_url, &product_pattern).await;
+    }
+
+    // Same product may be listed under several urls of the category, it is saved once
+    let mut seen_external_ids: HashSet<String> = HashSet::new();
+    let mut complete = true;
+
     for url in crawler.get_next_page_urls(category) {
-        for page in (1..10000).step_by(concurrent_pages) {
-            let mut page_requests = vec![];
-            for page in page..page + concurrent_pages {
-                let url = url.replace("{page}", (page).to_string().as_ref());
+        complete &= parse_pages(crawler, &url, category, &mut seen_external_ids).await;
+    }
+
+    // Products of the postponed pages are not seen yet
+    if complete {
+        delist_unseen_products(crawler.get_source(), category, &seen_external_ids);
+    }
+
+    Ok(())
+}
+
+/// Pages are requested concurrently but processed in order, every page is saved as soon as it is parsed.
+/// Pages which are already requested beyond the last one are dropped.
+/// Returns false if some pages were not parsed.
+async fn parse_pages(
+    crawler: &dyn Crawler,
+    url: &str,
+    category: CategorySlug,
+    seen_external_ids: &mut HashSet<String>,
+) -> bool {
+    let source = crawler.get_source();
+    let mut pagination = Pagination::new(crawler.get_concurrent_pages());
+
+    let mut pages = stream::iter(1..MAX_PAGES)
+        .map(|page| {
+            let page_url = url.replace("{page}", page.to_string().as_ref());
+
+            async move {
+                let response = get(&page_url).await;
+
+                (page_url, response)
+            }
+        })
+        .buffered(pagination.concurrent_pages);
+
+    while let Some((page_url, response)) = pages.next().await {
+        let products = match response {
+            Ok(response_data) => {
+                record(|run| run.pages += 1);
+
+                Ok(parse_html(&response_data, crawler))
+            }
+            Err(e) => Err(e),
+        };
 
-                page_requests.push(get_s(url));
+        match pagination.next_page(products, seen_external_ids, source) {
+            PageAction::Save(products) => {
+                save_parsed_products(source, crawler.get_currency(), products, category).await;
             }
+            PageAction::Postpone(e) => {
+                record(|run| run.failures += 1);
+                error_reporting::warning(
+                    format!(
+                        "Request for page failed[{source}]: {error:?}",
+                        source = source,
+                        error = e
+                    )
+                    .as_str(),
+                    &ReportingContext {
+                        executor: &ConsumerName::ParseCategory,
+                        action: "parse_category",
+                    },
+                );
 
-            let page_responses = join_all(page_requests).await;
-            let mut all_successful = true;
-            // To prevent endless requests if site is down
-            let mut amount_of_fails = 0;
-
-            let mut current_page = page;
-
-            // TODO rewrite in stream and Mutex (daemon in then and call next request)
-            for response in page_responses {
-                match response {
-                    Ok(response_data) => {
-                        let parsed = parse_html(&response_data, crawler);
-                        let mut amount_of_duplicates = 0;
-                        let parsed_amount = parsed.len();
-
-                        for parsed_product in parsed {
-                            let will_be_duplicated = products
-                                .iter()
-                                .any(|p| p.external_id == parsed_product.external_id);
-
-                            if will_be_duplicated {
-                                amount_of_duplicates += 1;
-                            } else {
-                                products.push(parsed_product);
-                            }
-                        }
-                        all_successful = all_successful
-                            && parsed_amount != 0 // Some sites return empty page
-                            && amount_of_duplicates != parsed_amount; // But some return the last page (samsung)
-                    }
-                    Err(e) => {
-                        amount_of_fails += 1;
-                        error_reporting::warning(
-                            format!(
-                                "Request for page failed[{source}]: {error:?}",
-                                source = source,
-                                error = e
-                            )
-                            .as_str(),
-                            &ReportingContext {
-                                executor: &ConsumerName::ParseCategory,
-                                action: "parse_category",
-                            },
-                        );
-
-                        postpone_page_parsing(
-                            url.replace("{page}", (current_page).to_string().as_ref()),
-                            source,
-                            category,
-                        )
-                        .await
-                        .expect("Failed to postpone page parsing");
-                    }
+                postpone_page_parsing(page_url, source, category)
+                    .await
+                    .expect("Failed to postpone page parsing");
+            }
+            PageAction::Abort(e) => {
+                record(|run| run.failures += 1);
+                error_reporting::warning(
+                    format!(
+                        "Pagination stopped by unavailable page[{source}]: {error:?}",
+                        source = source,
+                        error = e
+                    )
+                    .as_str(),
+                    &ReportingContext {
+                        executor: &ConsumerName::ParseCategory,
+                        action: "parse_category",
+                    },
+                );
+            }
+            PageAction::Stop => {}
+        }
+
+        if pagination.finished {
+            break;
+        }
+    }
+
+    pagination.complete
+}
+
+#[derive(Debug)]
+enum PageAction {
+    Save(Vec<LocalParsedProduct>),
+    Postpone(RequestError),
+    /// Page won't be available on retry, the rest of the pages is unknown
+    Abort(RequestError),
+    Stop,
+}
+
+/// Pages of one category url, fed in order
+#[derive(Debug)]
+struct Pagination {
+    concurrent_pages: usize,
+    /// Products of this url only, the last page is the one without new products among them
+    seen_external_ids: HashSet<String>,
+    // To prevent endless requests if site is down
+    amount_of_fails: usize,
+    postponed: bool,
+    finished: bool,
+    /// The page without new products is reached and every page before it is parsed
+    complete: bool,
+}
+
+impl Pagination {
+    fn new(concurrent_pages: usize) -> Self {
+        Pagination {
+            concurrent_pages: concurrent_pages.max(1),
+            seen_external_ids: HashSet::new(),
+            amount_of_fails: 0,
+            postponed: false,
+            finished: false,
+            complete: false,
+        }
+    }
+
+    /// `category_external_ids` are the products of all urls of the category, which are saved already
+    fn next_page(
+        &mut self,
+        page: Result<Vec<LocalParsedProduct>, RequestError>,
+        category_external_ids: &mut HashSet<String>,
+        source: SourceName,
+    ) -> PageAction {
+        match page {
+            Ok(mut products) => {
+                retain_new_products(&mut products, &mut self.seen_external_ids, source);
+
+                // Some sites return empty page, but some return the last page again (samsung)
+                if products.is_empty() {
+                    self.complete = !self.postponed;
+                    self.finished = true;
+
+                    return PageAction::Stop;
                 }
 
-                current_page += 1;
+                self.amount_of_fails = 0;
+                products
+                    .retain(|product| category_external_ids.insert(product.external_id.clone()));
+
+                PageAction::Save(products)
+            }
+            // 403 of robots.txt or 404 doesn't tell that there are no more products, so nothing is delisted
+            Err(e) if e.is_permanent() => {
+                self.complete = false;
+                self.finished = true;
+
+                PageAction::Abort(e)
             }
+            Err(e) => {
+                self.amount_of_fails += 1;
+                self.postponed = true;
+                self.finished = self.amount_of_fails == self.concurrent_pages;
 
-            // if last page
-            if !all_successful || amount_of_fails == concurrent_pages {
+                PageAction::Postpone(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use reqwest::StatusCode;
+
+    use crate::db::entity::source::SourceName;
+    use crate::dto::parsed_product::{LocalParsedProduct, ParsedVariant, ProductIdentifiers};
+    use crate::parse::pub_api::parse_category::{PageAction, Pagination};
+    use crate::service::request::RequestError;
+
+    fn product(external_id: &str) -> LocalParsedProduct {
+        LocalParsedProduct {
+            title: "Redmi Note 10 Pro".to_string(),
+            price: 24990.0,
+            available: true,
+            external_id: external_id.to_string(),
+            variant: ParsedVariant::default(),
+            identifiers: ProductIdentifiers::default(),
+        }
+    }
+
+    /// External ids of the saved pages, `None` for the failed ones
+    fn read_pages(
+        pagination: &mut Pagination,
+        pages: Vec<Result<Vec<LocalParsedProduct>, RequestError>>,
+    ) -> Vec<Option<Vec<String>>> {
+        read_url_pages(pagination, &mut HashSet::new(), pages)
+    }
+
+    fn read_url_pages(
+        pagination: &mut Pagination,
+        category_external_ids: &mut HashSet<String>,
+        pages: Vec<Result<Vec<LocalParsedProduct>, RequestError>>,
+    ) -> Vec<Option<Vec<String>>> {
+        let mut actions = vec![];
+
+        for page in pages {
+            match pagination.next_page(page, category_external_ids, SourceName::MiShopCom) {
+                PageAction::Save(products) => {
+                    actions.push(Some(products.into_iter().map(|p| p.external_id).collect()))
+                }
+                PageAction::Postpone(_) | PageAction::Abort(_) => actions.push(None),
+                PageAction::Stop => {}
+            }
+
+            if pagination.finished {
                 break;
             }
         }
+
+        actions
     }
-    dedup_products(&mut products, source);
 
-    save_parsed_products(source, crawler.get_currency(), products, category).await;
+    #[test]
+    fn it_saves_only_unseen_products_and_stops_on_repeated_page() {
+        let mut pagination = Pagination::new(2);
 
-    Ok(())
+        let actions = read_pages(
+            &mut pagination,
+            vec![
+                Ok(vec![product("/1"), product("/2"), product("/2")]),
+                Ok(vec![product("/2"), product("/3")]),
+                Ok(vec![product("/3"), product("/1")]),
+                Ok(vec![product("/4")]),
+            ],
+        );
+
+        assert_eq!(
+            actions,
+            vec![
+                Some(vec!["/1".to_string(), "/2".to_string()]),
+                Some(vec!["/3".to_string()])
+            ]
+        );
+        assert!(pagination.complete);
+    }
+
+    #[test]
+    fn it_reads_every_page_of_url_which_lists_products_of_previous_one() {
+        let mut category_external_ids = HashSet::new();
+        let mut first_url = Pagination::new(2);
+        let mut second_url = Pagination::new(2);
+
+        read_url_pages(
+            &mut first_url,
+            &mut category_external_ids,
+            vec![Ok(vec![product("/1"), product("/2")]), Ok(vec![])],
+        );
+        let actions = read_url_pages(
+            &mut second_url,
+            &mut category_external_ids,
+            vec![
+                Ok(vec![product("/1"), product("/2")]),
+                Ok(vec![product("/3")]),
+                Ok(vec![]),
+            ],
+        );
+
+        assert_eq!(actions, vec![Some(vec![]), Some(vec!["/3".to_string()])]);
+        assert!(second_url.complete);
+        assert_eq!(category_external_ids.len(), 3);
+    }
+
+    #[test]
+    fn it_postpones_failed_pages_and_gives_up_when_all_concurrent_ones_fail() {
+        let mut pagination = Pagination::new(2);
+        let failure = || Err(RequestError::Transient("timeout".to_string()));
+
+        let actions = read_pages(
+            &mut pagination,
+            vec![
+                failure(),
+                Ok(vec![product("/1")]),
+                failure(),
+                failure(),
+                Ok(vec![product("/2")]),
+            ],
+        );
+
+        assert_eq!(
+            actions,
+            vec![None, Some(vec!["/1".to_string()]), None, None]
+        );
+        assert!(pagination.finished);
+        assert!(!pagination.complete);
+    }
+
+    #[test]
+    fn it_is_incomplete_when_last_page_is_reached_after_postponed_one() {
+        let mut pagination = Pagination::new(2);
+
+        read_pages(
+            &mut pagination,
+            vec![
+                Ok(vec![product("/1")]),
+                Err(RequestError::Transient("timeout".to_string())),
+                Ok(vec![]),
+            ],
+        );
+
+        assert!(pagination.finished);
+        assert!(!pagination.complete);
+    }
+
+    #[test]
+    fn it_is_incomplete_when_page_fails_permanently() {
+        let mut pagination = Pagination::new(2);
+
+        let actions = read_pages(
+            &mut pagination,
+            vec![
+                Ok(vec![product("/1")]),
+                Err(RequestError::Permanent {
+                    url: "https://mi-shop.com/ru/catalog/smartphones/page/2/".to_string(),
+                    status: StatusCode::FORBIDDEN,
+                }),
+                Ok(vec![product("/2")]),
+            ],
+        );
+
+        assert_eq!(actions, vec![Some(vec!["/1".to_string()]), None]);
+        assert!(pagination.finished);
+        assert!(!pagination.complete);
+    }
+
+    #[test]
+    fn it_is_incomplete_when_page_is_disallowed() {
+        let mut pagination = Pagination::new(2);
+
+        read_pages(
+            &mut pagination,
+            vec![
+                Ok(vec![product("/1")]),
+                Err(RequestError::Disallowed(
+                    "https://mi-shop.com/ru/catalog/smartphones/page/2/".to_string(),
+                )),
+            ],
+        );
+
+        assert!(pagination.finished);
+        assert!(!pagination.complete);
+    }
 }
@@ -4,16 +4,25 @@ use crate::parse::crawler::get_crawler;
 use crate::parse::layer::save::save_parsed_products;
 use crate::parse::util::dedup::dedup_products;
 use crate::parse::util::parse_html;
-use crate::service::request::get;
+use crate::service::crawl_run::record;
+use crate::service::request::{get_if_modified, save_cache_validators, Fetched, RequestError};
 
 pub async fn parse_category_page(
     url: &str,
     source: SourceName,
     category: CategorySlug,
-) -> Result<(), reqwest::Error> {
+) -> Result<(), RequestError> {
     let crawler = get_crawler(&source);
 
-    let response = get(url).await?;
+    let fetched = get_if_modified(url).await.map_err(|e| {
+        record(|run| run.failures += 1);
+        e
+    })?;
+    let (response, validators) = match fetched {
+        Fetched::Modified { body, validators } => (body, validators),
+        Fetched::NotModified => return Ok(()),
+    };
+    record(|run| run.pages += 1);
     let mut products = parse_html(&response, crawler);
 
     dedup_products(&mut products, source);
@@ -26,5 +35,7 @@ pub async fn parse_category_page(
     )
     .await;
 
+    save_cache_validators(&validators);
+
     Ok(())
 }
@@ -3,25 +3,103 @@ use scraper::Html;
 use lib::error_reporting;
 use lib::error_reporting::ReportingContext;
 
+use crate::db::entity::category::CategorySlug;
 use crate::dto::parsed_product::AdditionalParsedProductInfo;
 use crate::parse::crawler::Crawler;
-use crate::service::request::get;
+use crate::parse::layer::archive::archive_page;
+use crate::parse::layer::save::save_discovered_product;
+use crate::service::crawl_run::record;
+use crate::service::request::{get_if_modified, Fetched, RequestError};
 use crate::ConsumerName;
 
+/// Details page which hasn't changed since the last successful parsing is not parsed again.
 pub async fn parse_details(
     external_id: &str,
     crawler: &dyn Crawler,
-) -> Option<AdditionalParsedProductInfo> {
+) -> Result<Fetched<Option<AdditionalParsedProductInfo>>, RequestError> {
+    let page = get_details_page(external_id, crawler).await?;
+
+    Ok(page.map(|data| {
+        let document = Html::parse_document(&data);
+
+        crawler.extract_additional_info(&document, external_id)
+    }))
+}
+
+/// Parses the product found only in the sitemap: the product is extracted and saved from its details page.
+/// Returns the id of the saved product with its additional info.
+pub async fn parse_discovered_product(
+    external_id: &str,
+    crawler: &dyn Crawler,
+    category: CategorySlug,
+) -> Result<Fetched<Option<(i32, AdditionalParsedProductInfo)>>, RequestError> {
+    let page = get_details_page(external_id, crawler).await?;
+
+    Ok(page.map(|data| parse_discovered_page(&data, external_id, crawler, category)))
+}
+
+fn parse_discovered_page(
+    data: &str,
+    external_id: &str,
+    crawler: &dyn Crawler,
+    category: CategorySlug,
+) -> Option<(i32, AdditionalParsedProductInfo)> {
+    let document = Html::parse_document(data);
+
+    let product = crawler.extract_product_from_details(&document, external_id);
+    let details = crawler.extract_additional_info(&document, external_id);
+
+    match product {
+        None => {
+            let message = format!(
+                "[parse_details] No product found on the details page [{source}] for: {id}",
+                source = crawler.get_source().to_string(),
+                id = external_id,
+            );
+            error_reporting::warning(
+                message.as_str(),
+                &ReportingContext {
+                    executor: &ConsumerName::ParseDetails,
+                    action: "parse_discovered_product",
+                },
+            );
+
+            None
+        }
+        Some(product) => {
+            let product_id = save_discovered_product(
+                crawler.get_source(),
+                crawler.get_currency(),
+                product,
+                category,
+            );
+
+            details.map(|details| (product_id, details))
+        }
+    }
+}
+
+async fn get_details_page(
+    external_id: &str,
+    crawler: &dyn Crawler,
+) -> Result<Fetched<String>, RequestError> {
     let url = crawler.get_additional_info_url(external_id);
-    let data = get(&url).await;
 
-    match data {
-        Ok(data) => {
-            let document = Html::parse_document(&data);
+    match get_if_modified(&url).await {
+        Ok(page) => {
+            if let Fetched::Modified { body, .. } = &page {
+                record(|run| run.pages += 1);
+                archive_page(crawler.get_source(), external_id, body).await;
+            }
 
-            crawler.extract_additional_info(&document, external_id)
+            Ok(page)
+        }
+        Err(e) if e.is_permanent() => {
+            record(|run| run.failures += 1);
+            Err(e)
         }
         Err(e) => {
+            record(|run| run.failures += 1);
             let message = format!(
                 "[parse_details] Request for additional data failed! [{source}] {error:?}",
                 source = crawler.get_source().to_string(),
@@ -35,7 +113,7 @@ pub async fn parse_details(
                 },
             );
 
-            None
+            Err(e)
         }
     }
 }
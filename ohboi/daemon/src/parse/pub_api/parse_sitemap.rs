@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDateTime;
+use regex::Regex;
+
+use lib::error_reporting;
+use lib::error_reporting::ReportingContext;
+
+use crate::db::entity::category::CategorySlug;
+use crate::db::entity::source::SourceName;
+use crate::db::repository::source_product::get_by_source_and_external_id;
+use crate::parse::crawler::Crawler;
+use crate::parse::layer::delist::delist_unseen_products;
+use crate::parse::util::sitemap::{decode_sitemap, parse_sitemap, Sitemap};
+use crate::queue::postpone::postpone_discovered_details_parsing;
+use crate::service::crawl_run::record;
+use crate::service::request::{get_bytes, RequestError};
+use crate::ConsumerName;
+
+/// Postpones details parsing of every category product from the sitemap which has changed since the last crawl.
+pub async fn parse_category_sitemap(
+    crawler: &dyn Crawler,
+    category: CategorySlug,
+    sitemap_url: String,
+    product_pattern: &Regex,
+) -> Result<(), RequestError> {
+    let source = crawler.get_source();
+    let context = ReportingContext {
+        executor: &ConsumerName::ParseCategory,
+        action: "parse_category_sitemap",
+    };
+    let mut visited_sitemaps: HashSet<String> = HashSet::new();
+    let mut sitemaps_to_visit = vec![sitemap_url.clone()];
+    let mut seen_external_ids: HashSet<String> = HashSet::new();
+    let mut complete = true;
+
+    while let Some(url) = sitemaps_to_visit.pop() {
+        if !visited_sitemaps.insert(url.clone()) {
+            continue;
+        }
+
+        let bytes = match get_bytes(&url).await {
+            Ok(bytes) => bytes,
+            Err(e) if url == sitemap_url => {
+                record(|run| run.failures += 1);
+                return Err(e);
+            }
+            Err(e) => {
+                record(|run| run.failures += 1);
+                let message = format!(
+                    "[{source}] Request for sitemap {url} failed: {error:?}",
+                    source = source,
+                    url = url,
+                    error = e
+                );
+                error_reporting::warning(message.as_str(), &context);
+                complete = false;
+                continue;
+            }
+        };
+
+        record(|run| run.pages += 1);
+
+        match decode_sitemap(&bytes).and_then(|xml| parse_sitemap(&xml)) {
+            Ok(Sitemap::Index(sitemaps)) => {
+                sitemaps_to_visit.extend(sitemaps.into_iter().map(|sitemap| sitemap.loc));
+            }
+            Ok(Sitemap::UrlSet(pages)) => {
+                for page in pages {
+                    if !product_pattern.is_match(&page.loc) {
+                        continue;
+                    }
+
+                    let external_id = crawler.get_external_id_from_url(&page.loc);
+                    seen_external_ids.insert(external_id.clone());
+                    if is_up_to_date(source, &external_id, page.lastmod) {
+                        continue;
+                    }
+
+                    postpone_discovered_details_parsing(external_id, source, category)
+                        .await
+                        .expect("Failed to postpone details parsing");
+                }
+            }
+            Err(e) => {
+                let message = format!(
+                    "[{source}] Sitemap {url} can't be parsed: {error}",
+                    source = source,
+                    url = url,
+                    error = e
+                );
+                error_reporting::warning(message.as_str(), &context);
+                complete = false;
+            }
+        }
+    }
+
+    if complete {
+        delist_unseen_products(source, category, &seen_external_ids);
+    }
+
+    Ok(())
+}
+
+/// Disabled offers are always rechecked, delisting touches `updated_at` without parsing the page
+fn is_up_to_date(source: SourceName, external_id: &str, lastmod: Option<NaiveDateTime>) -> bool {
+    lastmod.map_or(false, |lastmod| {
+        get_by_source_and_external_id(source, external_id).map_or(false, |source_product| {
+            source_product.enabled && source_product.updated_at >= lastmod
+        })
+    })
+}
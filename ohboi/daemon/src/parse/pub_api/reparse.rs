@@ -0,0 +1,69 @@
+use scraper::Html;
+
+use crate::db::entity::raw_page::RawPageKind;
+use crate::db::repository::product::update_reparsed_details;
+use crate::db::repository::raw_page::get_latest_details_pages;
+use crate::db::repository::source::get_source;
+use crate::db::repository::source_product::get_by_source_and_external_id;
+use crate::parse::crawler::Crawler;
+use crate::parse::layer::archive::read_archived_page;
+use crate::service::unknown_characteristic::collect_unknown_characteristics;
+
+#[derive(Debug, Default)]
+pub struct ReparseSummary {
+    pub updated: usize,
+    /// Pages without details or without the offer in db
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+/// Parses the latest archived details page of every offer of the source again and updates their products.
+/// Nothing is requested from the source, so improved parsers can be applied to all the known products at once.
+/// Unknown characteristics of the pages are collected like during a crawl.
+pub async fn reparse_archived_pages(crawler: &dyn Crawler, limit: i64) -> ReparseSummary {
+    collect_unknown_characteristics(crawler.get_source(), async {
+        let mut summary = ReparseSummary::default();
+
+        for (external_id, blob_key) in
+            get_latest_details_pages(get_source(crawler.get_source()).id, limit)
+        {
+            match read_archived_page(&blob_key).await {
+                Ok(html) => {
+                    if reparse_page(&html, &external_id, crawler) {
+                        summary.updated += 1;
+                    } else {
+                        summary.skipped += 1;
+                    }
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    log::warn!(
+                        "[{source}] Failed to read archived {kind} page {id}: {error}",
+                        source = crawler.get_source(),
+                        kind = RawPageKind::Details,
+                        id = external_id,
+                        error = e
+                    );
+                }
+            }
+        }
+
+        summary
+    })
+    .await
+}
+
+fn reparse_page(html: &str, external_id: &str, crawler: &dyn Crawler) -> bool {
+    let document = Html::parse_document(html);
+
+    let details = crawler.extract_additional_info(&document, external_id);
+    let source_product = get_by_source_and_external_id(crawler.get_source(), external_id);
+
+    match (details, source_product) {
+        (Some(details), Some(source_product)) => {
+            update_reparsed_details(source_product.product_id, crawler.get_source(), &details);
+            true
+        }
+        _ => false,
+    }
+}
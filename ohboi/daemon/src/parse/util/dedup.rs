@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use crate::dto::parsed_product::LocalParsedProduct;
 use crate::db::entity::source::SourceName;
 use lib::error_reporting;
@@ -22,4 +24,53 @@ pub fn dedup_products(products: &mut Vec<LocalParsedProduct>, source: SourceName
 
         a.external_id == b.external_id
     });
-}
\ No newline at end of file
+}
+
+/// Leaves only the products which haven't been seen yet and remembers them
+pub fn retain_new_products(
+    products: &mut Vec<LocalParsedProduct>,
+    seen_external_ids: &mut HashSet<String>,
+    source: SourceName,
+) {
+    dedup_products(products, source);
+    products.retain(|product| seen_external_ids.insert(product.external_id.clone()));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::db::entity::source::SourceName;
+    use crate::dto::parsed_product::{LocalParsedProduct, ParsedVariant, ProductIdentifiers};
+    use crate::parse::util::dedup::retain_new_products;
+
+    fn product(external_id: &str) -> LocalParsedProduct {
+        LocalParsedProduct {
+            title: "Redmi Note 10 Pro".to_string(),
+            price: 24990.0,
+            available: true,
+            external_id: external_id.to_string(),
+            variant: ParsedVariant::default(),
+            identifiers: ProductIdentifiers::default(),
+        }
+    }
+
+    #[test]
+    fn it_retains_only_unseen_products() {
+        let mut seen = HashSet::new();
+        let mut first_page = vec![product("/1"), product("/2"), product("/2")];
+        retain_new_products(&mut first_page, &mut seen, SourceName::MiShopCom);
+
+        assert_eq!(first_page, vec![product("/1"), product("/2")]);
+
+        let mut repeated_page = vec![product("/2"), product("/1")];
+        retain_new_products(&mut repeated_page, &mut seen, SourceName::MiShopCom);
+
+        assert!(repeated_page.is_empty());
+
+        let mut next_page = vec![product("/1"), product("/3")];
+        retain_new_products(&mut next_page, &mut seen, SourceName::MiShopCom);
+
+        assert_eq!(next_page, vec![product("/3")]);
+    }
+}
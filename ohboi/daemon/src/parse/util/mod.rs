@@ -4,6 +4,8 @@ use crate::dto::parsed_product::LocalParsedProduct;
 use crate::parse::crawler::Crawler;
 
 pub mod dedup;
+pub mod sitemap;
+pub mod variant;
 
 pub fn parse_html(data: &str, crawler: &dyn Crawler) -> Vec<LocalParsedProduct> {
     let document = Html::parse_document(data);
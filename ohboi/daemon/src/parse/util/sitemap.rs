@@ -0,0 +1,152 @@
+use std::io::Read;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use flate2::read::GzDecoder;
+
+static GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, PartialEq)]
+pub enum Sitemap {
+    /// `<sitemapindex>` which points to other sitemaps.
+    Index(Vec<SitemapEntry>),
+    /// `<urlset>` with the pages of the site.
+    UrlSet(Vec<SitemapEntry>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SitemapEntry {
+    pub loc: String,
+    pub lastmod: Option<NaiveDateTime>,
+}
+
+/// Sitemaps can be served as `.xml.gz` files, so they are decompressed if needed.
+pub fn decode_sitemap(bytes: &[u8]) -> Result<String, String> {
+    if bytes.starts_with(&GZIP_MAGIC_BYTES) {
+        let mut xml = String::new();
+        GzDecoder::new(bytes)
+            .read_to_string(&mut xml)
+            .map_err(|e| format!("Can't decompress sitemap: {}", e))?;
+
+        Ok(xml)
+    } else {
+        String::from_utf8(bytes.to_vec()).map_err(|e| format!("Sitemap is not utf8: {}", e))
+    }
+}
+
+pub fn parse_sitemap(xml: &str) -> Result<Sitemap, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+    let root = document.root_element();
+
+    let entry_tag = match root.tag_name().name() {
+        "sitemapindex" => "sitemap",
+        "urlset" => "url",
+        tag => return Err(format!("Unknown sitemap root tag: {}", tag)),
+    };
+
+    let entries = root
+        .children()
+        .filter(|node| node.tag_name().name() == entry_tag)
+        .filter_map(|node| {
+            let child_text = |name: &str| {
+                node.children()
+                    .find(|child| child.tag_name().name() == name)
+                    .and_then(|child| child.text())
+                    .map(str::trim)
+            };
+
+            child_text("loc").map(|loc| SitemapEntry {
+                loc: loc.to_string(),
+                lastmod: child_text("lastmod").and_then(parse_lastmod),
+            })
+        })
+        .collect();
+
+    if entry_tag == "sitemap" {
+        Ok(Sitemap::Index(entries))
+    } else {
+        Ok(Sitemap::UrlSet(entries))
+    }
+}
+
+/// W3C datetime: `2021-09-01` | `2021-09-01T10:00:00+03:00`, converted to UTC.
+fn parse_lastmod(value: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.naive_utc())
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_hms(0, 0, 0))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use chrono::NaiveDate;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use crate::parse::util::sitemap::{decode_sitemap, parse_sitemap, Sitemap, SitemapEntry};
+
+    #[test]
+    fn it_parses_url_set() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+                <url><loc>https://shop.com/phone-1</loc><lastmod>2021-09-01T10:00:00+03:00</lastmod></url>
+                <url><loc> https://shop.com/phone-2 </loc><lastmod>2021-09-02</lastmod></url>
+                <url><loc>https://shop.com/phone-3</loc></url>
+                <url><lastmod>2021-09-02</lastmod></url>
+            </urlset>"#;
+
+        assert_eq!(
+            parse_sitemap(xml),
+            Ok(Sitemap::UrlSet(vec![
+                SitemapEntry {
+                    loc: "https://shop.com/phone-1".to_string(),
+                    lastmod: Some(NaiveDate::from_ymd(2021, 9, 1).and_hms(7, 0, 0)),
+                },
+                SitemapEntry {
+                    loc: "https://shop.com/phone-2".to_string(),
+                    lastmod: Some(NaiveDate::from_ymd(2021, 9, 2).and_hms(0, 0, 0)),
+                },
+                SitemapEntry {
+                    loc: "https://shop.com/phone-3".to_string(),
+                    lastmod: None,
+                },
+            ]))
+        );
+    }
+
+    #[test]
+    fn it_parses_sitemap_index() {
+        let xml = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+                <sitemap><loc>https://shop.com/sitemap-products.xml.gz</loc></sitemap>
+            </sitemapindex>"#;
+
+        assert_eq!(
+            parse_sitemap(xml),
+            Ok(Sitemap::Index(vec![SitemapEntry {
+                loc: "https://shop.com/sitemap-products.xml.gz".to_string(),
+                lastmod: None,
+            }]))
+        );
+    }
+
+    #[test]
+    fn it_fails_on_unknown_documents() {
+        assert!(parse_sitemap("<html></html>").is_err());
+        assert!(parse_sitemap("not xml").is_err());
+    }
+
+    #[test]
+    fn it_decodes_gzipped_sitemap() {
+        let xml = "<urlset></urlset>";
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(xml.as_bytes()).unwrap();
+
+        assert_eq!(decode_sitemap(&encoder.finish().unwrap()), Ok(xml.to_string()));
+        assert_eq!(decode_sitemap(xml.as_bytes()), Ok(xml.to_string()));
+    }
+}
@@ -0,0 +1,172 @@
+use std::ops::Range;
+
+use regex::{Captures, Regex};
+
+use crate::dto::parsed_product::ParsedVariant;
+
+/// Beginnings of the inflected russian and ukrainian color names
+static COLOR_STEMS: &str = "черн чорн белый белая белое бело біл серый серая серое серо сір серебр срібл \
+    синий синяя синее сине синій синя блакит голуб зелен красн червон розов рожев фиолет фіолет сирен бузк \
+    золот желт жовт оранж помаран бирюз бірюз мятн м'ятн лаванд графит графіт бронз оникс онікс бежев коричн \
+    бордов корал титан полуноч опівніч";
+
+/// English color names, compared as whole words
+static COLOR_WORDS: &str = "black white gray grey silver blue green red pink purple violet gold \
+    yellow orange mint lavender graphite bronze onyx titanium midnight ivory beige brown cream coral";
+
+lazy_static! {
+    static ref COLOR_RE: Regex = Regex::new(r"\(([^()]+)\)\s*$").unwrap();
+    static ref EMPTY_PARENTHESES_RE: Regex = Regex::new(r"\(\s*\)").unwrap();
+    static ref RAM_AND_MEMORY_RE: Regex =
+        Regex::new(r"(?i)\b(\d+)\s*[/+]\s*(\d+)\s*(GB|ГБ|TB|ТБ)").unwrap();
+    static ref MEMORY_RE: Regex = Regex::new(r"(?i)\b(\d+)\s*(GB|ГБ|TB|ТБ)").unwrap();
+}
+
+/// Splits variant attributes off the listed title, so every variant is saved under the same product.
+/// `Redmi Note 10 Pro 8/128GB (Серый)` -> `Redmi Note 10 Pro` with 8GB RAM, 128GB memory and `Серый` color
+pub fn split_variant(listed_title: &str) -> (String, ParsedVariant) {
+    let mut title = listed_title.to_string();
+    let mut variant = ParsedVariant::default();
+
+    if let Some(color) = COLOR_RE.captures(listed_title).filter(|c| is_color(&c[1])) {
+        variant.color = Some(color[1].trim().to_string());
+        title.truncate(color.get(0).unwrap().start());
+    }
+
+    if let Some(memory) = take_memory(&title, &mut variant) {
+        title.replace_range(memory, "");
+    }
+
+    // `Redmi 9 (4/64GB)` leaves empty parentheses behind the memory
+    let title = EMPTY_PARENTHESES_RE.replace_all(&title, "");
+    let title = title.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    (title, variant)
+}
+
+/// Parentheses at the end of the title are a color only if they name one: `(Серый оникс)`, but not `(2021)`
+fn is_color(text: &str) -> bool {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphabetic() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .any(|word| {
+            COLOR_WORDS.split_whitespace().any(|color| color == word)
+                || COLOR_STEMS
+                    .split_whitespace()
+                    .any(|stem| word.starts_with(stem))
+        })
+}
+
+fn take_memory(title: &str, variant: &mut ParsedVariant) -> Option<Range<usize>> {
+    if let Some(captures) = RAM_AND_MEMORY_RE.captures(title) {
+        variant.ram_gb = captures[1].parse().ok();
+        variant.built_in_memory_gb = to_gb(&captures, 2);
+
+        return captures.get(0).map(|m| m.range());
+    }
+
+    MEMORY_RE.captures(title).and_then(|captures| {
+        variant.built_in_memory_gb = to_gb(&captures, 1);
+
+        captures.get(0).map(|m| m.range())
+    })
+}
+
+/// Unit is always the next group after the amount
+fn to_gb(captures: &Captures, amount_group: usize) -> Option<i32> {
+    let is_tb = matches!(&captures[amount_group + 1].to_uppercase()[..], "TB" | "ТБ");
+
+    captures[amount_group]
+        .parse::<i32>()
+        .ok()
+        .map(|amount| if is_tb { amount * 1000 } else { amount })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::parsed_product::ParsedVariant;
+    use crate::parse::util::variant::split_variant;
+
+    #[test]
+    fn it_splits_memory_and_color() {
+        assert_eq!(
+            split_variant("Redmi Note 10 Pro 8/128GB (Серый)"),
+            (
+                "Redmi Note 10 Pro".to_string(),
+                ParsedVariant {
+                    color: Some("Серый".to_string()),
+                    built_in_memory_gb: Some(128),
+                    ram_gb: Some(8),
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn it_splits_memory_without_ram() {
+        assert_eq!(
+            split_variant("Galaxy S21 Ultra 1 ТБ"),
+            (
+                "Galaxy S21 Ultra".to_string(),
+                ParsedVariant {
+                    color: None,
+                    built_in_memory_gb: Some(1000),
+                    ram_gb: None,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn it_splits_english_color() {
+        assert_eq!(
+            split_variant("Galaxy Watch3 45mm (Mystic Black)").1.color,
+            Some("Mystic Black".to_string())
+        );
+    }
+
+    #[test]
+    fn it_keeps_parentheses_which_are_not_color() {
+        assert_eq!(
+            split_variant("Redmi Note 10 Pro (2021)"),
+            (
+                "Redmi Note 10 Pro (2021)".to_string(),
+                ParsedVariant::default()
+            )
+        );
+        assert_eq!(
+            split_variant("Redmi Note 10 Pro 6/64GB (Global Version)"),
+            (
+                "Redmi Note 10 Pro (Global Version)".to_string(),
+                ParsedVariant {
+                    color: None,
+                    built_in_memory_gb: Some(64),
+                    ram_gb: Some(6),
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn it_splits_memory_in_parentheses() {
+        assert_eq!(
+            split_variant("Redmi Note 10 Pro (8/128GB)"),
+            (
+                "Redmi Note 10 Pro".to_string(),
+                ParsedVariant {
+                    color: None,
+                    built_in_memory_gb: Some(128),
+                    ram_gb: Some(8),
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn it_keeps_title_without_variant() {
+        assert_eq!(
+            split_variant("Galaxy Watch3 45mm"),
+            ("Galaxy Watch3 45mm".to_string(), ParsedVariant::default())
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use lib::error_reporting;
+use lib::db::entity::crawl_run::CrawlRunKind;
 use lib::error_reporting::ReportingContext;
 
 use crate::parse::parse_category;
 use crate::queue::layer::consume::consume;
 use crate::queue::producer::parse_category::ParseCategoryMessage;
+use crate::service::crawl_run::track_run;
 use crate::{ConsumerName, SETTINGS};
 
 pub async fn start() -> core::result::Result<(), ()> {
@@ -15,24 +17,37 @@ pub async fn start() -> core::result::Result<(), ()> {
 }
 
 async fn execute(message: ParseCategoryMessage) -> Result<(), ()> {
-    let parse_result = parse_category(message.source, message.category).await;
+    let parse_result = track_run(
+        CrawlRunKind::Category,
+        message.source,
+        Some(message.category),
+        parse_category(message.source, message.category),
+    )
+    .await;
 
-    if parse_result.is_err() {
-        let message = format!(
-            "[{source}] Parsing failed! ({category}) {error:?}",
-            error = parse_result.err(),
-            source = message.source,
-            category = message.category
-        );
-        error_reporting::error(
-            message.as_str(),
-            &ReportingContext {
-                executor: &ConsumerName::ParseCategory,
-                action: "execute",
-            },
-        );
-        Err(())
-    } else {
-        Ok(())
+    match parse_result {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            let message = format!(
+                "[{source}] Parsing failed! ({category}) {error:?}",
+                error = e,
+                source = message.source,
+                category = message.category
+            );
+            error_reporting::error(
+                message.as_str(),
+                &ReportingContext {
+                    executor: &ConsumerName::ParseCategory,
+                    action: "execute",
+                },
+            );
+
+            // E.g. sitemap has been removed, retry won't help until the crawler is fixed
+            if e.is_permanent() {
+                Ok(())
+            } else {
+                Err(())
+            }
+        }
     }
 }
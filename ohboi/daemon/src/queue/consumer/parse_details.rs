@@ -1,12 +1,16 @@
 use serde::{Deserialize, Serialize};
 
+use crate::db::entity::category::CategorySlug;
 use crate::db::entity::source::SourceName;
 use crate::db::repository::product::update_details;
 use crate::parse::crawler::upload_extracted_images;
 use crate::parse::crawler::get_crawler;
-use crate::parse::parse_details;
+use crate::parse::{parse_details, parse_discovered_product};
 use crate::queue::layer::consume::consume;
+use crate::service::crawl_run::track_run;
+use crate::service::request::{save_cache_validators, Fetched};
 use crate::{SETTINGS, ConsumerName};
+use lib::db::entity::crawl_run::CrawlRunKind;
 use lib::error_reporting;
 use lib::error_reporting::ReportingContext;
 
@@ -14,7 +18,10 @@ use lib::error_reporting::ReportingContext;
 pub struct ParseDetailsMessage {
     pub external_id: String,
     pub source: SourceName,
-    pub product_id: i32,
+    /// Products discovered in the sitemap are created only after their details page is parsed
+    pub product_id: Option<i32>,
+    #[serde(default)]
+    pub category: Option<CategorySlug>,
 }
 
 pub async fn start() -> core::result::Result<(), ()> {
@@ -26,11 +33,62 @@ pub async fn start() -> core::result::Result<(), ()> {
 }
 
 async fn execute(message: ParseDetailsMessage) -> Result<(), ()> {
+    let source = message.source;
+    let category = message.category;
+
+    track_run(CrawlRunKind::Details, source, category, process(message)).await
+}
+
+async fn process(message: ParseDetailsMessage) -> Result<(), ()> {
     let crawler = get_crawler(&message.source);
-    let details = parse_details(&message.external_id, crawler).await;
+    let details = match (message.product_id, message.category) {
+        (Some(product_id), _) => parse_details(&message.external_id, crawler)
+            .await
+            .map(|page| page.map(|details| details.map(|details| (product_id, details)))),
+        (None, Some(category)) => {
+            parse_discovered_product(&message.external_id, crawler, category).await
+        }
+        (None, None) => {
+            error_reporting::error(
+                format!(
+                    "[parse_details] Neither product nor category is known [{source}] for: {id}",
+                    source = crawler.get_source().to_string(),
+                    id = &message.external_id
+                )
+                .as_str(),
+                &ReportingContext {
+                    executor: &ConsumerName::ParseDetails,
+                    action: "execute"
+                }
+            );
+
+            // Retry won't fix the message
+            return Ok(());
+        }
+    };
 
     match details {
-        None => {
+        // Product page is gone, there is nothing to retry
+        Err(e) if e.is_permanent() => {
+            error_reporting::warning(
+                format!(
+                    "[parse_details] Details page is unavailable [{source}] for: {id} {error}",
+                    source = crawler.get_source().to_string(),
+                    id = &message.external_id,
+                    error = e
+                )
+                .as_str(),
+                &ReportingContext {
+                    executor: &ConsumerName::ParseDetails,
+                    action: "execute"
+                }
+            );
+
+            Ok(())
+        }
+        Err(_) => Err(()),
+        Ok(Fetched::NotModified) => Ok(()),
+        Ok(Fetched::Modified { body: None, .. }) => {
             error_reporting::error(
                 format!(
                     "[parse_details] No additional info found [{source}] for: {id}",
@@ -46,9 +104,13 @@ async fn execute(message: ParseDetailsMessage) -> Result<(), ()> {
 
             Err(())
         }
-        Some(mut details) => {
+        Ok(Fetched::Modified {
+            body: Some((product_id, mut details)),
+            validators,
+        }) => {
             let uploaded_urls = upload_extracted_images(
                 crawler.get_source(),
+                product_id,
                 details.image_urls,
                 &message.external_id,
                 &crawler.get_site_base(),
@@ -56,7 +118,8 @@ async fn execute(message: ParseDetailsMessage) -> Result<(), ()> {
             .await;
             details.image_urls = uploaded_urls;
 
-            update_details(message.product_id, &details);
+            update_details(product_id, crawler.get_source(), &details);
+            save_cache_validators(&validators);
 
             Ok(())
         }
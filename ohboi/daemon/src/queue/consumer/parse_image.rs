@@ -11,7 +11,7 @@ use crate::db::entity::source::SourceName;
 use crate::db::repository::product::add_image_to_product_details;
 use crate::db::repository::source_product::get_by_source_and_external_id;
 use crate::queue::layer::consume::consume;
-use crate::service::cloud::upload_image_to_cloud;
+use crate::service::cloud::{upload_image_to_cloud, ImageUpload};
 use crate::{ConsumerName, SETTINGS};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -31,6 +31,11 @@ pub async fn start() -> core::result::Result<(), ()> {
 }
 
 async fn execute(message: UploadImageMessage) -> Result<(), ()> {
+    let source_product = get_by_source_and_external_id(message.source, &message.external_id)
+        .expect(&format!(
+            "SourceProduct doesn't exist. source: {} external_id: {}",
+            message.source, &message.external_id
+        ));
     add_consumer_breadcrumb(
         "downloading image",
         btreemap! {
@@ -38,26 +43,31 @@ async fn execute(message: UploadImageMessage) -> Result<(), ()> {
             },
         "upload_image"
     );
-    let result = upload_image_to_cloud(message.file_path.clone(), message.image_url).await;
+    let result = upload_image_to_cloud(
+        source_product.product_id,
+        message.source,
+        message.file_path,
+        message.image_url,
+    )
+    .await;
 
-    if result {
-        let source_product = get_by_source_and_external_id(message.source, &message.external_id)
-            .expect(&format!(
-                "SourceProduct doesn't exist. source: {} external_id: {}",
-                message.source, &message.external_id
-            ));
-        add_consumer_breadcrumb(
-            "updating product",
-            btreemap! {
-                "id" => source_product.product_id.to_string(),
-            },
-            "update_product"
-        );
-        add_image_to_product_details(source_product.product_id, &message.file_path);
+    match result {
+        Ok(ImageUpload::Stored(uploaded_path)) => {
+            add_consumer_breadcrumb(
+                "updating product",
+                btreemap! {
+                    "id" => source_product.product_id.to_string(),
+                },
+                "update_product"
+            );
+            add_image_to_product_details(source_product.product_id, &uploaded_path);
 
-        Ok(())
-    } else {
-        Err(())
+            Ok(())
+        }
+        Ok(ImageUpload::Duplicate) => Ok(()),
+        // Not an image or not found, so the message is not postponed again
+        Err(e) if e.is_permanent() => Ok(()),
+        Err(_) => Err(()),
     }
 }
 
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use lib::db::entity::crawl_run::CrawlRunKind;
 use lib::error_reporting;
 use lib::error_reporting::ReportingContext;
 
@@ -7,6 +8,7 @@ use crate::db::entity::category::CategorySlug;
 use crate::db::entity::source::SourceName;
 use crate::parse::parse_category_page;
 use crate::queue::layer::consume::consume;
+use crate::service::crawl_run::track_run;
 use crate::{ConsumerName, SETTINGS};
 
 #[derive(Serialize, Deserialize)]
@@ -25,24 +27,36 @@ pub async fn start() -> core::result::Result<(), ()> {
 }
 
 async fn execute(message: ParsePageMessage) -> Result<(), ()> {
-    let parse_result = parse_category_page(&message.url, message.source, message.category).await;
-
-    if parse_result.is_err() {
-        let message = format!(
-            "Page parsing failed! [{source}]({category}){error:?}",
-            source = message.source,
-            category = message.category,
-            error = parse_result.err()
-        );
-        error_reporting::error(
-            message.as_str(),
-            &ReportingContext {
+    let parse_result = track_run(
+        CrawlRunKind::Page,
+        message.source,
+        Some(message.category),
+        parse_category_page(&message.url, message.source, message.category),
+    )
+    .await;
+
+    match parse_result {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            let message = format!(
+                "Page parsing failed! [{source}]({category}){error:?}",
+                source = message.source,
+                category = message.category,
+                error = e
+            );
+            let context = ReportingContext {
                 executor: &ConsumerName::ParsePage,
                 action: "execute",
-            },
-        );
-        Err(())
-    } else {
-        Ok(())
+            };
+
+            // The page won't appear on retry
+            if e.is_permanent() {
+                error_reporting::warning(message.as_str(), &context);
+                Ok(())
+            } else {
+                error_reporting::error(message.as_str(), &context);
+                Err(())
+            }
+        }
     }
 }
@@ -3,9 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::db::entity::category::CategorySlug;
 use crate::db::entity::source::SourceName;
-use crate::parse::crawler::Crawler;
-use crate::parse::crawler::MiShopComCrawler;
-use crate::parse::crawler::SamsungShopComUaCrawler;
+use crate::parse::crawler::{get_all_crawlers, Crawler};
 use crate::queue::layer::produce::produce;
 use crate::SETTINGS;
 
@@ -16,17 +14,14 @@ pub struct ParseCategoryMessage {
 }
 
 pub async fn start() -> Result<()> {
-    // TODO get crawler based on enum
-    produce_message_for_crawler(MiShopComCrawler {}).await?;
-    produce_message_for_crawler(SamsungShopComUaCrawler {}).await?;
+    for crawler in get_all_crawlers() {
+        produce_message_for_crawler(crawler).await?;
+    }
 
     Ok(())
 }
 
-async fn produce_message_for_crawler<T>(crawler: T) -> Result<()>
-where
-    T: Crawler,
-{
+async fn produce_message_for_crawler(crawler: &dyn Crawler) -> Result<()> {
     // TODO check if crawler is enabled
     for category in crawler.get_categories() {
         let payload = ParseCategoryMessage {
@@ -8,6 +8,7 @@ use crate::queue::consumer::parse_details::ParseDetailsMessage;
 use crate::queue::consumer::parse_image::UploadImageMessage;
 use crate::queue::consumer::parse_page::ParsePageMessage;
 use crate::queue::layer::produce::produce;
+use crate::service::crawl_run::record;
 use crate::{SETTINGS, ConsumerName};
 use std::collections::BTreeMap;
 
@@ -32,6 +33,8 @@ pub async fn postpone_page_parsing(
         ConsumerName::ParsePage,
     );
 
+    record(|run| run.postponed += 1);
+
     produce(&SETTINGS.queue_broker.queues.parse_page, &message).await
 }
 pub async fn postpone_details_parsing(
@@ -42,7 +45,8 @@ pub async fn postpone_details_parsing(
     let message = ParseDetailsMessage {
         external_id,
         source,
-        product_id,
+        product_id: Some(product_id),
+        category: None,
     };
     let breadcrumb_data = btreemap! {
         "source" => message.source.to_string(),
@@ -53,6 +57,36 @@ pub async fn postpone_details_parsing(
         breadcrumb_data,
         ConsumerName::ParseDetails,
     );
+    record(|run| run.postponed += 1);
+
+    produce(&SETTINGS.queue_broker.queues.parse_details, &message).await
+}
+
+/// Postpones parsing of a product page which is known only from the sitemap, so the product itself is extracted
+/// from the page too.
+pub async fn postpone_discovered_details_parsing(
+    external_id: String,
+    source: SourceName,
+    category: CategorySlug,
+) -> Result<()> {
+    let message = ParseDetailsMessage {
+        external_id,
+        source,
+        product_id: None,
+        category: Some(category),
+    };
+    let breadcrumb_data = btreemap! {
+        "category" => category.to_string(),
+        "source" => message.source.to_string(),
+        "external_id" => message.external_id.to_string()
+    };
+    add_consumer_breadcrumb(
+        "postponing discovered details parsing",
+        breadcrumb_data,
+        ConsumerName::ParseDetails,
+    );
+    record(|run| run.postponed += 1);
+
     produce(&SETTINGS.queue_broker.queues.parse_details, &message).await
 }
 
@@ -0,0 +1,124 @@
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use tokio::fs;
+
+use crate::service::blob_store::{BlobStore, BlobStoreError};
+
+/// Objects are files under the root directory
+pub struct FsBlobStore {
+    root: PathBuf,
+}
+
+impl FsBlobStore {
+    pub fn new(root: &str) -> Self {
+        FsBlobStore {
+            root: PathBuf::from(root),
+        }
+    }
+
+    /// Keys come from the crawled pages, they must not leave the root
+    fn get_path(&self, key: &str) -> Result<PathBuf, BlobStoreError> {
+        let is_relative = Path::new(key)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+        if is_relative {
+            Ok(self.root.join(key))
+        } else {
+            Err(BlobStoreError::InvalidKey(key.to_string()))
+        }
+    }
+}
+
+impl BlobStore for FsBlobStore {
+    fn put<'a>(&'a self, key: &'a str, data: Vec<u8>) -> BoxFuture<'a, Result<(), BlobStoreError>> {
+        async move {
+            let path = self.get_path(key)?;
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)
+                    .await
+                    .map_err(|e| BlobStoreError::Io(e.to_string()))?;
+            }
+
+            fs::write(path, data)
+                .await
+                .map_err(|e| BlobStoreError::Io(e.to_string()))
+        }
+        .boxed()
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Vec<u8>, BlobStoreError>> {
+        async move {
+            fs::read(self.get_path(key)?)
+                .await
+                .map_err(|e| match e.kind() {
+                    ErrorKind::NotFound => BlobStoreError::NotFound(key.to_string()),
+                    _ => BlobStoreError::Io(e.to_string()),
+                })
+        }
+        .boxed()
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), BlobStoreError>> {
+        async move {
+            match fs::remove_file(self.get_path(key)?).await {
+                Err(e) if e.kind() != ErrorKind::NotFound => Err(BlobStoreError::Io(e.to_string())),
+                _ => Ok(()),
+            }
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::blob_store::{BlobStore, BlobStoreError, FsBlobStore};
+
+    #[tokio::test]
+    async fn it_reads_written_objects() {
+        let root = std::env::temp_dir().join("ohboi_fs_blob_store_test");
+        let store = FsBlobStore::new(root.to_str().unwrap());
+
+        store
+            .put("mi_shop_com/details/1.html.gz", vec![1, 2, 3])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.get("mi_shop_com/details/1.html.gz").await.unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(matches!(
+            store.get("mi_shop_com/details/2.html.gz").await,
+            Err(BlobStoreError::NotFound(_))
+        ));
+
+        store.delete("mi_shop_com/details/1.html.gz").await.unwrap();
+        store.delete("mi_shop_com/details/2.html.gz").await.unwrap();
+        assert!(matches!(
+            store.get("mi_shop_com/details/1.html.gz").await,
+            Err(BlobStoreError::NotFound(_))
+        ));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_keeps_objects_under_the_root() {
+        let root = std::env::temp_dir().join("ohboi_fs_blob_store_root_test");
+        let store = FsBlobStore::new(root.to_str().unwrap());
+
+        assert!(matches!(
+            store.put("product_images/../../1.jpg", vec![1]).await,
+            Err(BlobStoreError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            store.put("/tmp/1.jpg", vec![1]).await,
+            Err(BlobStoreError::InvalidKey(_))
+        ));
+        assert!(!root.exists());
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+use crate::service::blob_store::{BlobStore, BlobStoreError};
+
+/// Objects are lost with the process, for tests and dry runs
+#[derive(Default)]
+pub struct MemoryBlobStore {
+    objects: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl BlobStore for MemoryBlobStore {
+    fn put<'a>(&'a self, key: &'a str, data: Vec<u8>) -> BoxFuture<'a, Result<(), BlobStoreError>> {
+        self.objects.lock().unwrap().insert(key.to_string(), data);
+
+        futures::future::ok(()).boxed()
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Vec<u8>, BlobStoreError>> {
+        let object = self
+            .objects
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| BlobStoreError::NotFound(key.to_string()));
+
+        futures::future::ready(object).boxed()
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), BlobStoreError>> {
+        self.objects.lock().unwrap().remove(key);
+
+        futures::future::ok(()).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::blob_store::{BlobStore, BlobStoreError, MemoryBlobStore};
+
+    #[tokio::test]
+    async fn it_keeps_objects_in_memory() {
+        let store = MemoryBlobStore::default();
+
+        store
+            .put("product_images/mi_shop_com/1.jpg", vec![1, 2, 3])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.get("product_images/mi_shop_com/1.jpg").await.unwrap(),
+            vec![1, 2, 3]
+        );
+        store
+            .delete("product_images/mi_shop_com/1.jpg")
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.get("product_images/mi_shop_com/1.jpg").await,
+            Err(BlobStoreError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+
+use futures::future::BoxFuture;
+
+pub use fs::FsBlobStore;
+pub use memory::MemoryBlobStore;
+pub use s3::S3BlobStore;
+
+use crate::settings::{ArchiveStore, ImageStoreKind};
+use crate::SETTINGS;
+
+mod fs;
+mod memory;
+mod s3;
+
+/// Storage of binary objects by key, keys are `/` separated paths
+pub trait BlobStore: Send + Sync {
+    fn put<'a>(&'a self, key: &'a str, data: Vec<u8>) -> BoxFuture<'a, Result<(), BlobStoreError>>;
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Vec<u8>, BlobStoreError>>;
+
+    /// Deleting of a missing object is not an error
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), BlobStoreError>>;
+}
+
+lazy_static! {
+    static ref ARCHIVE_STORE: Option<Box<dyn BlobStore>> = match SETTINGS.archive.store {
+        ArchiveStore::None => None,
+        ArchiveStore::Fs => Some(Box::new(FsBlobStore::new(&SETTINGS.archive.dir))),
+        ArchiveStore::S3 => Some(Box::new(S3BlobStore::new(
+            &SETTINGS.s3.bucket,
+            &SETTINGS.archive.s3_prefix,
+        ))),
+    };
+    static ref IMAGE_STORE: Box<dyn BlobStore> = match SETTINGS.images.store {
+        ImageStoreKind::S3 => Box::new(S3BlobStore::new(&SETTINGS.s3.bucket, "")),
+        ImageStoreKind::Fs => Box::new(FsBlobStore::new(&SETTINGS.images.dir)),
+        ImageStoreKind::Memory => Box::new(MemoryBlobStore::default()),
+    };
+}
+
+/// Store of the raw pages, `None` if archiving is turned off
+pub fn get_archive_store() -> Option<&'static dyn BlobStore> {
+    ARCHIVE_STORE.as_deref()
+}
+
+/// Store of the product images, keys are the public paths of the images
+pub fn get_image_store() -> &'static dyn BlobStore {
+    IMAGE_STORE.as_ref()
+}
+
+#[derive(Debug)]
+pub enum BlobStoreError {
+    NotFound(String),
+    InvalidKey(String),
+    Io(String),
+}
+
+impl fmt::Display for BlobStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlobStoreError::NotFound(key) => write!(f, "{} not found", key),
+            BlobStoreError::InvalidKey(key) => write!(f, "Invalid key {}", key),
+            BlobStoreError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
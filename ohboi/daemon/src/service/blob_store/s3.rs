@@ -0,0 +1,98 @@
+use futures::future::BoxFuture;
+use futures::{FutureExt, TryStreamExt};
+use rusoto_core::RusotoError;
+use rusoto_s3::{
+    DeleteObjectRequest, GetObjectError, GetObjectRequest, PutObjectRequest, S3Client,
+    StreamingBody, S3,
+};
+
+use crate::service::blob_store::{BlobStore, BlobStoreError};
+use crate::service::cloud::new_s3_client;
+
+/// Objects are stored in the bucket under the prefix
+pub struct S3BlobStore {
+    client: S3Client,
+    bucket: String,
+    prefix: String,
+}
+
+impl S3BlobStore {
+    pub fn new(bucket: &str, prefix: &str) -> Self {
+        S3BlobStore {
+            client: new_s3_client(),
+            bucket: bucket.to_string(),
+            prefix: prefix.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Empty prefix keeps the keys as they are, e.g. for the public images
+    fn object_key(&self, key: &str) -> String {
+        if self.prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}/{}", self.prefix, key)
+        }
+    }
+}
+
+impl BlobStore for S3BlobStore {
+    fn put<'a>(&'a self, key: &'a str, data: Vec<u8>) -> BoxFuture<'a, Result<(), BlobStoreError>> {
+        async move {
+            let request = PutObjectRequest {
+                bucket: self.bucket.clone(),
+                key: self.object_key(key),
+                body: Some(StreamingBody::from(data)),
+                ..PutObjectRequest::default()
+            };
+
+            self.client
+                .put_object(request)
+                .await
+                .map(|_| ())
+                .map_err(|e| BlobStoreError::Io(e.to_string()))
+        }
+        .boxed()
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Vec<u8>, BlobStoreError>> {
+        async move {
+            let request = GetObjectRequest {
+                bucket: self.bucket.clone(),
+                key: self.object_key(key),
+                ..GetObjectRequest::default()
+            };
+
+            let output = self.client.get_object(request).await.map_err(|e| match e {
+                RusotoError::Service(GetObjectError::NoSuchKey(_)) => {
+                    BlobStoreError::NotFound(key.to_string())
+                }
+                _ => BlobStoreError::Io(e.to_string()),
+            })?;
+            let body = output
+                .body
+                .ok_or_else(|| BlobStoreError::NotFound(key.to_string()))?;
+
+            body.map_ok(|chunk| chunk.to_vec())
+                .try_concat()
+                .await
+                .map_err(|e| BlobStoreError::Io(e.to_string()))
+        }
+        .boxed()
+    }
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), BlobStoreError>> {
+        async move {
+            let request = DeleteObjectRequest {
+                bucket: self.bucket.clone(),
+                key: self.object_key(key),
+                ..DeleteObjectRequest::default()
+            };
+
+            self.client
+                .delete_object(request)
+                .await
+                .map(|_| ())
+                .map_err(|e| BlobStoreError::Io(e.to_string()))
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,21 @@
+use rusoto_core::Region;
+use rusoto_s3::S3Client;
+
+use crate::SETTINGS;
+
+/// Client of AWS or of the S3 compatible storage from `S3_ENDPOINT`
+pub fn new_s3_client() -> S3Client {
+    let region = match &SETTINGS.s3.endpoint {
+        Some(endpoint) => Region::Custom {
+            name: SETTINGS.s3.region.clone(),
+            endpoint: endpoint.clone(),
+        },
+        None => SETTINGS
+            .s3
+            .region
+            .parse()
+            .expect("S3_REGION is not a valid region"),
+    };
+
+    S3Client::new(region)
+}
@@ -1,55 +1,400 @@
-use rusoto_core::Region;
-use rusoto_s3::{PutObjectRequest, S3Client, StreamingBody, S3};
+use std::convert::TryFrom;
+use std::fmt;
+use std::time::Duration;
 
-use lib::error_reporting;
+use tokio::task::spawn_blocking;
 
-use crate::service::request::get_bytes;
-use crate::SETTINGS;
+use lib::error_reporting;
 use lib::error_reporting::ReportingContext;
+
+use crate::db::entity::product_image::{NewProductImage, NewProductImageVariant};
+use crate::db::entity::source::SourceName;
+use crate::db::repository::product_image::{
+    create_unless_duplicate, get_hashes, get_unhashed_images, to_db_hash,
+};
+use crate::db::repository::source::get_source;
+use crate::service::blob_store::{BlobStore, BlobStoreError};
+use crate::service::cloud::metrics::{record_transfer, Transfer};
+use crate::service::cloud::processing::{
+    inspect_image, is_same_photo, process_image, ImageEncoding, ImageSize, InvalidImage,
+};
+use crate::service::cloud::transfer::{download_file, DownloadedFile, TransferError};
+use crate::service::request::RequestError;
 use crate::service::Executor;
+use crate::SETTINGS;
 
-pub async fn upload_image_to_s3(file_path: String, image_url: String) -> bool {
-    let data = get_bytes(&image_url).await;
-    let context = ReportingContext {
-        executor: &Executor::Cloud,
-        action: "upload_image"
-    };
+/// Size and format of the variant which is the image stored before the photos were processed
+const STORED_IMAGE_VARIANT: &str = "original";
+
+#[derive(Debug, PartialEq)]
+pub enum ImageUpload {
+    /// Path of the large jpeg, the one which is added to the product
+    Stored(String),
+    /// Product already has the same photo, e.g. from another shop
+    Duplicate,
+}
 
-    if data.is_err() {
+#[derive(Debug)]
+pub enum ImageUploadError {
+    Download(RequestError),
+    Invalid(InvalidImage),
+    Store(BlobStoreError),
+}
+
+impl ImageUploadError {
+    /// Upload which can't succeed later, so there is no sense to postpone it
+    pub fn is_permanent(&self) -> bool {
+        match self {
+            ImageUploadError::Download(e) => e.is_permanent(),
+            ImageUploadError::Invalid(_) => true,
+            ImageUploadError::Store(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for ImageUploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageUploadError::Download(e) => write!(f, "Cannot get image: {:?}", e),
+            ImageUploadError::Invalid(e) => write!(f, "Image is not valid: {}", e),
+            ImageUploadError::Store(e) => write!(f, "Image can't be uploaded to cloud! {}", e),
+        }
+    }
+}
+
+/// Downloads the image, stores its sizes and records them for the product
+pub async fn upload_image(
+    store: &dyn BlobStore,
+    product_id: i32,
+    source: SourceName,
+    file_path: String,
+    image_url: String,
+) -> Result<ImageUpload, ImageUploadError> {
+    let result = store_image(store, product_id, source, &file_path, &image_url).await;
+
+    if let Err(e) = &result {
         let message = format!(
-            "[cloud::image::upload] Cannot get image: {url} {error:?}",
-            url = image_url,
-            error = data.err().unwrap()
+            "[cloud::image::upload] {error} {url}",
+            error = e,
+            url = image_url
         );
+        error_reporting::warning(
+            message.as_str(),
+            &ReportingContext {
+                executor: &Executor::Cloud,
+                action: "upload_image",
+            },
+        );
+    }
+
+    result
+}
+
+async fn store_image(
+    store: &dyn BlobStore,
+    product_id: i32,
+    source: SourceName,
+    file_path: &str,
+    image_url: &str,
+) -> Result<ImageUpload, ImageUploadError> {
+    let settings = &SETTINGS.images;
+    let download = download_file(
+        image_url,
+        settings.max_bytes,
+        Duration::from_secs(settings.download_timeout_seconds),
+    )
+    .await
+    .map_err(|e| match e {
+        TransferError::TooBig(bytes) => {
+            record_transfer(Transfer::Refused);
+            ImageUploadError::Invalid(InvalidImage::TooBig(bytes))
+        }
+        TransferError::Timeout => {
+            record_transfer(Transfer::Failed);
+            ImageUploadError::Download(RequestError::Transient(format!(
+                "Download took longer than {}s",
+                settings.download_timeout_seconds
+            )))
+        }
+        TransferError::Request(e) => {
+            record_transfer(Transfer::Failed);
+            ImageUploadError::Download(e)
+        }
+    })?;
+    let DownloadedFile { data, content_type } = download;
+    record_transfer(Transfer::Downloaded(data.len()));
 
-        error_reporting::warning(message.as_str(), &context);
+    // Decoding and encoding take a while, they would block other tasks of the executor
+    let processed = spawn_blocking(move || process_image(&data, content_type.as_deref(), settings))
+        .await
+        .map_err(|e| ImageUploadError::Invalid(InvalidImage::Broken(e.to_string())))?
+        .map_err(ImageUploadError::Invalid)?;
 
-        return false;
+    hash_stored_images(store, product_id).await;
+    let hash = processed.hash;
+    let is_duplicate = |other_hash| is_same_photo(hash, other_hash, settings.dedupe_distance);
+    if get_hashes(product_id).into_iter().any(is_duplicate) {
+        return Ok(ImageUpload::Duplicate);
     }
 
-    let client = S3Client::new(Region::EuWest2);
+    let mut variants = vec![];
+    let mut stored_paths = vec![];
+    for variant in processed.variants {
+        let variant_path = get_variant_path(file_path, variant.size, variant.encoding);
+        let bytes = variant.data.len();
+        let stored = store.put(&variant_path, variant.data).await;
+        if let Err(e) = stored {
+            record_transfer(Transfer::Failed);
+            delete_files(store, &stored_paths).await;
+            return Err(ImageUploadError::Store(e));
+        }
+        record_transfer(Transfer::Uploaded(bytes));
+        stored_paths.push(variant_path.clone());
 
-    let request: PutObjectRequest = PutObjectRequest {
-        bucket: { &SETTINGS.s3.bucket }.to_string(),
-        key: file_path,
-        // TODO stream directly from http
-        body: Some(StreamingBody::from(
-            data.expect(&format!("Failed to get bytes from {}", &image_url)),
+        variants.push(NewProductImageVariant {
+            image_id: 0,
+            size: variant.size.to_string(),
+            format: variant.encoding.to_string(),
+            file_path: variant_path,
+            width: to_i32(variant.width),
+            height: to_i32(variant.height),
+        });
+    }
+
+    // Same photo could be stored by a concurrent upload meanwhile, its files are not needed then
+    let image_id = create_unless_duplicate(
+        &NewProductImage {
+            product_id,
+            source_id: Some(get_source(source).id),
+            original_url: image_url,
+            hash: to_db_hash(hash),
+            width: to_i32(processed.width),
+            height: to_i32(processed.height),
+        },
+        variants,
+        is_duplicate,
+    );
+    if image_id.is_none() {
+        delete_files(store, &stored_paths).await;
+    }
+
+    Ok(match image_id {
+        Some(_) => ImageUpload::Stored(get_variant_path(
+            file_path,
+            ImageSize::Large,
+            ImageEncoding::Jpeg,
         )),
-        ..PutObjectRequest::default()
+        None => ImageUpload::Duplicate,
+    })
+}
+
+/// Images stored before the photos were hashed get their hash once the product gets a new image,
+/// otherwise the new photo wouldn't be compared with them
+async fn hash_stored_images(store: &dyn BlobStore, product_id: i32) {
+    for file_path in get_unhashed_images(product_id) {
+        let inspected = match store.get(&file_path).await {
+            Ok(data) => spawn_blocking(move || inspect_image(&data))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result.map_err(|e| e.to_string())),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match inspected {
+            Ok(image) => {
+                create_unless_duplicate(
+                    &NewProductImage {
+                        product_id,
+                        source_id: None,
+                        original_url: &file_path,
+                        hash: to_db_hash(image.hash),
+                        width: to_i32(image.width),
+                        height: to_i32(image.height),
+                    },
+                    vec![NewProductImageVariant {
+                        image_id: 0,
+                        size: String::from(STORED_IMAGE_VARIANT),
+                        format: String::from(STORED_IMAGE_VARIANT),
+                        file_path: file_path.clone(),
+                        width: to_i32(image.width),
+                        height: to_i32(image.height),
+                    }],
+                    |_| false,
+                );
+            }
+            Err(e) => log::warn!("Stored image {} can't be hashed: {}", file_path, e),
+        }
+    }
+}
+
+/// Files of the image which is not saved, errors are only logged as the files are just unused
+async fn delete_files(store: &dyn BlobStore, file_paths: &[String]) {
+    for file_path in file_paths {
+        if let Err(e) = store.delete(file_path).await {
+            log::warn!("Unused image {} can't be deleted: {}", file_path, e);
+        }
+    }
+}
+
+/// Every size is stored next to each other: `product_images/source/path/of/image/large.jpg`
+fn get_variant_path(file_path: &str, size: ImageSize, encoding: ImageEncoding) -> String {
+    let file_name_start = file_path.rfind('/').map_or(0, |slash| slash + 1);
+    let base = match file_path[file_name_start..].rfind('.') {
+        Some(dot) => &file_path[..file_name_start + dot],
+        None => file_path,
     };
 
-    let result = client.put_object(request).await;
-    let success = result.is_ok();
+    format!(
+        "{base}/{size}.{extension}",
+        base = base,
+        size = size,
+        extension = encoding.extension()
+    )
+}
 
-    if !success {
-        let message = format!(
-            "[cloud::image::upload] Image can't be uploaded to cloud! {url} {error:?}",
-            url = image_url,
-            error = result.err().unwrap()
+/// Dimensions are limited by `IMAGE_MAX_SIDE`
+fn to_i32(value: u32) -> i32 {
+    i32::try_from(value).unwrap_or(i32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use image::{DynamicImage, ImageOutputFormat, Rgb, RgbImage};
+
+    use lib::db;
+    use lib::diesel::prelude::*;
+    use lib::schema::product;
+
+    use crate::db::entity::category::CategorySlug;
+    use crate::db::entity::product::NewProduct;
+    use crate::db::entity::source::SourceName;
+    use crate::db::repository::category::get_category;
+    use crate::service::blob_store::{BlobStore, MemoryBlobStore};
+    use crate::service::cloud::layer::{get_variant_path, upload_image, ImageUpload};
+    use crate::service::cloud::processing::{ImageEncoding, ImageSize};
+
+    fn start_stand_in_shop(image: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buffer = [0; 4096];
+                let _ = stream.read(&mut buffer);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    image.len()
+                );
+                let _ = stream.write_all(&image);
+            }
+        });
+
+        format!("http://{}", address)
+    }
+
+    fn get_png() -> Vec<u8> {
+        let photo = DynamicImage::ImageRgb8(RgbImage::from_fn(400, 300, |x, y| {
+            Rgb([
+                u8::try_from(x % 256).unwrap(),
+                u8::try_from(y % 256).unwrap(),
+                128,
+            ])
+        }));
+        let mut data = vec![];
+        photo.write_to(&mut data, ImageOutputFormat::Png).unwrap();
+
+        data
+    }
+
+    fn create_product(title: &str) -> i32 {
+        let now = Utc::now().naive_utc();
+
+        diesel::insert_into(product::table)
+            .values(&NewProduct {
+                category: get_category(CategorySlug::Smartphone).id,
+                title,
+                lowest_price: BigDecimal::from(1),
+                highest_price: BigDecimal::from(1),
+                enabled: false,
+                created_at: &now,
+                updated_at: &now,
+            })
+            .returning(product::id)
+            .get_result(&db::establish_connection())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn it_stores_every_variant_once() {
+        let shop = start_stand_in_shop(get_png());
+        let product_id = create_product("Upload image test phone");
+        let store = MemoryBlobStore::default();
+        let file_path = "product_images/mi_shop_com/upload/phone.png";
+
+        let first = upload_image(
+            &store,
+            product_id,
+            SourceName::MiShopCom,
+            file_path.to_string(),
+            format!("{}/upload/phone.png", shop),
+        )
+        .await;
+        let second = upload_image(
+            &store,
+            product_id,
+            SourceName::MiShopCom,
+            file_path.to_string(),
+            format!("{}/upload/phone.png", shop),
+        )
+        .await;
+
+        diesel::delete(product::table.find(product_id))
+            .execute(&db::establish_connection())
+            .unwrap();
+        assert_eq!(
+            first.unwrap(),
+            ImageUpload::Stored(String::from(
+                "product_images/mi_shop_com/upload/phone/large.jpg"
+            ))
         );
-        error_reporting::warning(message.as_str(), &context);
+        assert_eq!(second.unwrap(), ImageUpload::Duplicate);
+        for size in &["thumbnail", "medium", "large"] {
+            for extension in &["jpg", "webp"] {
+                let key = format!(
+                    "product_images/mi_shop_com/upload/phone/{}.{}",
+                    size, extension
+                );
+                assert!(!store.get(&key).await.unwrap().is_empty());
+            }
+        }
     }
 
-    success
+    #[test]
+    fn it_stores_sizes_next_to_each_other() {
+        assert_eq!(
+            get_variant_path(
+                "product_images/mi_shop_com/upload/iblock/phone.png",
+                ImageSize::Thumbnail,
+                ImageEncoding::Webp
+            ),
+            "product_images/mi_shop_com/upload/iblock/phone/thumbnail.webp"
+        );
+        assert_eq!(
+            get_variant_path(
+                "product_images/mi_shop_com/v1.2/phone",
+                ImageSize::Large,
+                ImageEncoding::Jpeg
+            ),
+            "product_images/mi_shop_com/v1.2/phone/large.jpg"
+        );
+    }
 }
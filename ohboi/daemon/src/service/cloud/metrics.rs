@@ -0,0 +1,131 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::SETTINGS;
+
+lazy_static! {
+    static ref TRANSFER_STATS: Mutex<TransferStats> = Mutex::new(TransferStats::new(
+        Duration::from_secs(SETTINGS.images.stats_interval_seconds),
+        Instant::now()
+    ));
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Transfer {
+    /// Bytes of the downloaded image
+    Downloaded(usize),
+    /// Bytes of the stored image variant
+    Uploaded(usize),
+    /// Download was stopped because of its size
+    Refused,
+    /// Download or upload which failed or took too long
+    Failed,
+}
+
+/// Counts the transfer and logs the stats once per `IMAGE_STATS_INTERVAL_SECONDS`
+pub fn record_transfer(transfer: Transfer) {
+    let now = Instant::now();
+    let mut stats = TRANSFER_STATS.lock().unwrap();
+
+    stats.record(transfer);
+
+    if let Some(stats) = stats.take_stats(now) {
+        log::info!("{}", stats);
+    }
+}
+
+struct TransferStats {
+    interval: Duration,
+    logged_at: Instant,
+    downloads: usize,
+    downloaded_bytes: usize,
+    uploads: usize,
+    uploaded_bytes: usize,
+    refused: usize,
+    failed: usize,
+}
+
+impl TransferStats {
+    fn new(interval: Duration, now: Instant) -> Self {
+        TransferStats {
+            interval,
+            logged_at: now,
+            downloads: 0,
+            downloaded_bytes: 0,
+            uploads: 0,
+            uploaded_bytes: 0,
+            refused: 0,
+            failed: 0,
+        }
+    }
+
+    fn record(&mut self, transfer: Transfer) {
+        match transfer {
+            Transfer::Downloaded(bytes) => {
+                self.downloads += 1;
+                self.downloaded_bytes += bytes;
+            }
+            Transfer::Uploaded(bytes) => {
+                self.uploads += 1;
+                self.uploaded_bytes += bytes;
+            }
+            Transfer::Refused => self.refused += 1,
+            Transfer::Failed => self.failed += 1,
+        }
+    }
+
+    /// Transfers since the previous stats, once per interval
+    fn take_stats(&mut self, now: Instant) -> Option<String> {
+        if now.saturating_duration_since(self.logged_at) < self.interval {
+            return None;
+        }
+
+        let stats = format!(
+            "Image transfers: {downloads} downloads ({downloaded} KiB), {uploads} uploads ({uploaded} KiB), \
+             {refused} refused, {failed} failed",
+            downloads = self.downloads,
+            downloaded = self.downloaded_bytes / 1024,
+            uploads = self.uploads,
+            uploaded = self.uploaded_bytes / 1024,
+            refused = self.refused,
+            failed = self.failed
+        );
+        *self = TransferStats::new(self.interval, now);
+
+        Some(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::service::cloud::metrics::{Transfer, TransferStats};
+
+    #[test]
+    fn it_reports_transfers_once_per_interval() {
+        let now = Instant::now();
+        let mut stats = TransferStats::new(Duration::from_secs(60), now);
+
+        stats.record(Transfer::Downloaded(3072));
+        stats.record(Transfer::Uploaded(1024));
+        stats.record(Transfer::Uploaded(1024));
+        stats.record(Transfer::Refused);
+        stats.record(Transfer::Failed);
+
+        assert_eq!(stats.take_stats(now + Duration::from_secs(30)), None);
+        assert_eq!(
+            stats.take_stats(now + Duration::from_secs(60)),
+            Some(String::from(
+                "Image transfers: 1 downloads (3 KiB), 2 uploads (2 KiB), 1 refused, 1 failed"
+            ))
+        );
+        assert_eq!(stats.take_stats(now + Duration::from_secs(61)), None);
+        assert_eq!(
+            stats.take_stats(now + Duration::from_secs(120)),
+            Some(String::from(
+                "Image transfers: 0 downloads (0 KiB), 0 uploads (0 KiB), 0 refused, 0 failed"
+            ))
+        );
+    }
+}
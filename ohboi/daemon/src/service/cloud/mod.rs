@@ -1,4 +1,10 @@
+pub use client::new_s3_client;
+pub use layer::{ImageUpload, ImageUploadError};
 pub use pub_api::*;
 
+mod client;
 mod layer;
+mod metrics;
+mod processing;
 mod pub_api;
+mod transfer;
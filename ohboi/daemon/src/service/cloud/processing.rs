@@ -0,0 +1,433 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::io::Reader;
+use image::{ColorType, DynamicImage, ImageError, ImageFormat, Rgb, RgbImage};
+use strum_macros::Display;
+
+use crate::settings::Images;
+
+const JPEG_QUALITY: u8 = 85;
+const WEBP_QUALITY: f32 = 80.0;
+
+#[derive(Display, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum ImageSize {
+    Thumbnail,
+    Medium,
+    Large,
+}
+
+impl ImageSize {
+    /// Longer side of the image, smaller images are not upscaled
+    fn max_side(self) -> u32 {
+        match self {
+            ImageSize::Thumbnail => 200,
+            ImageSize::Medium => 600,
+            ImageSize::Large => 1200,
+        }
+    }
+}
+
+#[derive(Display, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum ImageEncoding {
+    Jpeg,
+    Webp,
+}
+
+impl ImageEncoding {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageEncoding::Jpeg => "jpg",
+            ImageEncoding::Webp => "webp",
+        }
+    }
+}
+
+pub struct ProcessedImage {
+    /// Difference hash, see `is_same_photo`
+    pub hash: u64,
+    pub width: u32,
+    pub height: u32,
+    pub variants: Vec<ImageVariant>,
+}
+
+/// Photo which is already stored, it is only hashed to find its duplicates
+pub struct InspectedImage {
+    pub hash: u64,
+    pub width: u32,
+    pub height: u32,
+}
+
+pub struct ImageVariant {
+    pub size: ImageSize,
+    pub encoding: ImageEncoding,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+/// Downloaded file which is not a usable photo, there is no sense to download it again
+#[derive(Debug, PartialEq)]
+pub enum InvalidImage {
+    ContentType(String),
+    Format,
+    TooBig(usize),
+    Dimensions(u32, u32),
+    Broken(String),
+}
+
+impl fmt::Display for InvalidImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidImage::ContentType(content_type) => {
+                write!(f, "Unexpected content type {}", content_type)
+            }
+            InvalidImage::Format => write!(f, "Unsupported image format"),
+            InvalidImage::TooBig(bytes) => write!(f, "Image is too big: {} bytes", bytes),
+            InvalidImage::Dimensions(width, height) => {
+                write!(f, "Unexpected dimensions {}x{}", width, height)
+            }
+            InvalidImage::Broken(error) => write!(f, "Broken image: {}", error),
+        }
+    }
+}
+
+impl From<ImageError> for InvalidImage {
+    fn from(error: ImageError) -> Self {
+        InvalidImage::Broken(error.to_string())
+    }
+}
+
+/// Validates the downloaded image and encodes every size of it as jpeg and webp.
+/// Images are encoded from pixels, so EXIF and other metadata of the original are not stored.
+pub fn process_image(
+    data: &[u8],
+    content_type: Option<&str>,
+    settings: &Images,
+) -> Result<ProcessedImage, InvalidImage> {
+    if data.len() > settings.max_bytes {
+        return Err(InvalidImage::TooBig(data.len()));
+    }
+    if let Some(content_type) = content_type {
+        if !is_image_content_type(content_type) {
+            return Err(InvalidImage::ContentType(content_type.to_string()));
+        }
+    }
+
+    let format = image::guess_format(data).map_err(|_| InvalidImage::Format)?;
+    if !matches!(
+        format,
+        ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP | ImageFormat::Gif
+    ) {
+        return Err(InvalidImage::Format);
+    }
+
+    // Dimensions are read from the header, so huge images are refused before decoding
+    let (width, height) = Reader::with_format(Cursor::new(data), format).into_dimensions()?;
+    if width.min(height) < settings.min_side || width.max(height) > settings.max_side {
+        return Err(InvalidImage::Dimensions(width, height));
+    }
+
+    let photo = DynamicImage::ImageRgb8(flatten_on_white(&image::load_from_memory_with_format(
+        data, format,
+    )?));
+
+    let mut variants = vec![];
+    for &size in &[ImageSize::Thumbnail, ImageSize::Medium, ImageSize::Large] {
+        let resized = if width.max(height) > size.max_side() {
+            photo
+                .resize(size.max_side(), size.max_side(), FilterType::Lanczos3)
+                .to_rgb8()
+        } else {
+            photo.to_rgb8()
+        };
+
+        variants.push(encode_jpeg(&resized, size)?);
+        variants.push(encode_webp(&resized, size));
+    }
+
+    Ok(ProcessedImage {
+        hash: difference_hash(&photo),
+        width,
+        height,
+        variants,
+    })
+}
+
+/// Hashes the photo the same way as `process_image` does
+pub fn inspect_image(data: &[u8]) -> Result<InspectedImage, InvalidImage> {
+    let photo = flatten_on_white(&image::load_from_memory(data)?);
+
+    Ok(InspectedImage {
+        width: photo.width(),
+        height: photo.height(),
+        hash: difference_hash(&DynamicImage::ImageRgb8(photo)),
+    })
+}
+
+/// Same photo resized or re-encoded by another shop has almost the same hash
+pub fn is_same_photo(hash: u64, other_hash: u64, distance: u32) -> bool {
+    (hash ^ other_hash).count_ones() <= distance
+}
+
+/// Shops often serve images as a binary file, such files are checked by their content
+fn is_image_content_type(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+
+    mime.starts_with("image/") || mime == "application/octet-stream"
+}
+
+/// Transparent background becomes white, as it is shown on the product page
+fn flatten_on_white(image: &DynamicImage) -> RgbImage {
+    let rgba = image.to_rgba8();
+
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [red, green, blue, alpha] = rgba.get_pixel(x, y).0;
+        let blend = |channel: u8| {
+            let alpha = u16::from(alpha);
+            let value = (u16::from(channel) * alpha + 255 * (255 - alpha)) / 255;
+
+            u8::try_from(value).unwrap_or(u8::MAX)
+        };
+
+        Rgb([blend(red), blend(green), blend(blue)])
+    })
+}
+
+fn encode_jpeg(image: &RgbImage, size: ImageSize) -> Result<ImageVariant, InvalidImage> {
+    let mut data = vec![];
+    JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY).encode(
+        image.as_raw(),
+        image.width(),
+        image.height(),
+        ColorType::Rgb8,
+    )?;
+
+    Ok(ImageVariant {
+        size,
+        encoding: ImageEncoding::Jpeg,
+        width: image.width(),
+        height: image.height(),
+        data,
+    })
+}
+
+fn encode_webp(image: &RgbImage, size: ImageSize) -> ImageVariant {
+    let data = webp::Encoder::from_rgb(image.as_raw(), image.width(), image.height())
+        .encode(WEBP_QUALITY)
+        .to_vec();
+
+    ImageVariant {
+        size,
+        encoding: ImageEncoding::Webp,
+        width: image.width(),
+        height: image.height(),
+        data,
+    }
+}
+
+/// 64 bits of brightness differences between neighbour pixels of the 9x8 image
+fn difference_hash(image: &DynamicImage) -> u64 {
+    let small = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+
+    let mut hash = 0_u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y).0[0] < small.get_pixel(x + 1, y).0[0] {
+                hash |= 1;
+            }
+        }
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use image::{DynamicImage, ImageOutputFormat, Rgb, RgbImage};
+
+    use crate::service::cloud::processing::{
+        inspect_image, is_same_photo, process_image, ImageEncoding, ImageSize, InvalidImage,
+    };
+    use crate::settings::{ImageStoreKind, Images};
+
+    fn get_images_settings() -> Images {
+        Images {
+            store: ImageStoreKind::Memory,
+            dir: String::from("images"),
+            max_bytes: 1_000_000,
+            min_side: 100,
+            max_side: 2000,
+            dedupe_distance: 6,
+            download_timeout_seconds: 60,
+            stats_interval_seconds: 60,
+        }
+    }
+
+    fn photo(width: u32, height: u32, horizontal: bool) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            let position = if horizontal {
+                x * 255 / width
+            } else {
+                y * 255 / height
+            };
+            let value = u8::try_from(position).unwrap();
+
+            Rgb([value, value / 2, 255 - value])
+        }))
+    }
+
+    fn encode(image: &DynamicImage, format: ImageOutputFormat) -> Vec<u8> {
+        let mut data = vec![];
+        image.write_to(&mut data, format).unwrap();
+
+        data
+    }
+
+    #[test]
+    fn it_encodes_every_size_without_upscaling() {
+        let data = encode(&photo(900, 600, true), ImageOutputFormat::Png);
+
+        let processed = process_image(&data, Some("image/png"), &get_images_settings()).unwrap();
+
+        assert_eq!((processed.width, processed.height), (900, 600));
+        let sizes: Vec<(ImageSize, ImageEncoding, u32, u32)> = processed
+            .variants
+            .iter()
+            .map(|variant| {
+                (
+                    variant.size,
+                    variant.encoding,
+                    variant.width,
+                    variant.height,
+                )
+            })
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                (ImageSize::Thumbnail, ImageEncoding::Jpeg, 200, 133),
+                (ImageSize::Thumbnail, ImageEncoding::Webp, 200, 133),
+                (ImageSize::Medium, ImageEncoding::Jpeg, 600, 400),
+                (ImageSize::Medium, ImageEncoding::Webp, 600, 400),
+                (ImageSize::Large, ImageEncoding::Jpeg, 900, 600),
+                (ImageSize::Large, ImageEncoding::Webp, 900, 600),
+            ]
+        );
+        assert!(processed
+            .variants
+            .iter()
+            .all(|variant| !variant.data.is_empty()));
+    }
+
+    #[test]
+    fn it_strips_exif() {
+        let mut data = encode(&photo(300, 200, true), ImageOutputFormat::Jpeg(90));
+        // APP1 segment right after the start of image marker
+        let exif: Vec<u8> = [
+            &[0xFF, 0xE1, 0x00, 0x10][..],
+            b"Exif\0\0",
+            b"II*\0\x08\0\0\0",
+        ]
+        .concat();
+        data.splice(2..2, exif);
+
+        let processed = process_image(&data, None, &get_images_settings()).unwrap();
+
+        let has_exif = |data: &[u8]| data.windows(4).any(|window| window == b"Exif");
+        assert!(has_exif(&data));
+        assert!(processed
+            .variants
+            .iter()
+            .all(|variant| !has_exif(&variant.data)));
+    }
+
+    #[test]
+    fn it_refuses_files_which_are_not_photos() {
+        let png = encode(&photo(300, 200, true), ImageOutputFormat::Png);
+
+        assert_eq!(
+            process_image(b"<html></html>", Some("image/png"), &get_images_settings()).err(),
+            Some(InvalidImage::Format)
+        );
+        assert_eq!(
+            process_image(
+                &png,
+                Some("text/html; charset=utf-8"),
+                &get_images_settings()
+            )
+            .err(),
+            Some(InvalidImage::ContentType(String::from(
+                "text/html; charset=utf-8"
+            )))
+        );
+        assert_eq!(
+            process_image(
+                &encode(&photo(300, 50, true), ImageOutputFormat::Png),
+                Some("application/octet-stream"),
+                &get_images_settings()
+            )
+            .err(),
+            Some(InvalidImage::Dimensions(300, 50))
+        );
+        assert_eq!(
+            process_image(
+                &png,
+                None,
+                &Images {
+                    max_bytes: 10,
+                    ..get_images_settings()
+                }
+            )
+            .err(),
+            Some(InvalidImage::TooBig(png.len()))
+        );
+    }
+
+    #[test]
+    fn it_recognizes_the_same_photo_from_another_shop() {
+        let original = photo(900, 600, true);
+        let hash = |image: &DynamicImage, format| {
+            process_image(&encode(image, format), None, &get_images_settings())
+                .unwrap()
+                .hash
+        };
+
+        let png = hash(&original, ImageOutputFormat::Png);
+        let resized_jpeg = hash(
+            &original.resize(450, 300, image::imageops::FilterType::Triangle),
+            ImageOutputFormat::Jpeg(70),
+        );
+        let other_photo = hash(&photo(900, 600, false), ImageOutputFormat::Png);
+
+        let distance = get_images_settings().dedupe_distance;
+        assert!(is_same_photo(png, resized_jpeg, distance));
+        assert!(!is_same_photo(png, other_photo, distance));
+    }
+
+    #[test]
+    fn it_hashes_stored_photo_as_processed_one() {
+        let data = encode(&photo(900, 600, true), ImageOutputFormat::Jpeg(90));
+
+        let processed = process_image(&data, None, &get_images_settings()).unwrap();
+        let inspected = inspect_image(&data).unwrap();
+
+        assert_eq!(
+            (inspected.hash, inspected.width, inspected.height),
+            (processed.hash, 900, 600)
+        );
+    }
+}
@@ -4,21 +4,28 @@ use maplit::btreemap;
 
 use lib::error_reporting::{add_breadcrumb, ReportingContext};
 
-use crate::service::cloud::layer::upload_image_to_s3;
+use crate::db::entity::source::SourceName;
+use crate::service::blob_store::get_image_store;
+use crate::service::cloud::layer::{upload_image, ImageUpload, ImageUploadError};
 use crate::service::Executor;
 
-pub async fn upload_image_to_cloud(file_path: String, image_url: String) -> bool {
+pub async fn upload_image_to_cloud(
+    product_id: i32,
+    source: SourceName,
+    file_path: String,
+    image_url: String,
+) -> Result<ImageUpload, ImageUploadError> {
     let breadcrumb_data = btreemap! {
         "file_path" => file_path.clone(),
         "image_url" => image_url.clone()
     };
     add_uploader_breadcrumb("uploading image", breadcrumb_data.clone());
 
-    let success = upload_image_to_s3(file_path, image_url).await;
+    let result = upload_image(get_image_store(), product_id, source, file_path, image_url).await;
 
     add_uploader_breadcrumb("uploaded image", breadcrumb_data);
 
-    success
+    result
 }
 
 fn add_uploader_breadcrumb(message: &str, data: BTreeMap<&str, String>) {
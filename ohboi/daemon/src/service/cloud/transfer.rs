@@ -0,0 +1,134 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+
+use crate::service::request::{get_stream, RequestError};
+
+#[derive(Debug)]
+pub enum TransferError {
+    /// Announced or already read amount of bytes
+    TooBig(usize),
+    Timeout,
+    Request(RequestError),
+}
+
+pub struct DownloadedFile {
+    pub data: Bytes,
+    pub content_type: Option<String>,
+}
+
+/// Downloads the file, it has to be in memory to be decoded.
+/// `timeout` covers the whole download with its retries, from the connection to the last chunk.
+/// Files over `max_bytes` are refused as soon as it is known, before the rest of them is read.
+pub async fn download_file(
+    url: &str,
+    max_bytes: usize,
+    timeout: Duration,
+) -> Result<DownloadedFile, TransferError> {
+    let download = async {
+        let download = get_stream(url).await.map_err(TransferError::Request)?;
+        let content_type = download.content_type();
+        let declared_length = download.content_length();
+
+        let data = read_limited(declared_length, download.into_chunks(), max_bytes).await?;
+
+        Ok(DownloadedFile { data, content_type })
+    };
+
+    tokio::time::timeout(timeout, download)
+        .await
+        .map_err(|_| TransferError::Timeout)?
+}
+
+async fn read_limited<S>(
+    declared_length: Option<u64>,
+    chunks: S,
+    max_bytes: usize,
+) -> Result<Bytes, TransferError>
+where
+    S: Stream<Item = Result<Bytes, RequestError>>,
+{
+    let declared_length =
+        declared_length.map(|length| usize::try_from(length).unwrap_or(usize::MAX));
+    if let Some(length) = declared_length.filter(|&length| length > max_bytes) {
+        return Err(TransferError::TooBig(length));
+    }
+
+    futures::pin_mut!(chunks);
+    let mut body = BytesMut::with_capacity(declared_length.unwrap_or_default().min(max_bytes));
+
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(TransferError::Request)?;
+        if body.len() + chunk.len() > max_bytes {
+            return Err(TransferError::TooBig(body.len() + chunk.len()));
+        }
+
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use futures::stream;
+
+    use crate::service::cloud::transfer::{download_file, read_limited, TransferError};
+    use crate::service::request::RequestError;
+
+    fn chunks(parts: &[&'static [u8]]) -> Vec<Result<Bytes, RequestError>> {
+        parts
+            .iter()
+            .map(|&part| Ok(Bytes::from_static(part)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_reads_all_chunks() {
+        let body = read_limited(Some(4), stream::iter(chunks(&[b"ab", b"cd"])), 10)
+            .await
+            .unwrap();
+
+        assert_eq!(body, Bytes::from_static(b"abcd"));
+    }
+
+    #[tokio::test]
+    async fn it_refuses_too_big_files() {
+        let announced = read_limited(Some(100), stream::iter(chunks(&[b"ab"])), 10).await;
+        let read = read_limited(
+            None,
+            stream::iter(chunks(&[b"abcdef", b"ghijkl", b"mn"])),
+            10,
+        )
+        .await;
+
+        assert!(matches!(announced, Err(TransferError::TooBig(100))));
+        assert!(matches!(read, Err(TransferError::TooBig(12))));
+    }
+
+    #[tokio::test]
+    async fn it_stops_downloads_without_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        // Connection is accepted, but the headers never come
+        thread::spawn(move || {
+            let _connections: Vec<_> = listener.incoming().flatten().collect();
+        });
+
+        let result = download_file(
+            &format!("http://{}/image.png", address),
+            10,
+            Duration::from_millis(100),
+        )
+        .await;
+
+        assert!(matches!(result, Err(TransferError::Timeout)));
+    }
+}
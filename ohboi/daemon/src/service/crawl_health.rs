@@ -0,0 +1,314 @@
+use strum_macros::Display;
+
+use lib::db::entity::crawl_run::{CrawlRunCounters, CrawlRunKind};
+use lib::db::repository::crawl_run::{
+    get_finished_counters, get_previous_health_alert, set_health_alert,
+};
+use lib::error_reporting;
+use lib::error_reporting::ReportingContext;
+
+use crate::db::entity::source::SourceName;
+use crate::service::Executor;
+use crate::settings::Health;
+use crate::SETTINGS;
+
+#[derive(Display, Debug, Clone, Copy, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+enum HealthMetric {
+    MissingId,
+    MissingTitle,
+    MissingPrice,
+    MissingAvailability,
+    MissingDescription,
+    UnknownCharacteristics,
+}
+
+#[derive(Debug, PartialEq)]
+struct MetricChange {
+    metric: HealthMetric,
+    current: f64,
+    baseline: f64,
+}
+
+/// Checked when a category run is finished: the run itself and the latest page and details runs of the source
+/// are compared with the previous ones, the degraded metrics are saved to the category run.
+/// Alert is sent once per category run and only when the set of degraded metrics differs from the previous one.
+pub fn check_health(run_id: i32, source: SourceName, source_id: i32) {
+    let settings = &SETTINGS.health;
+
+    let changes: Vec<(CrawlRunKind, MetricChange)> = [
+        CrawlRunKind::Category,
+        CrawlRunKind::Page,
+        CrawlRunKind::Details,
+    ]
+    .iter()
+    .flat_map(|&kind| {
+        find_kind_degradations(source_id, kind, settings)
+            .into_iter()
+            .map(move |change| (kind, change))
+    })
+    .collect();
+
+    let alert = if changes.is_empty() {
+        None
+    } else {
+        Some(
+            changes
+                .iter()
+                .map(|(kind, change)| format!("{}:{}", kind, change.metric))
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+    };
+    let previous_alert = get_previous_health_alert(source_id, CrawlRunKind::Category, run_id);
+    set_health_alert(run_id, alert.as_deref());
+
+    if alert.is_none() || alert == previous_alert {
+        return;
+    }
+
+    let message = format!(
+        "[{source}] Crawler health degraded: {changes}",
+        source = source,
+        changes = changes
+            .iter()
+            .map(|(kind, change)| format!("{} {}", kind, describe(change)))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    error_reporting::error(
+        message.as_str(),
+        &ReportingContext {
+            executor: &Executor::CrawlHealth,
+            action: "check_health",
+        },
+    );
+}
+
+/// Category run is compared alone, details and page jobs have one page per run, so their windows are summed
+fn find_kind_degradations(
+    source_id: i32,
+    kind: CrawlRunKind,
+    settings: &Health,
+) -> Vec<MetricChange> {
+    let window = match kind {
+        CrawlRunKind::Category => 1,
+        CrawlRunKind::Page | CrawlRunKind::Details => settings.window_runs,
+    };
+
+    let current = sum(&get_finished_counters(source_id, kind, 0, window));
+    let baseline = sum(&get_finished_counters(
+        source_id,
+        kind,
+        window,
+        window * settings.baseline_windows,
+    ));
+
+    find_degradations(&current, &baseline, settings)
+}
+
+fn find_degradations(
+    current: &CrawlRunCounters,
+    baseline: &CrawlRunCounters,
+    settings: &Health,
+) -> Vec<MetricChange> {
+    let shares = [
+        (
+            HealthMetric::MissingId,
+            (current.missing_id, current.items),
+            (baseline.missing_id, baseline.items),
+        ),
+        (
+            HealthMetric::MissingTitle,
+            (current.missing_title, current.items),
+            (baseline.missing_title, baseline.items),
+        ),
+        (
+            HealthMetric::MissingPrice,
+            (current.missing_price, current.items),
+            (baseline.missing_price, baseline.items),
+        ),
+        (
+            HealthMetric::MissingAvailability,
+            (current.missing_availability, current.items),
+            (baseline.missing_availability, baseline.items),
+        ),
+        (
+            HealthMetric::MissingDescription,
+            (current.missing_description, current.pages),
+            (baseline.missing_description, baseline.pages),
+        ),
+    ];
+
+    let mut changes: Vec<MetricChange> = shares
+        .iter()
+        .filter_map(|&(metric, current, baseline)| {
+            compare(metric, current, baseline, settings.min_items)
+        })
+        .filter(|change| change.current - change.baseline >= settings.ratio_increase)
+        .collect();
+
+    changes.extend(
+        compare(
+            HealthMetric::UnknownCharacteristics,
+            (current.unknown_characteristics, current.pages),
+            (baseline.unknown_characteristics, baseline.pages),
+            settings.min_items,
+        )
+        .filter(|change| {
+            change.current - change.baseline >= settings.unknown_characteristics_increase
+        }),
+    );
+
+    changes
+}
+
+/// Values are `(amount, total)`, too small runs and runs without history are not compared
+fn compare(
+    metric: HealthMetric,
+    current: (i32, i32),
+    baseline: (i32, i32),
+    min_items: i32,
+) -> Option<MetricChange> {
+    if current.1 < min_items.max(1) || baseline.1 == 0 {
+        return None;
+    }
+
+    Some(MetricChange {
+        metric,
+        current: f64::from(current.0) / f64::from(current.1),
+        baseline: f64::from(baseline.0) / f64::from(baseline.1),
+    })
+}
+
+fn describe(change: &MetricChange) -> String {
+    match change.metric {
+        HealthMetric::UnknownCharacteristics => format!(
+            "{metric} {current:.2} per page (was {baseline:.2})",
+            metric = change.metric,
+            current = change.current,
+            baseline = change.baseline
+        ),
+        _ => format!(
+            "{metric} {current:.1}% (was {baseline:.1}%)",
+            metric = change.metric,
+            current = change.current * 100.0,
+            baseline = change.baseline * 100.0
+        ),
+    }
+}
+
+fn sum(runs: &[CrawlRunCounters]) -> CrawlRunCounters {
+    runs.iter()
+        .fold(CrawlRunCounters::default(), |total, run| CrawlRunCounters {
+            pages: total.pages + run.pages,
+            products_new: total.products_new + run.products_new,
+            products_updated: total.products_updated + run.products_updated,
+            products_unchanged: total.products_unchanged + run.products_unchanged,
+            failures: total.failures + run.failures,
+            postponed: total.postponed + run.postponed,
+            warnings: total.warnings + run.warnings,
+            items: total.items + run.items,
+            missing_id: total.missing_id + run.missing_id,
+            missing_title: total.missing_title + run.missing_title,
+            missing_price: total.missing_price + run.missing_price,
+            missing_availability: total.missing_availability + run.missing_availability,
+            missing_description: total.missing_description + run.missing_description,
+            unknown_characteristics: total.unknown_characteristics + run.unknown_characteristics,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use lib::db::entity::crawl_run::CrawlRunCounters;
+
+    use crate::service::crawl_health::{find_degradations, sum, HealthMetric, MetricChange};
+    use crate::settings::Health;
+
+    fn get_listing_counters(items: i32, missing_price: i32) -> CrawlRunCounters {
+        CrawlRunCounters {
+            pages: 2,
+            items,
+            missing_price,
+            ..CrawlRunCounters::default()
+        }
+    }
+
+    #[test]
+    fn it_finds_drifted_selector() {
+        let changes = find_degradations(
+            &get_listing_counters(40, 40),
+            &get_listing_counters(200, 2),
+            &Health::default(),
+        );
+
+        assert_eq!(
+            changes,
+            vec![MetricChange {
+                metric: HealthMetric::MissingPrice,
+                current: 1.0,
+                baseline: 0.01,
+            }]
+        );
+    }
+
+    #[test]
+    fn it_ignores_stable_ratios() {
+        let changes = find_degradations(
+            &get_listing_counters(40, 2),
+            &get_listing_counters(200, 8),
+            &Health::default(),
+        );
+
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn it_ignores_small_runs_and_runs_without_history() {
+        assert!(find_degradations(
+            &get_listing_counters(5, 5),
+            &get_listing_counters(200, 0),
+            &Health::default()
+        )
+        .is_empty());
+        assert!(find_degradations(
+            &get_listing_counters(40, 40),
+            &CrawlRunCounters::default(),
+            &Health::default()
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn it_finds_growth_of_unknown_characteristics() {
+        let details = |pages, unknown_characteristics| CrawlRunCounters {
+            pages,
+            unknown_characteristics,
+            ..CrawlRunCounters::default()
+        };
+
+        let changes = find_degradations(&details(10, 30), &details(100, 50), &Health::default());
+
+        assert_eq!(
+            changes,
+            vec![MetricChange {
+                metric: HealthMetric::UnknownCharacteristics,
+                current: 3.0,
+                baseline: 0.5,
+            }]
+        );
+    }
+
+    #[test]
+    fn it_sums_runs() {
+        let total = sum(&[get_listing_counters(40, 1), get_listing_counters(30, 2)]);
+
+        assert_eq!(
+            total,
+            CrawlRunCounters {
+                pages: 4,
+                ..get_listing_counters(70, 3)
+            }
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::cell::RefCell;
+use std::future::Future;
+
+use lib::db::entity::crawl_run::{CrawlRunCounters, CrawlRunKind, CrawlRunStatus, NewCrawlRun};
+use lib::db::repository::crawl_run;
+
+use crate::db::entity::category::CategorySlug;
+use crate::db::entity::source::SourceName;
+use crate::db::repository::category::get_category;
+use crate::db::repository::source::get_source;
+use crate::service::crawl_health::check_health;
+use crate::service::product_matching::with_title_index;
+use crate::service::unknown_characteristic::collect_unknown_characteristics;
+
+tokio::task_local! {
+    static CURRENT_RUN: RefCell<CrawlRunCounters>;
+}
+
+/// Runs the crawl job and saves it to `crawl_run` with everything recorded while it was running.
+/// Failed result of the job marks the run as failed. Unknown characteristics are collected as well,
+/// titles of categories are loaded for product matching once per run.
+pub async fn track_run<Job, T, E>(
+    kind: CrawlRunKind,
+    source: SourceName,
+    category: Option<CategorySlug>,
+    job: Job,
+) -> Result<T, E>
+where
+    Job: Future<Output = Result<T, E>>,
+{
+    let source_id = get_source(source).id;
+    let run_id = crawl_run::start(&NewCrawlRun {
+        source_id,
+        category_id: category.map(|category| get_category(category).id),
+        kind: kind.to_string(),
+    });
+
+    let (result, counters) = CURRENT_RUN
+        .scope(RefCell::new(CrawlRunCounters::default()), async {
+            let result = collect_unknown_characteristics(source, with_title_index(job)).await;
+
+            (result, CURRENT_RUN.with(|counters| *counters.borrow()))
+        })
+        .await;
+
+    let status = if result.is_ok() {
+        CrawlRunStatus::Completed
+    } else {
+        CrawlRunStatus::Failed
+    };
+    crawl_run::finish(run_id, status, &counters);
+    if let CrawlRunKind::Category = kind {
+        check_health(run_id, source, source_id);
+    }
+
+    result
+}
+
+/// Updates counters of the current run, does nothing outside of a tracked run (e.g. in tests)
+pub fn record<F>(update: F)
+where
+    F: FnOnce(&mut CrawlRunCounters),
+{
+    let _ = CURRENT_RUN.try_with(|counters| update(&mut counters.borrow_mut()));
+}
+
+/// Prints the latest runs, one per line
+pub fn print_crawl_runs(source_name: Option<&str>, limit: i64) {
+    for run in crawl_run::get_recent_runs(source_name, limit) {
+        let duration = run.finished_at.map_or_else(
+            || String::from("-"),
+            |finished_at| format!("{}s", (finished_at - run.started_at).num_seconds()),
+        );
+
+        println!(
+            "{started_at} {source} {category} {kind} {status} {duration} pages={pages} new={new} updated={updated} \
+             unchanged={unchanged} failures={failures} postponed={postponed} warnings={warnings} health={health}",
+            started_at = run.started_at.format("%Y-%m-%d %H:%M:%S"),
+            source = run.source,
+            category = run.category.as_deref().unwrap_or("-"),
+            kind = run.kind,
+            status = run.status,
+            duration = duration,
+            pages = run.pages,
+            new = run.products_new,
+            updated = run.products_updated,
+            unchanged = run.products_unchanged,
+            failures = run.failures,
+            postponed = run.postponed,
+            warnings = run.warnings,
+            health = run.health_alert.as_deref().unwrap_or("ok")
+        );
+    }
+}
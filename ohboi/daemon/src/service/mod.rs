@@ -2,13 +2,19 @@ use inflector::Inflector;
 
 use lib::error_reporting::DisplayString;
 
+pub mod blob_store;
 pub mod cloud;
+pub mod crawl_health;
+pub mod crawl_run;
 pub mod html_cleaner;
+pub mod product_matching;
 pub mod request;
+pub mod unknown_characteristic;
 
 #[derive(Debug)]
 enum Executor {
     Cloud,
+    CrawlHealth,
 }
 
 impl DisplayString for Executor {
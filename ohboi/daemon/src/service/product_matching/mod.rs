@@ -0,0 +1,272 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+
+use crate::db::entity::product_identifier::IdentifierKind;
+use crate::db::entity::product_match_candidate::MatchReason;
+use crate::db::repository::product::get_titles_by_category;
+use crate::db::repository::{product_identifier, product_match_candidate};
+use crate::dto::parsed_product::{InternationalParsedProduct, ProductIdentifiers};
+use crate::service::product_matching::normalize::{
+    normalize_gtin, normalize_model, normalize_title, NormalizedTitle,
+};
+use crate::service::product_matching::score::{is_same_model, similarity};
+use crate::service::product_matching::title_index::TitleIndex;
+use crate::SETTINGS;
+
+mod normalize;
+mod score;
+mod title_index;
+
+tokio::task_local! {
+    /// Titles of categories by category id, loaded once per run instead of once per offer
+    static TITLE_INDEXES: RefCell<HashMap<i32, TitleIndex>>;
+}
+
+/// Review of more candidates makes no sense, the best ones are the first
+static MAX_CANDIDATES: usize = 5;
+
+#[derive(Debug, PartialEq)]
+pub enum ProductMatch {
+    Found(i32),
+    /// Offer is saved as a new product and candidates wait for the review
+    Ambiguous(Vec<MatchCandidate>),
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MatchCandidate {
+    pub product_id: i32,
+    pub score: f64,
+}
+
+/// Looks for the product which is listed under another title: by identifiers at first,
+/// then by similarity with titles of the category
+pub fn match_product(
+    parsed_product: &InternationalParsedProduct,
+    category_id: i32,
+) -> ProductMatch {
+    if let Some(product_id) = find_by_identifiers(&parsed_product.identifiers) {
+        return ProductMatch::Found(product_id);
+    }
+
+    let title = normalize_title(&parsed_product.title);
+    let choose = |index: &TitleIndex| {
+        choose_match(
+            &title,
+            index,
+            SETTINGS.matching.auto_link_score,
+            SETTINGS.matching.review_score,
+        )
+    };
+
+    TITLE_INDEXES
+        .try_with(|indexes| {
+            choose(
+                indexes
+                    .borrow_mut()
+                    .entry(category_id)
+                    .or_insert_with(|| TitleIndex::new(get_titles_by_category(category_id))),
+            )
+        })
+        .unwrap_or_else(|_| choose(&TitleIndex::new(get_titles_by_category(category_id))))
+}
+
+/// Runs the job with titles of categories loaded on the first match and kept until the end of the job
+pub async fn with_title_index<Job, T>(job: Job) -> T
+where
+    Job: Future<Output = T>,
+{
+    TITLE_INDEXES.scope(RefCell::new(HashMap::new()), job).await
+}
+
+/// Adds the product created in the current job to the loaded titles, does nothing outside of the job
+pub fn remember_title(category_id: i32, product_id: i32, title: &str) {
+    let _ = TITLE_INDEXES.try_with(|indexes| {
+        if let Some(index) = indexes.borrow_mut().get_mut(&category_id) {
+            index.add(product_id, title);
+        }
+    });
+}
+
+/// Identifier which already belongs to another product means that they are probably the same,
+/// but it is not merged automatically since sources make mistakes in codes
+pub fn remember_identifiers(product_id: i32, identifiers: &ProductIdentifiers) {
+    for (kind, value) in normalize_identifiers(identifiers) {
+        let owner_id = product_identifier::create_if_not_exists(product_id, kind, &value);
+
+        if owner_id != product_id {
+            let reason = match kind {
+                IdentifierKind::Gtin => MatchReason::Gtin,
+                IdentifierKind::Model => MatchReason::Model,
+            };
+            product_match_candidate::create_many_if_not_exists(
+                product_id,
+                &[MatchCandidate {
+                    product_id: owner_id,
+                    score: 1.0,
+                }],
+                reason,
+            );
+        }
+    }
+}
+
+/// Only one confident candidate of the same model is linked automatically, the rest of similar ones go to the review
+fn choose_match(
+    title: &NormalizedTitle,
+    index: &TitleIndex,
+    auto_link_score: f64,
+    review_score: f64,
+) -> ProductMatch {
+    let mut candidates: Vec<(MatchCandidate, bool)> = index
+        .get_sharing_tokens(title)
+        .into_iter()
+        .map(|(product_id, product_title)| {
+            let candidate = MatchCandidate {
+                product_id: *product_id,
+                score: similarity(title, product_title),
+            };
+
+            (candidate, is_same_model(title, product_title))
+        })
+        .filter(|(candidate, _)| candidate.score >= review_score)
+        .collect();
+    candidates.sort_by(|(a, _), (b, _)| b.score.partial_cmp(&a.score).unwrap());
+
+    let confident_candidates: Vec<&(MatchCandidate, bool)> = candidates
+        .iter()
+        .take_while(|(candidate, _)| candidate.score >= auto_link_score)
+        .collect();
+
+    if let [(candidate, true)] = confident_candidates.as_slice() {
+        ProductMatch::Found(candidate.product_id)
+    } else if candidates.is_empty() {
+        ProductMatch::NotFound
+    } else {
+        ProductMatch::Ambiguous(
+            candidates
+                .into_iter()
+                .take(MAX_CANDIDATES)
+                .map(|(candidate, _)| candidate)
+                .collect(),
+        )
+    }
+}
+
+fn find_by_identifiers(identifiers: &ProductIdentifiers) -> Option<i32> {
+    normalize_identifiers(identifiers)
+        .into_iter()
+        .find_map(|(kind, value)| product_identifier::get_product_id(kind, &value))
+}
+
+/// GTIN goes first as the most precise one
+fn normalize_identifiers(identifiers: &ProductIdentifiers) -> Vec<(IdentifierKind, String)> {
+    let gtin = identifiers
+        .gtin
+        .as_deref()
+        .map(|gtin| (IdentifierKind::Gtin, normalize_gtin(gtin)));
+    let model = identifiers
+        .model
+        .as_deref()
+        .map(|model| (IdentifierKind::Model, normalize_model(model)));
+
+    gtin.into_iter()
+        .chain(model)
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::product_matching::normalize::normalize_title;
+    use crate::service::product_matching::title_index::TitleIndex;
+    use crate::service::product_matching::{choose_match, MatchCandidate, ProductMatch};
+
+    fn products(titles: &[&str]) -> TitleIndex {
+        TitleIndex::new(
+            (1..)
+                .zip(titles.iter())
+                .map(|(id, title)| (id, (*title).to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn it_links_the_only_confident_candidate() {
+        assert_eq!(
+            choose_match(
+                &normalize_title("Redmi Note 10 Pro 6/128GB"),
+                &products(&["Xiaomi Redmi Note 10 Pro", "Redmi Note 10"]),
+                0.9,
+                0.6,
+            ),
+            ProductMatch::Found(1)
+        );
+    }
+
+    #[test]
+    fn it_sends_similar_candidates_to_review() {
+        assert_eq!(
+            choose_match(
+                &normalize_title("Redmi Note 10 Pro"),
+                &products(&["Redmi Note 10S", "Redmi Note 10", "Galaxy Watch 3"]),
+                0.9,
+                0.6,
+            ),
+            ProductMatch::Ambiguous(vec![
+                MatchCandidate {
+                    product_id: 2,
+                    score: 0.75,
+                },
+                MatchCandidate {
+                    product_id: 1,
+                    score: 0.6,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn it_sends_several_confident_candidates_to_review() {
+        let result = choose_match(
+            &normalize_title("Redmi Note 10 Pro"),
+            &products(&["Xiaomi Redmi Note 10 Pro", "Redmi Note 10 Pro 8/128GB"]),
+            0.9,
+            0.6,
+        );
+
+        assert!(matches!(result, ProductMatch::Ambiguous(candidates) if candidates.len() == 2));
+    }
+
+    #[test]
+    fn it_finds_nothing_in_other_models() {
+        assert_eq!(
+            choose_match(
+                &normalize_title("Galaxy Watch 3"),
+                &products(&["Redmi Note 10"]),
+                0.9,
+                0.6,
+            ),
+            ProductMatch::NotFound
+        );
+    }
+
+    #[test]
+    fn it_does_not_link_other_models_of_the_line() {
+        let result = choose_match(
+            &normalize_title("Samsung Galaxy S21+ 8/256GB"),
+            &products(&["Samsung Galaxy S21"]),
+            0.5,
+            0.5,
+        );
+
+        assert_eq!(
+            result,
+            ProductMatch::Ambiguous(vec![MatchCandidate {
+                product_id: 1,
+                score: 0.75,
+            }])
+        );
+    }
+}
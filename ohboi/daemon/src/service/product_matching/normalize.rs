@@ -0,0 +1,198 @@
+use std::collections::BTreeSet;
+
+use regex::Regex;
+
+lazy_static! {
+    /// `6/128gb` | `6+128gb` | `128 gb` | `1tb`, runs after transliteration so `ГБ` is already `gb`.
+    /// Plus without the unit is a model (`S21+ 8/256`), not memory.
+    static ref MEMORY_RE: Regex = Regex::new(
+        r"\b\d+\s*/\s*\d+\s*(gb|tb)?\b|\b\d+\s*\+\s*\d+\s*(gb|tb)\b|\b\d+\s*(gb|tb)\b"
+    )
+    .unwrap();
+    static ref TOKEN_RE: Regex = Regex::new(r"[a-z]+|\d+|\+").unwrap();
+}
+
+/// Words which some sources put in front of the model
+static NOISE_WORDS: [&str; 6] = [
+    "smartfon",
+    "smartphone",
+    "mobilnyi",
+    "telefon",
+    "umnye",
+    "chasy",
+];
+
+/// Brand with its sub-brands, titles often have only a sub-brand
+static BRANDS: [(&str, &[&str]); 6] = [
+    ("xiaomi", &["redmi", "poco", "mi"]),
+    ("samsung", &["galaxy"]),
+    ("apple", &["iphone", "ipad", "airpods"]),
+    ("huawei", &[]),
+    ("honor", &[]),
+    ("realme", &[]),
+];
+
+#[derive(Debug, PartialEq)]
+pub struct NormalizedTitle {
+    pub brand: Option<&'static str>,
+    /// Brand itself is not a token, sub-brands are, since they tell apart model lines
+    pub tokens: BTreeSet<String>,
+}
+
+/// `Смартфон Xiaomi Редми 9A 2/32 ГБ` -> `xiaomi` with `redmi`, `9`, `a`
+pub fn normalize_title(title: &str) -> NormalizedTitle {
+    let text = transliterate(&title.to_lowercase());
+    let text = MEMORY_RE.replace_all(&text, " ");
+
+    let mut brand = None;
+    let mut tokens = BTreeSet::new();
+
+    for token in TOKEN_RE.find_iter(&text).map(|m| m.as_str()) {
+        // `S21+` is the same as `S21 Plus`
+        let token = if token == "+" { "plus" } else { token };
+
+        if NOISE_WORDS.contains(&token) {
+            continue;
+        }
+
+        if let Some((name, _)) = BRANDS
+            .iter()
+            .find(|(name, sub_brands)| *name == token || sub_brands.contains(&token))
+        {
+            brand = Some(*name);
+
+            if *name == token {
+                continue;
+            }
+        }
+
+        tokens.insert(token.to_string());
+    }
+
+    NormalizedTitle { brand, tokens }
+}
+
+/// GTIN-13 is often written as GTIN-14 with a leading zero
+pub fn normalize_gtin(gtin: &str) -> String {
+    gtin.chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>()
+        .trim_start_matches('0')
+        .to_string()
+}
+
+/// `m2101k6g` | `M2101-K6G` -> `M2101K6G`
+pub fn normalize_model(model: &str) -> String {
+    model
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+fn transliterate(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match transliterate_char(c) {
+            Some(latin) => result.push_str(latin),
+            None => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Russian and ukrainian letters, lowercase only
+fn transliterate_char(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' | 'ё' | 'э' => "e",
+        'є' => "ye",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    };
+
+    Some(latin)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::product_matching::normalize::{
+        normalize_gtin, normalize_model, normalize_title,
+    };
+
+    fn tokens(title: &str) -> Vec<String> {
+        normalize_title(title).tokens.into_iter().collect()
+    }
+
+    #[test]
+    fn it_drops_brand_and_memory() {
+        let normalized = normalize_title("Xiaomi Redmi Note 10 Pro 6/128GB");
+
+        assert_eq!(normalized.brand, Some("xiaomi"));
+        assert_eq!(
+            tokens("Xiaomi Redmi Note 10 Pro 6/128GB"),
+            vec!["10", "note", "pro", "redmi"]
+        );
+    }
+
+    #[test]
+    fn it_transliterates_cyrillic() {
+        let normalized = normalize_title("Смартфон Редми 9A 2/32 ГБ");
+
+        assert_eq!(normalized.brand, Some("xiaomi"));
+        assert_eq!(tokens("Смартфон Редми 9A 2/32 ГБ"), vec!["9", "a", "redmi"]);
+    }
+
+    #[test]
+    fn it_splits_letters_and_digits() {
+        assert_eq!(tokens("Galaxy Watch3"), tokens("Samsung Galaxy Watch 3"));
+    }
+
+    #[test]
+    fn it_keeps_plus_of_model() {
+        assert_eq!(
+            tokens("Galaxy S21+ 8/256GB"),
+            vec!["21", "galaxy", "plus", "s"]
+        );
+        assert_eq!(
+            tokens("Galaxy S21+ 8/256GB"),
+            tokens("Samsung Galaxy S21 Plus")
+        );
+        assert_eq!(tokens("Redmi 9 4+64GB"), vec!["9", "redmi"]);
+    }
+
+    #[test]
+    fn it_normalizes_identifiers() {
+        assert_eq!(normalize_gtin("06934177734335"), "6934177734335");
+        assert_eq!(normalize_model("m2101-k6g"), "M2101K6G");
+    }
+}
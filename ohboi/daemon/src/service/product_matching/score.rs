@@ -0,0 +1,98 @@
+use crate::service::product_matching::normalize::NormalizedTitle;
+
+/// Words which tell apart models of the same line
+static MODEL_WORDS: [&str; 14] = [
+    "pro", "plus", "ultra", "lite", "max", "mini", "fe", "se", "neo", "note", "prime", "edge",
+    "fold", "flip",
+];
+
+/// Jaccard index of title tokens. Titles of different brands never match, neither do titles without a known brand,
+/// since nothing tells that they are of the same brand.
+#[allow(clippy::cast_precision_loss)]
+pub fn similarity(a: &NormalizedTitle, b: &NormalizedTitle) -> f64 {
+    match (a.brand, b.brand) {
+        (Some(a_brand), Some(b_brand)) if a_brand == b_brand => {}
+        _ => return 0.0,
+    }
+
+    let union = a.tokens.union(&b.tokens).count();
+    if union == 0 {
+        return 0.0;
+    }
+
+    a.tokens.intersection(&b.tokens).count() as f64 / union as f64
+}
+
+/// Titles which differ in a number, a letter of the model (`9a`, `10s`) or a model word are different models,
+/// however similar the rest of them is
+pub fn is_same_model(a: &NormalizedTitle, b: &NormalizedTitle) -> bool {
+    !a.tokens
+        .symmetric_difference(&b.tokens)
+        .any(|token| is_model_token(token))
+}
+
+fn is_model_token(token: &str) -> bool {
+    token.len() == 1 || token.chars().all(|c| c.is_ascii_digit()) || MODEL_WORDS.contains(&token)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::product_matching::normalize::normalize_title;
+    use crate::service::product_matching::score::{is_same_model, similarity};
+
+    #[test]
+    fn it_scores_same_models_with_one() {
+        let score = similarity(
+            &normalize_title("Xiaomi Redmi Note 10 Pro"),
+            &normalize_title("Redmi Note 10 Pro 6/128GB"),
+        );
+
+        assert!((score - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn it_scores_shared_tokens() {
+        let score = similarity(
+            &normalize_title("Redmi Note 10"),
+            &normalize_title("Redmi Note 10 Pro"),
+        );
+
+        assert!((score - 0.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn it_does_not_match_different_brands() {
+        let score = similarity(
+            &normalize_title("Samsung Watch 3"),
+            &normalize_title("Huawei Watch 3"),
+        );
+
+        assert!(score.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn it_does_not_match_titles_without_brand() {
+        let score = similarity(
+            &normalize_title("Watch 3 Pro"),
+            &normalize_title("Huawei Watch 3 Pro"),
+        );
+
+        assert!(score.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn it_tells_apart_models_of_the_same_line() {
+        assert!(is_same_model(
+            &normalize_title("Xiaomi Redmi Note 10 Pro"),
+            &normalize_title("Redmi Note 10 Pro 6/128GB"),
+        ));
+        assert!(!is_same_model(
+            &normalize_title("Samsung Galaxy S21"),
+            &normalize_title("Samsung Galaxy S21+"),
+        ));
+        assert!(!is_same_model(
+            &normalize_title("Redmi 9"),
+            &normalize_title("Redmi 9A"),
+        ));
+    }
+}
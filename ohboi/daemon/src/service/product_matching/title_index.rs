@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use crate::service::product_matching::normalize::{normalize_title, NormalizedTitle};
+
+/// Normalized titles of the category products with their positions by token
+#[derive(Debug, Default)]
+pub struct TitleIndex {
+    titles: Vec<(i32, NormalizedTitle)>,
+    by_token: HashMap<String, Vec<usize>>,
+}
+
+impl TitleIndex {
+    pub fn new(products: Vec<(i32, String)>) -> Self {
+        let mut index = TitleIndex::default();
+        for (product_id, title) in products {
+            index.add(product_id, &title);
+        }
+
+        index
+    }
+
+    pub fn add(&mut self, product_id: i32, title: &str) {
+        let normalized = normalize_title(title);
+        let position = self.titles.len();
+
+        for token in &normalized.tokens {
+            self.by_token
+                .entry(token.clone())
+                .or_default()
+                .push(position);
+        }
+        self.titles.push((product_id, normalized));
+    }
+
+    /// Titles which share at least one token with the given one, the rest are not similar at all
+    pub fn get_sharing_tokens(&self, title: &NormalizedTitle) -> Vec<&(i32, NormalizedTitle)> {
+        let mut positions: Vec<usize> = title
+            .tokens
+            .iter()
+            .filter_map(|token| self.by_token.get(token))
+            .flatten()
+            .copied()
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+
+        positions
+            .into_iter()
+            .map(|position| &self.titles[position])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::product_matching::normalize::normalize_title;
+    use crate::service::product_matching::title_index::TitleIndex;
+
+    #[test]
+    fn it_finds_only_titles_sharing_tokens() {
+        let mut index = TitleIndex::new(vec![
+            (1, "Redmi Note 10".to_string()),
+            (2, "Galaxy Watch 3".to_string()),
+        ]);
+        index.add(3, "Redmi 9A");
+
+        let product_ids: Vec<i32> = index
+            .get_sharing_tokens(&normalize_title("Xiaomi Redmi Note 10 Pro"))
+            .into_iter()
+            .map(|(product_id, _)| *product_id)
+            .collect();
+
+        assert_eq!(product_ids, vec![1, 3]);
+    }
+}
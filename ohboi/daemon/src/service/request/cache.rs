@@ -0,0 +1,121 @@
+use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
+use crate::db::entity::http_cache::HttpCache;
+
+/// Validators of the page version, they are sent back to the site to find out if the page has been changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheValidators {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+pub enum Fetched<T> {
+    Modified { body: T, validators: CacheValidators },
+    /// Page is the same as it was when validators were saved, so there is nothing to parse
+    NotModified,
+}
+
+impl<T> Fetched<T> {
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Fetched<U> {
+        match self {
+            Fetched::Modified { body, validators } => Fetched::Modified {
+                body: f(body),
+                validators,
+            },
+            Fetched::NotModified => Fetched::NotModified,
+        }
+    }
+}
+
+impl CacheValidators {
+    pub fn from_response_headers(url: &str, headers: &HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(String::from)
+        };
+
+        CacheValidators {
+            url: url.to_string(),
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+
+    pub fn to_request_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let values = [
+            (IF_NONE_MATCH, &self.etag),
+            (IF_MODIFIED_SINCE, &self.last_modified),
+        ];
+
+        for (name, value) in &values {
+            if let Some(value) = value.as_ref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(name.clone(), value);
+            }
+        }
+
+        headers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+impl From<HttpCache> for CacheValidators {
+    fn from(cache: HttpCache) -> Self {
+        CacheValidators {
+            url: cache.url,
+            etag: cache.etag,
+            last_modified: cache.last_modified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
+    use crate::service::request::cache::CacheValidators;
+
+    #[test]
+    fn it_sends_back_received_validators() {
+        let mut response_headers = HeaderMap::new();
+        response_headers.insert(ETAG, HeaderValue::from_static("W/\"5e15153d\""));
+        response_headers.insert(LAST_MODIFIED, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+
+        let validators = CacheValidators::from_response_headers("https://mi-shop.com/", &response_headers);
+        let request_headers = validators.to_request_headers();
+
+        assert_eq!(request_headers.get(IF_NONE_MATCH).unwrap(), "W/\"5e15153d\"");
+        assert_eq!(
+            request_headers.get(IF_MODIFIED_SINCE).unwrap(),
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+    }
+
+    #[test]
+    fn it_sends_only_present_validators() {
+        let validators = CacheValidators {
+            url: "https://mi-shop.com/".to_string(),
+            etag: None,
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+        };
+        let request_headers = validators.to_request_headers();
+
+        assert!(!validators.is_empty());
+        assert!(request_headers.get(IF_NONE_MATCH).is_none());
+        assert_eq!(request_headers.len(), 1);
+    }
+
+    #[test]
+    fn it_is_empty_without_validators() {
+        let validators = CacheValidators::from_response_headers("https://mi-shop.com/", &HeaderMap::new());
+
+        assert!(validators.is_empty());
+        assert!(validators.to_request_headers().is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+use bytes::Bytes;
+use futures::{stream, Stream};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Response;
+
+use crate::service::request::error::RequestError;
+
+/// Response which body is read chunk by chunk instead of being loaded at once
+pub struct Download {
+    response: Response,
+}
+
+impl Download {
+    pub(super) fn new(response: Response) -> Self {
+        Download { response }
+    }
+
+    pub fn content_type(&self) -> Option<String> {
+        self.response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from)
+    }
+
+    /// `Content-Length` announced by the site, the body itself is not checked against it
+    pub fn content_length(&self) -> Option<u64> {
+        self.response.content_length()
+    }
+
+    pub fn into_chunks(self) -> impl Stream<Item = Result<Bytes, RequestError>> {
+        stream::unfold(Some(self.response), |response| async move {
+            let mut response = response?;
+
+            match response.chunk().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(response))),
+                Ok(None) => None,
+                // Nothing is read after the failure
+                Err(e) => Some((Err(RequestError::from(e)), None)),
+            }
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+
+#[derive(Debug)]
+pub enum RequestError {
+    /// Url is disallowed for us by robots.txt of the site
+    Disallowed(String),
+    /// Site answered with a status which won't change on retry (404, 410)
+    Permanent { url: String, status: StatusCode },
+    /// Connection problems, timeouts, bans, other 4xx and 5xx which are left after all the retries
+    Transient(String),
+}
+
+impl RequestError {
+    /// Permanent errors shouldn't be postponed to the queue, the next attempt will fail the same way
+    pub fn is_permanent(&self) -> bool {
+        !matches!(self, RequestError::Transient(_))
+    }
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(error: reqwest::Error) -> Self {
+        RequestError::Transient(error.to_string())
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::Disallowed(url) => write!(f, "{} is disallowed by robots.txt", url),
+            RequestError::Permanent { url, status } => write!(f, "{} answered with {}", url, status),
+            RequestError::Transient(error) => write!(f, "{}", error),
+        }
+    }
+}
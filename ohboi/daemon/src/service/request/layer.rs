@@ -1,24 +1,221 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use futures::lock::Mutex as AsyncMutex;
 use rand::distributions::Uniform;
 use rand::prelude::Distribution;
-use reqwest::header::{ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, CONNECTION, REFERER, USER_AGENT};
-use reqwest::Response;
+use reqwest::header::{HeaderMap, ACCEPT, ACCEPT_LANGUAGE, REFERER, RETRY_AFTER, USER_AGENT};
+use reqwest::{Client, Response, Url};
+
+use crate::service::request::error::RequestError;
+use crate::service::request::proxy::{pick_proxy, report_proxy_outcome, ProxyOutcome};
+use crate::service::request::rate_limit::{
+    acquire, get_host, get_host_budget, get_other_host_budget, RequestBudget,
+};
+use crate::service::request::retry::{
+    backoff_delay, is_permanent_status, is_transient_status, parse_retry_after,
+};
+use crate::service::request::robots::{
+    parse_robots, Robots, CRAWLER_USER_AGENT, ROBOTS_USER_AGENT,
+};
+use crate::SETTINGS;
+
+const ROBOTS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+lazy_static! {
+    static ref ROBOTS_CACHE: Mutex<HashMap<String, (Instant, Arc<Robots>)>> = Mutex::new(HashMap::new());
+    /// Only one request of the host fetches its robots.txt, the others wait for it
+    static ref ROBOTS_FETCHES: Mutex<HashMap<String, Arc<AsyncMutex<()>>>> = Mutex::new(HashMap::new());
+    static ref CLIENTS: Mutex<HashMap<(String, Option<String>), Client>> = Mutex::new(HashMap::new());
+}
+
+/// Requests to the crawled sites are checked by their robots.txt, limited by their budget
+/// and sent with `CRAWLER_USER_AGENT`, other hosts are limited by the common budget.
+/// Transient failures are retried with backoff, so the caller gets the final result.
+pub async fn get_request(url: &str, headers: &HeaderMap) -> Result<Response, RequestError> {
+    let limit = match (get_host(url), Url::parse(url)) {
+        (Some(host), Ok(parsed_url)) => match get_host_budget(&host) {
+            Some(budget) => {
+                let robots = get_robots(&parsed_url, &host, budget).await;
+
+                if !robots.is_allowed(&get_path_with_query(&parsed_url)) {
+                    return Err(RequestError::Disallowed(url.to_string()));
+                }
+
+                let budget = budget.limited_by_crawl_delay(robots.crawl_delay);
+                Some((host, budget, Some(CRAWLER_USER_AGENT)))
+            }
+            None => Some((host, get_other_host_budget(), None)),
+        },
+        _ => None,
+    };
+
+    let settings = &SETTINGS.request;
+    let mut attempt = 1;
+
+    loop {
+        if let Some((host, budget, _)) = &limit {
+            acquire(host, *budget).await;
+        }
+
+        let user_agent = limit.as_ref().and_then(|(_, _, user_agent)| *user_agent);
+        let (error, retry_after) = match send_request(url, headers, user_agent).await {
+            Ok(response) if is_transient_status(response.status()) => {
+                let retry_after = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| parse_retry_after(value, Utc::now()));
+                let error = RequestError::Transient(format!("{} answered with {}", url, response.status()));
+
+                (error, retry_after)
+            }
+            Ok(response) if is_permanent_status(response.status()) => {
+                return Err(RequestError::Permanent {
+                    url: url.to_string(),
+                    status: response.status(),
+                });
+            }
+            Ok(response) => return Ok(response),
+            Err(e) => (RequestError::from(e), None),
+        };
+
+        if attempt >= settings.max_attempts {
+            return Err(error);
+        }
+
+        let delay = match retry_after {
+            Some(delay) if delay > Duration::from_secs(settings.max_retry_after_seconds) => return Err(error),
+            Some(delay) => delay,
+            None => backoff_delay(
+                attempt,
+                Duration::from_millis(settings.backoff_base_milliseconds),
+                rand::random(),
+            ),
+        };
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+async fn get_robots(url: &Url, host: &str, budget: RequestBudget) -> Arc<Robots> {
+    if let Some(robots) = get_cached_robots(host) {
+        return robots;
+    }
+
+    let fetch = ROBOTS_FETCHES
+        .lock()
+        .unwrap()
+        .entry(host.to_string())
+        .or_default()
+        .clone();
+    let _fetching = fetch.lock().await;
 
-pub async fn get_request(url: &str) -> Result<Response, reqwest::Error> {
-    // TODO preconnect, zip header
-    // TODO proxy
+    // Robots.txt could be fetched while waiting for the lock
+    if let Some(robots) = get_cached_robots(host) {
+        return robots;
+    }
 
-    let client = reqwest::Client::builder().build().unwrap();
+    acquire(host, budget).await;
+
+    // Missing or broken robots.txt doesn't restrict anything
+    let robots = match url.join("/robots.txt") {
+        Ok(robots_url) => {
+            match send_request(robots_url.as_str(), &HeaderMap::new(), Some(CRAWLER_USER_AGENT)).await {
+                Ok(response) if response.status().is_success() => response
+                    .text()
+                    .await
+                    .map_or_else(|_| Robots::allow_all(), |text| parse_robots(&text, ROBOTS_USER_AGENT)),
+                _ => Robots::allow_all(),
+            }
+        }
+        Err(_) => Robots::allow_all(),
+    };
+    let robots = Arc::new(robots);
+
+    ROBOTS_CACHE
+        .lock()
+        .unwrap()
+        .insert(host.to_string(), (Instant::now(), robots.clone()));
+
+    robots
+}
+
+fn get_cached_robots(host: &str) -> Option<Arc<Robots>> {
+    ROBOTS_CACHE
+        .lock()
+        .unwrap()
+        .get(host)
+        .filter(|(fetched_at, _)| fetched_at.elapsed() < ROBOTS_TTL)
+        .map(|(_, robots)| robots.clone())
+}
+
+fn get_path_with_query(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+/// Random browser user agent is sent when `user_agent` is not given
+async fn send_request(
+    url: &str,
+    headers: &HeaderMap,
+    user_agent: Option<&str>,
+) -> Result<Response, reqwest::Error> {
+    let host = get_host(url);
+    let proxy = host.as_deref().and_then(pick_proxy);
+    let client = get_client(host.unwrap_or_default(), proxy.clone())?;
 
     let req = client
         .get(url)
-        .header(USER_AGENT, get_random_user_agent())
+        .header(
+            USER_AGENT,
+            user_agent.map_or_else(get_random_user_agent, ToString::to_string),
+        )
         .header(REFERER, get_random_referer())
         .header(ACCEPT_LANGUAGE, "en-gb")
-        .header(ACCEPT_ENCODING, "*")
         .header(ACCEPT, "*/*")
-        .header(CONNECTION, "keep-alive");
+        .headers(headers.clone());
+
+    let started_at = Instant::now();
+    let response = req.send().await;
+
+    if let Some(proxy) = &proxy {
+        let outcome = match &response {
+            Ok(response) => ProxyOutcome::from_status(response.status(), started_at.elapsed()),
+            Err(_) => ProxyOutcome::Failure,
+        };
+        report_proxy_outcome(proxy, outcome);
+    }
+
+    response
+}
+
+/// Clients keep connections alive, so they are shared by all the requests to the host through the same proxy.
+/// Compressed responses (gzip, brotli) are decoded by the client.
+fn get_client(host: String, proxy: Option<String>) -> Result<Client, reqwest::Error> {
+    let mut clients = CLIENTS.lock().unwrap();
+    let key = (host, proxy);
+
+    if let Some(client) = clients.get(&key) {
+        return Ok(client.clone());
+    }
+
+    let mut client_builder = Client::builder()
+        .timeout(Duration::from_secs(SETTINGS.request.timeout_seconds))
+        .tcp_keepalive(Duration::from_secs(60))
+        .pool_idle_timeout(Duration::from_secs(90));
+    if let Some(proxy) = &key.1 {
+        client_builder = client_builder.proxy(reqwest::Proxy::all(proxy.as_str())?);
+    }
+    let client = client_builder.build()?;
+
+    clients.insert(key, client.clone());
 
-    req.send().await
+    Ok(client)
 }
 
 static REFERER_LIST: &str = include_str!("./cache/referrers");
@@ -1,4 +1,15 @@
+pub use self::cache::{CacheValidators, Fetched};
+pub use self::download::Download;
+pub use self::error::RequestError;
 pub use self::pub_api::*;
+pub use self::rate_limit::RequestBudget;
 
+mod cache;
+mod download;
+mod error;
 mod layer;
+mod proxy;
 mod pub_api;
+mod rate_limit;
+mod retry;
+mod robots;
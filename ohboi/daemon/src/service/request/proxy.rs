@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+
+use crate::settings::{Proxy, ProxyRotation};
+use crate::SETTINGS;
+
+lazy_static! {
+    static ref PROXY_POOL: Mutex<ProxyPool> = Mutex::new(ProxyPool::new(&SETTINGS.proxy, Instant::now()));
+}
+
+/// Proxy for the next request to the host, `None` means direct connection
+pub fn pick_proxy(host: &str) -> Option<String> {
+    PROXY_POOL.lock().unwrap().pick(host, Instant::now())
+}
+
+pub fn report_proxy_outcome(proxy: &str, outcome: ProxyOutcome) {
+    let now = Instant::now();
+    let mut pool = PROXY_POOL.lock().unwrap();
+
+    pool.report(proxy, outcome, now);
+
+    if let Some(stats) = pool.take_stats(now) {
+        log::info!("{}", stats);
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ProxyOutcome {
+    Success(Duration),
+    /// Connection error or timeout
+    Failure,
+    /// Site has recognized the proxy and refuses to serve it
+    Banned,
+}
+
+impl ProxyOutcome {
+    pub fn from_status(status: StatusCode, latency: Duration) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED
+            | StatusCode::FORBIDDEN
+            | StatusCode::PROXY_AUTHENTICATION_REQUIRED
+            | StatusCode::TOO_MANY_REQUESTS => ProxyOutcome::Banned,
+            _ => ProxyOutcome::Success(latency),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ProxyState {
+    url: String,
+    consecutive_failures: u32,
+    successes: u64,
+    failures: u64,
+    bans: u64,
+    /// Exponentially weighted average of the successful requests
+    latency: Option<Duration>,
+    evicted_until: Option<Instant>,
+}
+
+impl ProxyState {
+    fn is_available(&self, now: Instant) -> bool {
+        self.evicted_until.map_or(true, |until| until <= now)
+    }
+}
+
+#[derive(Debug)]
+pub struct ProxyPool {
+    proxies: Vec<ProxyState>,
+    rotation: ProxyRotation,
+    max_failures: u32,
+    eviction: Duration,
+    stats_interval: Duration,
+    next: usize,
+    /// Host to proxy index, used by the per source rotation
+    assignments: HashMap<String, usize>,
+    stats_logged_at: Instant,
+}
+
+impl ProxyPool {
+    pub fn new(settings: &Proxy, now: Instant) -> Self {
+        ProxyPool {
+            proxies: settings
+                .urls
+                .iter()
+                .map(|url| ProxyState {
+                    url: url.clone(),
+                    consecutive_failures: 0,
+                    successes: 0,
+                    failures: 0,
+                    bans: 0,
+                    latency: None,
+                    evicted_until: None,
+                })
+                .collect(),
+            rotation: settings.rotation,
+            max_failures: settings.max_failures.max(1),
+            eviction: Duration::from_secs(settings.eviction_seconds),
+            stats_interval: Duration::from_secs(settings.stats_interval_seconds),
+            next: 0,
+            assignments: HashMap::new(),
+            stats_logged_at: now,
+        }
+    }
+
+    pub fn pick(&mut self, host: &str, now: Instant) -> Option<String> {
+        if self.proxies.is_empty() {
+            return None;
+        }
+
+        let index = match self.rotation {
+            ProxyRotation::PerRequest => self.next_available(now),
+            ProxyRotation::PerSource => match self.assignments.get(host) {
+                Some(&index) if self.proxies[index].is_available(now) => index,
+                _ => {
+                    let index = self.next_available(now);
+                    self.assignments.insert(host.to_string(), index);
+
+                    index
+                }
+            },
+        };
+
+        Some(self.proxies[index].url.clone())
+    }
+
+    pub fn report(&mut self, url: &str, outcome: ProxyOutcome, now: Instant) {
+        let max_failures = self.max_failures;
+        let eviction = self.eviction;
+        let proxy = match self.proxies.iter_mut().find(|proxy| proxy.url == url) {
+            Some(proxy) => proxy,
+            None => return,
+        };
+
+        match outcome {
+            ProxyOutcome::Success(latency) => {
+                proxy.successes += 1;
+                proxy.consecutive_failures = 0;
+                proxy.latency = Some(match proxy.latency {
+                    Some(average) => (average * 4 + latency) / 5,
+                    None => latency,
+                });
+            }
+            ProxyOutcome::Failure | ProxyOutcome::Banned => {
+                if outcome == ProxyOutcome::Banned {
+                    proxy.bans += 1;
+                    // Ban won't go away on the next request
+                    proxy.consecutive_failures = max_failures;
+                } else {
+                    proxy.failures += 1;
+                    proxy.consecutive_failures += 1;
+                }
+
+                if proxy.consecutive_failures >= max_failures {
+                    proxy.consecutive_failures = 0;
+                    proxy.evicted_until = Some(now + eviction);
+                    log::warn!(
+                        "Proxy {} is evicted for {}s after {:?}",
+                        proxy.url,
+                        eviction.as_secs(),
+                        outcome
+                    );
+                }
+            }
+        }
+    }
+
+    /// Stats of every proxy, once per stats interval
+    pub fn take_stats(&mut self, now: Instant) -> Option<String> {
+        if self.proxies.is_empty() || now.saturating_duration_since(self.stats_logged_at) < self.stats_interval {
+            return None;
+        }
+        self.stats_logged_at = now;
+
+        let mut stats = String::from("Proxy pool:");
+        for proxy in &self.proxies {
+            let _ = write!(
+                stats,
+                "\n  {url}: {state}, {successes} ok, {failures} failed, {bans} banned, latency {latency}",
+                url = proxy.url,
+                state = if proxy.is_available(now) { "available" } else { "evicted" },
+                successes = proxy.successes,
+                failures = proxy.failures,
+                bans = proxy.bans,
+                latency = proxy
+                    .latency
+                    .map_or_else(|| "unknown".to_string(), |latency| format!("{}ms", latency.as_millis())),
+            );
+        }
+
+        Some(stats)
+    }
+
+    /// Round robin through the available proxies. If all of them are evicted, the one which returns first is used,
+    /// direct connection would expose our own address.
+    fn next_available(&mut self, now: Instant) -> usize {
+        let amount = self.proxies.len();
+        let index = (0..amount)
+            .map(|offset| (self.next + offset) % amount)
+            .find(|&index| self.proxies[index].is_available(now))
+            .unwrap_or_else(|| {
+                (0..amount)
+                    .min_by_key(|&index| self.proxies[index].evicted_until)
+                    .unwrap_or(0)
+            });
+        self.next = (index + 1) % amount;
+
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use reqwest::StatusCode;
+
+    use crate::service::request::proxy::{ProxyOutcome, ProxyPool};
+    use crate::settings::{Proxy, ProxyRotation};
+
+    fn settings(urls: &[&str], rotation: ProxyRotation) -> Proxy {
+        Proxy {
+            urls: urls.iter().map(|url| (*url).to_string()).collect(),
+            rotation,
+            max_failures: 2,
+            eviction_seconds: 60,
+            stats_interval_seconds: 60,
+        }
+    }
+
+    #[test]
+    fn it_connects_directly_without_proxies() {
+        let now = Instant::now();
+        let mut pool = ProxyPool::new(&settings(&[], ProxyRotation::PerRequest), now);
+
+        assert_eq!(pool.pick("mi-shop.com", now), None);
+        assert_eq!(pool.take_stats(now + Duration::from_secs(120)), None);
+    }
+
+    #[test]
+    fn it_rotates_per_request() {
+        let now = Instant::now();
+        let mut pool = ProxyPool::new(&settings(&["http://a", "http://b"], ProxyRotation::PerRequest), now);
+
+        assert_eq!(pool.pick("mi-shop.com", now).unwrap(), "http://a");
+        assert_eq!(pool.pick("mi-shop.com", now).unwrap(), "http://b");
+        assert_eq!(pool.pick("samsungshop.com.ua", now).unwrap(), "http://a");
+    }
+
+    #[test]
+    fn it_sticks_to_proxy_per_source() {
+        let now = Instant::now();
+        let mut pool = ProxyPool::new(&settings(&["http://a", "http://b"], ProxyRotation::PerSource), now);
+
+        assert_eq!(pool.pick("mi-shop.com", now).unwrap(), "http://a");
+        assert_eq!(pool.pick("samsungshop.com.ua", now).unwrap(), "http://b");
+        assert_eq!(pool.pick("mi-shop.com", now).unwrap(), "http://a");
+
+        pool.report("http://a", ProxyOutcome::Banned, now);
+        assert_eq!(pool.pick("mi-shop.com", now).unwrap(), "http://b");
+    }
+
+    #[test]
+    fn it_evicts_after_consecutive_failures_only() {
+        let now = Instant::now();
+        let mut pool = ProxyPool::new(&settings(&["http://a", "http://b"], ProxyRotation::PerRequest), now);
+
+        pool.report("http://a", ProxyOutcome::Failure, now);
+        pool.report("http://a", ProxyOutcome::Success(Duration::from_millis(100)), now);
+        pool.report("http://a", ProxyOutcome::Failure, now);
+        assert_eq!(pool.pick("mi-shop.com", now).unwrap(), "http://a");
+
+        pool.report("http://a", ProxyOutcome::Failure, now);
+        assert_eq!(pool.pick("mi-shop.com", now).unwrap(), "http://b");
+        assert_eq!(pool.pick("mi-shop.com", now).unwrap(), "http://b");
+
+        let later = now + Duration::from_secs(61);
+        assert_eq!(pool.pick("mi-shop.com", later).unwrap(), "http://a");
+    }
+
+    #[test]
+    fn it_uses_proxy_which_returns_first_when_all_are_evicted() {
+        let now = Instant::now();
+        let mut pool = ProxyPool::new(&settings(&["http://a", "http://b"], ProxyRotation::PerRequest), now);
+
+        pool.report("http://b", ProxyOutcome::Banned, now);
+        pool.report("http://a", ProxyOutcome::Banned, now + Duration::from_secs(1));
+
+        assert_eq!(pool.pick("mi-shop.com", now).unwrap(), "http://b");
+    }
+
+    #[test]
+    fn it_reports_stats_once_per_interval() {
+        let now = Instant::now();
+        let mut pool = ProxyPool::new(&settings(&["http://a"], ProxyRotation::PerRequest), now);
+        pool.report("http://a", ProxyOutcome::Success(Duration::from_millis(100)), now);
+
+        assert_eq!(pool.take_stats(now), None);
+
+        let stats = pool.take_stats(now + Duration::from_secs(60)).unwrap();
+        assert!(stats.contains("http://a: available, 1 ok, 0 failed, 0 banned, latency 100ms"));
+        assert_eq!(pool.take_stats(now + Duration::from_secs(61)), None);
+    }
+
+    /// Http proxy which answers every request itself with the given status
+    fn start_stand_in_proxy(status: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buffer = [0; 4096];
+                let _ = stream.read(&mut buffer);
+                let body = address.to_string();
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        format!("http://{}", address)
+    }
+
+    async fn request_through(proxy: &str) -> (StatusCode, String) {
+        let client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all(proxy).unwrap())
+            .build()
+            .unwrap();
+        let response = client.get("http://shop.test/catalog").send().await.unwrap();
+        let status = response.status();
+
+        (status, response.text().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn it_evicts_banned_stand_in_proxy() {
+        let healthy = start_stand_in_proxy("200 OK");
+        let banned = start_stand_in_proxy("403 Forbidden");
+        let now = Instant::now();
+        let mut pool = ProxyPool::new(
+            &settings(&[banned.as_str(), healthy.as_str()], ProxyRotation::PerRequest),
+            now,
+        );
+
+        let mut used = vec![];
+        for _ in 0..4 {
+            let proxy = pool.pick("shop.test", now).unwrap();
+            let started_at = Instant::now();
+            let (status, served_by) = request_through(&proxy).await;
+
+            assert_eq!(format!("http://{}", served_by), proxy);
+            pool.report(&proxy, ProxyOutcome::from_status(status, started_at.elapsed()), now);
+            used.push(proxy);
+        }
+
+        assert_eq!(used, vec![banned, healthy.clone(), healthy.clone(), healthy]);
+    }
+}
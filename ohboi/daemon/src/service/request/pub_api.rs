@@ -1,20 +1,61 @@
-use crate::service::request::layer::get_request;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
 
-// TODO remove
-pub async fn get_s(url: String) -> Result<String, reqwest::Error> {
-    get(&url).await
-}
+use crate::db::repository::http_cache;
+use crate::service::request::cache::{CacheValidators, Fetched};
+use crate::service::request::download::Download;
+use crate::service::request::error::RequestError;
+use crate::service::request::layer::get_request;
 
-pub async fn get(url: &str) -> Result<String, reqwest::Error> {
-    let response = get_request(url).await?;
+pub async fn get(url: &str) -> Result<String, RequestError> {
+    let response = get_request(url, &HeaderMap::new()).await?;
 
     let text = response.text().await?;
 
     Ok(text)
 }
 
-pub async fn get_bytes(url: &str) -> Result<Vec<u8>, reqwest::Error> {
-    let response = get_request(url).await?;
+pub async fn get_bytes(url: &str) -> Result<Vec<u8>, RequestError> {
+    let response = get_request(url, &HeaderMap::new()).await?;
 
     Ok(response.bytes().await?.to_vec())
 }
+
+/// Body of the response is not read yet, so big files don't have to fit in memory
+pub async fn get_stream(url: &str) -> Result<Download, RequestError> {
+    let response = get_request(url, &HeaderMap::new()).await?;
+
+    Ok(Download::new(response))
+}
+
+/// Conditional request with the validators saved for the url.
+/// Validators of the modified page have to be saved by the caller once the page is processed successfully,
+/// otherwise a failed page would be skipped as not modified the next time.
+pub async fn get_if_modified(url: &str) -> Result<Fetched<String>, RequestError> {
+    let headers = http_cache::get_by_url(url)
+        .map(|cache| CacheValidators::from(cache).to_request_headers())
+        .unwrap_or_default();
+
+    let response = get_request(url, &headers).await?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(Fetched::NotModified);
+    }
+
+    let validators = CacheValidators::from_response_headers(url, response.headers());
+    let body = response.text().await?;
+
+    Ok(Fetched::Modified { body, validators })
+}
+
+pub fn save_cache_validators(validators: &CacheValidators) {
+    if validators.is_empty() {
+        return;
+    }
+
+    http_cache::create_or_update(
+        &validators.url,
+        validators.etag.as_deref(),
+        validators.last_modified.as_deref(),
+    );
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::parse::crawler::get_all_crawlers;
+use crate::SETTINGS;
+
+/// How many requests per second a site can receive from us
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RequestBudget {
+    pub requests_per_second: f64,
+    /// Amount of requests which can be sent at once after a quiet period
+    pub burst: f64,
+}
+
+impl Default for RequestBudget {
+    fn default() -> Self {
+        RequestBudget {
+            requests_per_second: 2.0,
+            burst: 4.0,
+        }
+    }
+}
+
+impl RequestBudget {
+    /// Crawl-delay of robots.txt can only make the budget stricter
+    pub fn limited_by_crawl_delay(self, crawl_delay: Option<f64>) -> Self {
+        match crawl_delay {
+            Some(delay) if 1.0 / delay < self.requests_per_second => RequestBudget {
+                requests_per_second: 1.0 / delay,
+                burst: 1.0,
+            },
+            _ => self,
+        }
+    }
+}
+
+lazy_static! {
+    static ref HOST_BUDGETS: HashMap<String, RequestBudget> = get_all_crawlers()
+        .into_iter()
+        .filter_map(|crawler| {
+            get_host(&crawler.get_site_base()).map(|host| (host, crawler.get_request_budget()))
+        })
+        .collect();
+    static ref BUCKETS: Mutex<HashMap<String, TokenBucket>> = Mutex::new(HashMap::new());
+}
+
+/// Budget of the site we crawl
+pub fn get_host_budget(host: &str) -> Option<RequestBudget> {
+    HOST_BUDGETS.get(&normalize_host(host)).copied()
+}
+
+/// Hosts which are not crawled (image CDNs, api) go through the same proxies, so they are limited too
+pub fn get_other_host_budget() -> RequestBudget {
+    RequestBudget {
+        requests_per_second: SETTINGS.request.other_hosts_requests_per_second,
+        burst: SETTINGS.request.other_hosts_burst,
+    }
+}
+
+/// Reserves a request to the host and waits until it can be sent
+pub async fn acquire(host: &str, budget: RequestBudget) {
+    let wait = BUCKETS
+        .lock()
+        .unwrap()
+        .entry(normalize_host(host))
+        .or_insert_with(|| TokenBucket::new(budget, Instant::now()))
+        .reserve(budget, Instant::now());
+
+    if wait > Duration::from_secs(0) {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+pub fn get_host(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(normalize_host))
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim_start_matches("www.").to_lowercase()
+}
+
+/// Token bucket with reservations: tokens can go below zero, so every caller knows its own wait time and requests
+/// are sent in the order they were reserved.
+#[derive(Debug)]
+pub struct TokenBucket {
+    budget: RequestBudget,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(budget: RequestBudget, now: Instant) -> Self {
+        TokenBucket {
+            budget,
+            tokens: budget.burst,
+            refilled_at: now,
+        }
+    }
+
+    /// Takes a token and returns how long the request has to wait
+    pub fn reserve(&mut self, budget: RequestBudget, now: Instant) -> Duration {
+        self.refill(now);
+
+        if budget != self.budget {
+            self.budget = budget;
+            self.tokens = self.tokens.min(budget.burst);
+        }
+
+        self.tokens -= 1.0;
+
+        if self.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.budget.requests_per_second)
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * self.budget.requests_per_second).min(self.budget.burst);
+        self.refilled_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::service::request::rate_limit::{get_host, RequestBudget, TokenBucket};
+
+    const BUDGET: RequestBudget = RequestBudget {
+        requests_per_second: 2.0,
+        burst: 2.0,
+    };
+
+    #[test]
+    fn it_lets_burst_through_without_waiting() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(BUDGET, now);
+
+        assert_eq!(bucket.reserve(BUDGET, now), Duration::from_secs(0));
+        assert_eq!(bucket.reserve(BUDGET, now), Duration::from_secs(0));
+        assert_eq!(bucket.reserve(BUDGET, now), Duration::from_millis(500));
+        assert_eq!(bucket.reserve(BUDGET, now), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn it_refills_tokens_over_time() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(BUDGET, now);
+        bucket.reserve(BUDGET, now);
+        bucket.reserve(BUDGET, now);
+
+        let later = now + Duration::from_millis(500);
+        assert_eq!(bucket.reserve(BUDGET, later), Duration::from_secs(0));
+        assert_eq!(bucket.reserve(BUDGET, later), Duration::from_millis(500));
+
+        let much_later = later + Duration::from_secs(60);
+        assert_eq!(bucket.reserve(BUDGET, much_later), Duration::from_secs(0));
+        assert_eq!(bucket.reserve(BUDGET, much_later), Duration::from_secs(0));
+        assert_eq!(bucket.reserve(BUDGET, much_later), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn it_applies_crawl_delay() {
+        let delayed = BUDGET.limited_by_crawl_delay(Some(4.0));
+        assert_eq!(
+            delayed,
+            RequestBudget {
+                requests_per_second: 0.25,
+                burst: 1.0
+            }
+        );
+
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(BUDGET, now);
+        assert_eq!(bucket.reserve(delayed, now), Duration::from_secs(0));
+        assert_eq!(bucket.reserve(delayed, now), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn it_ignores_crawl_delay_looser_than_budget() {
+        assert_eq!(BUDGET.limited_by_crawl_delay(Some(0.1)), BUDGET);
+        assert_eq!(BUDGET.limited_by_crawl_delay(None), BUDGET);
+    }
+
+    #[test]
+    fn it_normalizes_hosts() {
+        assert_eq!(get_host("https://www.Mi-Shop.com/catalog/"), Some("mi-shop.com".to_string()));
+        assert_eq!(get_host("not a url"), None);
+    }
+}
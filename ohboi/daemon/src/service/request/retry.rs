@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+
+/// Statuses which may go away if the request is repeated later, e.g. through another proxy after a ban (401, 403, 407)
+pub fn is_transient_status(status: StatusCode) -> bool {
+    (status.is_client_error() || status.is_server_error()) && !is_permanent_status(status)
+}
+
+/// The page is gone, the next attempt will fail the same way
+pub fn is_permanent_status(status: StatusCode) -> bool {
+    status == StatusCode::NOT_FOUND || status == StatusCode::GONE
+}
+
+/// `Retry-After` is either amount of seconds or http date
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| (date.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+/// Exponential backoff, `jitter` from 0 to 1 spreads the second half of the delay to not retry all at once
+pub fn backoff_delay(attempt: u32, base: Duration, jitter: f64) -> Duration {
+    let delay = base * 2_u32.saturating_pow(attempt.saturating_sub(1));
+
+    delay / 2 + (delay / 2).mul_f64(jitter.max(0.0).min(1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{TimeZone, Utc};
+    use reqwest::StatusCode;
+
+    use crate::service::request::retry::{
+        backoff_delay, is_permanent_status, is_transient_status, parse_retry_after,
+    };
+
+    #[test]
+    fn it_tells_transient_statuses() {
+        assert!(is_transient_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_transient_status(StatusCode::BAD_GATEWAY));
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_transient_status(StatusCode::NOT_FOUND));
+        assert!(!is_transient_status(StatusCode::GONE));
+        assert!(!is_transient_status(StatusCode::OK));
+    }
+
+    #[test]
+    fn it_retries_proxy_bans() {
+        assert!(is_transient_status(StatusCode::FORBIDDEN));
+        assert!(is_transient_status(StatusCode::UNAUTHORIZED));
+        assert!(is_transient_status(StatusCode::PROXY_AUTHENTICATION_REQUIRED));
+        assert!(!is_permanent_status(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn it_tells_permanent_statuses() {
+        assert!(is_permanent_status(StatusCode::NOT_FOUND));
+        assert!(is_permanent_status(StatusCode::GONE));
+        assert!(!is_permanent_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_permanent_status(StatusCode::OK));
+    }
+
+    #[test]
+    fn it_parses_retry_after_seconds() {
+        assert_eq!(parse_retry_after(" 120", Utc::now()), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn it_parses_retry_after_date() {
+        let now = Utc.ymd(2015, 10, 21).and_hms(7, 27, 30);
+
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::from_secs(0))
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn it_doubles_backoff_with_jitter() {
+        let base = Duration::from_millis(400);
+
+        assert_eq!(backoff_delay(1, base, 0.0), Duration::from_millis(200));
+        assert_eq!(backoff_delay(1, base, 1.0), Duration::from_millis(400));
+        assert_eq!(backoff_delay(3, base, 0.5), Duration::from_millis(1200));
+    }
+}
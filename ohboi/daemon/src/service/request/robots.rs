@@ -0,0 +1,196 @@
+use regex::Regex;
+
+/// Groups for this agent take precedence over the `*` ones
+pub static ROBOTS_USER_AGENT: &str = "ohboi";
+
+/// Requests which are checked by robots.txt introduce themselves as the agent the rules were picked for
+pub static CRAWLER_USER_AGENT: &str = "Mozilla/5.0 (compatible; ohboi/1.0)";
+
+/// Rules of robots.txt which apply to us
+#[derive(Debug, Default)]
+pub struct Robots {
+    rules: Vec<Rule>,
+    pub crawl_delay: Option<f64>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    allow: bool,
+    /// Length of the original pattern, the most specific (longest) matching rule wins
+    specificity: usize,
+    pattern: Regex,
+}
+
+#[derive(Default)]
+struct Group {
+    user_agents: Vec<String>,
+    rules: Vec<Rule>,
+    crawl_delay: Option<f64>,
+}
+
+impl Group {
+    fn applies_to(&self, user_agent: &str) -> bool {
+        self.user_agents.iter().any(|agent| agent == user_agent)
+    }
+}
+
+impl Robots {
+    /// Everything is allowed if robots.txt is missing
+    pub fn allow_all() -> Self {
+        Robots::default()
+    }
+
+    /// `path` is the path of the url together with the query
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| rule.pattern.is_match(path))
+            // allow wins in case of equally specific rules
+            .max_by_key(|rule| (rule.specificity, rule.allow))
+            .map_or(true, |rule| rule.allow)
+    }
+}
+
+pub fn parse_robots(content: &str, user_agent: &str) -> Robots {
+    let groups = parse_groups(content);
+    let user_agent = user_agent.to_lowercase();
+    let has_own_group = groups.iter().any(|group| group.applies_to(&user_agent));
+    let agent = if has_own_group { user_agent.as_str() } else { "*" };
+
+    let mut robots = Robots::allow_all();
+    for group in groups.into_iter().filter(|group| group.applies_to(agent)) {
+        robots.rules.extend(group.rules);
+        robots.crawl_delay = robots.crawl_delay.or(group.crawl_delay);
+    }
+
+    robots
+}
+
+fn parse_groups(content: &str) -> Vec<Group> {
+    let mut groups: Vec<Group> = vec![];
+    let mut current = Group::default();
+    let mut reading_agents = false;
+
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) => (key.trim().to_lowercase(), value.trim()),
+            None => continue,
+        };
+
+        match key.as_str() {
+            "user-agent" => {
+                if !reading_agents && !current.user_agents.is_empty() {
+                    groups.push(current);
+                    current = Group::default();
+                }
+                reading_agents = true;
+                current.user_agents.push(value.to_lowercase());
+            }
+            "allow" | "disallow" => {
+                reading_agents = false;
+                // Empty disallow means that everything is allowed
+                if !value.is_empty() {
+                    current.rules.push(Rule {
+                        allow: key == "allow",
+                        specificity: value.len(),
+                        pattern: compile_pattern(value),
+                    });
+                }
+            }
+            "crawl-delay" => {
+                reading_agents = false;
+                current.crawl_delay = value.parse().ok().filter(|delay: &f64| *delay > 0.0);
+            }
+            _ => {}
+        }
+    }
+
+    if !current.user_agents.is_empty() {
+        groups.push(current);
+    }
+
+    groups
+}
+
+/// Patterns are prefixes with `*` as any sequence and `$` as the end of the url
+fn compile_pattern(pattern: &str) -> Regex {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let regex = pattern
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<String>>()
+        .join(".*");
+
+    Regex::new(&format!("^{}{}", regex, if anchored { "$" } else { "" }))
+        .expect("Escaped robots.txt pattern is always valid regex")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::request::robots::{parse_robots, Robots};
+
+    static ROBOTS: &str = r"
+# comment
+User-agent: Googlebot
+Disallow: /
+
+User-agent: *
+Disallow: /cart
+Disallow: /*?sort=
+Disallow: /*.pdf$
+Allow: /cart/public
+Crawl-delay: 2.5
+";
+
+    #[test]
+    fn it_uses_common_group_when_there_is_no_own_one() {
+        let robots = parse_robots(ROBOTS, "ohboi");
+
+        assert!(robots.is_allowed("/catalog/phones"));
+        assert!(!robots.is_allowed("/cart/checkout"));
+        assert_eq!(robots.crawl_delay, Some(2.5));
+    }
+
+    #[test]
+    fn it_prefers_the_most_specific_rule() {
+        let robots = parse_robots(ROBOTS, "ohboi");
+
+        assert!(robots.is_allowed("/cart/public/list"));
+    }
+
+    #[test]
+    fn it_supports_wildcards_and_end_anchors() {
+        let robots = parse_robots(ROBOTS, "ohboi");
+
+        assert!(!robots.is_allowed("/catalog/phones?sort=price"));
+        assert!(!robots.is_allowed("/manuals/phone.pdf"));
+        assert!(robots.is_allowed("/manuals/phone.pdf.html"));
+    }
+
+    #[test]
+    fn it_uses_own_group_when_it_exists() {
+        let robots = parse_robots(ROBOTS, "googlebot");
+
+        assert!(!robots.is_allowed("/catalog/phones"));
+        assert_eq!(robots.crawl_delay, None);
+    }
+
+    #[test]
+    fn it_allows_everything_on_empty_disallow() {
+        let robots = parse_robots("User-agent: *\nDisallow:\n", "ohboi");
+
+        assert!(robots.is_allowed("/"));
+        assert!(Robots::allow_all().is_allowed("/cart"));
+    }
+
+    #[test]
+    fn it_groups_consecutive_user_agents() {
+        let robots = parse_robots("User-agent: ohboi\nUser-agent: other\nDisallow: /private\n", "ohboi");
+
+        assert!(!robots.is_allowed("/private/page"));
+    }
+}
@@ -0,0 +1,127 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+
+use lib::db::entity::unknown_characteristic::{
+    UnknownCharacteristicKind, UnknownCharacteristicSightings,
+};
+use lib::db::repository::unknown_characteristic::{
+    get_unknown_characteristics, save_sightings, KEPT_EXAMPLES,
+};
+
+use crate::db::entity::source::SourceName;
+use crate::db::repository::source::get_source;
+
+tokio::task_local! {
+    static INBOX: RefCell<HashMap<(UnknownCharacteristicKind, String), Sightings>>;
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Sightings {
+    occurrences: i32,
+    external_ids: Vec<String>,
+    sample_values: Vec<String>,
+}
+
+impl Sightings {
+    fn add(&mut self, external_id: &str, value: &str) {
+        self.occurrences += 1;
+        add_example(&mut self.external_ids, external_id);
+        add_example(&mut self.sample_values, value);
+    }
+}
+
+/// Runs the job and saves unknown characteristics reported while it was running to `unknown_characteristic`,
+/// one row per title with the occurrences summed
+pub async fn collect_unknown_characteristics<Job, T>(source: SourceName, job: Job) -> T
+where
+    Job: Future<Output = T>,
+{
+    let (result, inbox) = INBOX
+        .scope(RefCell::new(HashMap::new()), async {
+            let result = job.await;
+
+            (result, INBOX.with(RefCell::take))
+        })
+        .await;
+
+    if !inbox.is_empty() {
+        let source_id = get_source(source).id;
+        for ((kind, title), sightings) in inbox {
+            save_sightings(&UnknownCharacteristicSightings {
+                source_id,
+                kind,
+                title,
+                occurrences: sightings.occurrences,
+                external_ids: sightings.external_ids,
+                sample_values: sightings.sample_values,
+            });
+        }
+    }
+
+    result
+}
+
+/// Remembers the characteristic of the current job, does nothing outside of it (e.g. in tests)
+pub fn report_unknown_characteristic(
+    kind: UnknownCharacteristicKind,
+    title: &str,
+    value: &str,
+    external_id: &str,
+) {
+    let _ = INBOX.try_with(|inbox| {
+        inbox
+            .borrow_mut()
+            .entry((kind, title.to_string()))
+            .or_default()
+            .add(external_id, value.trim())
+    });
+}
+
+/// Prints the most frequent unknown characteristics, one per line
+pub fn print_unknown_characteristics(source_name: Option<&str>, limit: i64) {
+    for characteristic in get_unknown_characteristics(source_name, limit) {
+        println!(
+            "{occurrences} {source} {kind} \"{title}\" values={values:?} external_ids={external_ids:?} last_seen={last_seen_at}",
+            occurrences = characteristic.occurrences,
+            source = characteristic.source,
+            kind = characteristic.kind,
+            title = characteristic.title,
+            values = characteristic.sample_values,
+            external_ids = characteristic.external_ids,
+            last_seen_at = characteristic.last_seen_at.format("%Y-%m-%d %H:%M:%S"),
+        );
+    }
+}
+
+fn add_example(examples: &mut Vec<String>, example: &str) {
+    if examples.len() < KEPT_EXAMPLES && !examples.iter().any(|known| known == example) {
+        examples.push(example.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::unknown_characteristic::Sightings;
+
+    #[test]
+    fn it_keeps_few_distinct_examples() {
+        let mut sightings = Sightings::default();
+        for i in 0..10 {
+            sightings.add(&format!("product-{}", i), "5G");
+        }
+
+        assert_eq!(sightings.occurrences, 10);
+        assert_eq!(
+            sightings.external_ids,
+            vec![
+                "product-0",
+                "product-1",
+                "product-2",
+                "product-3",
+                "product-4"
+            ]
+        );
+        assert_eq!(sightings.sample_values, vec!["5G"]);
+    }
+}
@@ -31,6 +31,129 @@ pub struct QueueBroker {
 #[derive(Debug, Deserialize)]
 pub struct S3 {
     pub bucket: String,
+    pub region: String,
+    /// S3 compatible storage (e.g. MinIO) instead of AWS when set
+    pub endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Copy, Clone, PartialEq)]
+pub enum ImageStoreKind {
+    S3,
+    Fs,
+    /// Images are lost with the process, for tests and dry runs
+    Memory,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Images {
+    pub store: ImageStoreKind,
+    /// Root directory of the `fs` store
+    pub dir: String,
+    pub max_bytes: usize,
+    /// Smaller images are icons or placeholders rather than photos of the product
+    pub min_side: u32,
+    /// Bigger images are refused before decoding
+    pub max_side: u32,
+    /// Photos of the product with difference hashes which differ in at most this amount of bits are the same photo
+    pub dedupe_distance: u32,
+    /// Whole download of the image, including its body
+    pub download_timeout_seconds: u64,
+    pub stats_interval_seconds: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Crawlers {
+    pub specs_dir: String,
+    pub dictionaries_dir: String,
+}
+
+#[derive(Debug, Deserialize, Copy, Clone, PartialEq)]
+pub enum ProxyRotation {
+    PerRequest,
+    /// Every source sticks to its proxy until it is evicted
+    PerSource,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Proxy {
+    /// Requests go directly when empty
+    pub urls: Vec<String>,
+    pub rotation: ProxyRotation,
+    /// Consecutive failures after which proxy is evicted
+    pub max_failures: u32,
+    pub eviction_seconds: u64,
+    pub stats_interval_seconds: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub timeout_seconds: u64,
+    pub max_attempts: u32,
+    pub backoff_base_milliseconds: u64,
+    /// Longer `Retry-After` fails the request, so the job is postponed instead of blocking the consumer
+    pub max_retry_after_seconds: u64,
+    /// Budget of the hosts which are not crawled, e.g. image CDNs
+    pub other_hosts_requests_per_second: f64,
+    pub other_hosts_burst: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Matching {
+    /// Similarity of titles from 0 to 1 which is enough to link an offer to the product without review
+    pub auto_link_score: f64,
+    /// Less similar products are not even suggested for the review
+    pub review_score: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Delisting {
+    /// Run which has seen less than this share of the live offers of the category is treated as truncated
+    pub safety_ratio: f64,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Health {
+    /// Growth of a share of broken items or pages over the previous runs which raises the alert
+    pub ratio_increase: f64,
+    /// Growth of unknown characteristics per details page which raises the alert
+    pub unknown_characteristics_increase: f64,
+    /// Runs with less items or pages are not checked
+    pub min_items: i32,
+    /// Details and page jobs have one page per run, so this amount of the latest runs is checked together
+    pub window_runs: i64,
+    /// Amount of previous windows to compare with
+    pub baseline_windows: i64,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Health {
+            ratio_increase: 0.3,
+            unknown_characteristics_increase: 2.0,
+            min_items: 10,
+            window_runs: 50,
+            baseline_windows: 5,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Copy, Clone, PartialEq)]
+pub enum ArchiveStore {
+    /// Raw pages are not archived
+    None,
+    Fs,
+    S3,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Archive {
+    pub store: ArchiveStore,
+    /// Root directory of the `fs` store
+    pub dir: String,
+    /// Key prefix in the S3 bucket of the `s3` store
+    pub s3_prefix: String,
+    /// The latest archived pages of an offer which are kept, the older ones are pruned
+    pub keep_pages: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -38,6 +161,14 @@ pub struct Settings {
     pub database: Database,
     pub queue_broker: QueueBroker,
     pub s3: S3,
+    pub images: Images,
+    pub crawlers: Crawlers,
+    pub proxy: Proxy,
+    pub request: Request,
+    pub matching: Matching,
+    pub delisting: Delisting,
+    pub health: Health,
+    pub archive: Archive,
 }
 
 impl Settings {
@@ -52,12 +183,225 @@ impl Settings {
         };
         let s3_settings = S3 {
             bucket: dotenv::var("S3_BUCKET").expect("S3_BUCKET must be set"),
+            region: dotenv::var("S3_REGION")
+                .or_else::<String, _>(|_| Ok(String::from("eu-west-2")))
+                .unwrap(),
+            endpoint: dotenv::var("S3_ENDPOINT")
+                .ok()
+                .filter(|endpoint| !endpoint.is_empty()),
+        };
+        let crawlers_settings = Crawlers {
+            specs_dir: dotenv::var("CRAWLER_SPECS_DIR")
+                .or_else::<String, _>(|_| Ok(String::from("crawlers")))
+                .unwrap(),
+            dictionaries_dir: dotenv::var("CHARACTERISTIC_DICTIONARIES_DIR")
+                .or_else::<String, _>(|_| Ok(String::from("characteristics")))
+                .unwrap(),
         };
 
        Settings {
             database: database_settings,
             queue_broker: Settings::get_amqp_settings(),
             s3: s3_settings,
+            images: Settings::get_images_settings(),
+            crawlers: crawlers_settings,
+            proxy: Settings::get_proxy_settings(),
+            request: Settings::get_request_settings(),
+            matching: Settings::get_matching_settings(),
+            delisting: Settings::get_delisting_settings(),
+            health: Settings::get_health_settings(),
+            archive: Settings::get_archive_settings(),
+        }
+    }
+
+    fn get_images_settings() -> Images {
+        Images {
+            store: match dotenv::var("IMAGE_STORE")
+                .or_else::<String, _>(|_| Ok(String::from("s3")))
+                .unwrap()
+                .as_str()
+            {
+                "s3" => ImageStoreKind::S3,
+                "fs" => ImageStoreKind::Fs,
+                "memory" => ImageStoreKind::Memory,
+                store => panic!("Unknown IMAGE_STORE: {}, expected s3, fs or memory", store),
+            },
+            dir: dotenv::var("IMAGE_DIR")
+                .or_else::<String, _>(|_| Ok(String::from("images")))
+                .unwrap(),
+            max_bytes: dotenv::var("IMAGE_MAX_BYTES")
+                .or_else::<String, _>(|_| Ok(String::from("10485760")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            min_side: dotenv::var("IMAGE_MIN_SIDE")
+                .or_else::<String, _>(|_| Ok(String::from("100")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            max_side: dotenv::var("IMAGE_MAX_SIDE")
+                .or_else::<String, _>(|_| Ok(String::from("10000")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            dedupe_distance: dotenv::var("IMAGE_DEDUPE_DISTANCE")
+                .or_else::<String, _>(|_| Ok(String::from("6")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            download_timeout_seconds: dotenv::var("IMAGE_DOWNLOAD_TIMEOUT_SECONDS")
+                .or_else::<String, _>(|_| Ok(String::from("60")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            stats_interval_seconds: dotenv::var("IMAGE_STATS_INTERVAL_SECONDS")
+                .or_else::<String, _>(|_| Ok(String::from("60")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+        }
+    }
+
+    fn get_archive_settings() -> Archive {
+        Archive {
+            store: match dotenv::var("ARCHIVE_STORE")
+                .or_else::<String, _>(|_| Ok(String::from("fs")))
+                .unwrap()
+                .as_str()
+            {
+                "none" => ArchiveStore::None,
+                "fs" => ArchiveStore::Fs,
+                "s3" => ArchiveStore::S3,
+                store => panic!("Unknown ARCHIVE_STORE: {}, expected none, fs or s3", store),
+            },
+            dir: dotenv::var("ARCHIVE_DIR")
+                .or_else::<String, _>(|_| Ok(String::from("archive")))
+                .unwrap(),
+            s3_prefix: dotenv::var("ARCHIVE_S3_PREFIX")
+                .or_else::<String, _>(|_| Ok(String::from("raw")))
+                .unwrap(),
+            keep_pages: dotenv::var("ARCHIVE_KEEP_PAGES")
+                .or_else::<String, _>(|_| Ok(String::from("3")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+        }
+    }
+
+    fn get_health_settings() -> Health {
+        let default = Health::default();
+
+        Health {
+            ratio_increase: dotenv::var("HEALTH_RATIO_INCREASE")
+                .map_or(default.ratio_increase, |value| value.parse().unwrap()),
+            unknown_characteristics_increase: dotenv::var(
+                "HEALTH_UNKNOWN_CHARACTERISTICS_INCREASE",
+            )
+            .map_or(default.unknown_characteristics_increase, |value| {
+                value.parse().unwrap()
+            }),
+            min_items: dotenv::var("HEALTH_MIN_ITEMS")
+                .map_or(default.min_items, |value| value.parse().unwrap()),
+            window_runs: dotenv::var("HEALTH_WINDOW_RUNS")
+                .map_or(default.window_runs, |value| value.parse().unwrap()),
+            baseline_windows: dotenv::var("HEALTH_BASELINE_WINDOWS")
+                .map_or(default.baseline_windows, |value| value.parse().unwrap()),
+        }
+    }
+
+    fn get_delisting_settings() -> Delisting {
+        Delisting {
+            safety_ratio: dotenv::var("DELISTING_SAFETY_RATIO")
+                .or_else::<String, _>(|_| Ok(String::from("0.5")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+        }
+    }
+
+    fn get_matching_settings() -> Matching {
+        Matching {
+            auto_link_score: dotenv::var("MATCHING_AUTO_LINK_SCORE")
+                .or_else::<String, _>(|_| Ok(String::from("0.9")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            review_score: dotenv::var("MATCHING_REVIEW_SCORE")
+                .or_else::<String, _>(|_| Ok(String::from("0.6")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+        }
+    }
+
+    fn get_request_settings() -> Request {
+        Request {
+            timeout_seconds: dotenv::var("REQUEST_TIMEOUT_SECONDS")
+                .or_else::<String, _>(|_| Ok(String::from("30")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            max_attempts: dotenv::var("REQUEST_MAX_ATTEMPTS")
+                .or_else::<String, _>(|_| Ok(String::from("3")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            backoff_base_milliseconds: dotenv::var("REQUEST_BACKOFF_BASE_MILLISECONDS")
+                .or_else::<String, _>(|_| Ok(String::from("1000")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            max_retry_after_seconds: dotenv::var("REQUEST_MAX_RETRY_AFTER_SECONDS")
+                .or_else::<String, _>(|_| Ok(String::from("60")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            other_hosts_requests_per_second: dotenv::var("REQUEST_OTHER_HOSTS_REQUESTS_PER_SECOND")
+                .or_else::<String, _>(|_| Ok(String::from("5")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            other_hosts_burst: dotenv::var("REQUEST_OTHER_HOSTS_BURST")
+                .or_else::<String, _>(|_| Ok(String::from("10")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+        }
+    }
+
+    fn get_proxy_settings() -> Proxy {
+        Proxy {
+            urls: dotenv::var("PROXY_URLS")
+                .or_else::<String, _>(|_| Ok(String::from("")))
+                .unwrap()
+                .split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(String::from)
+                .collect(),
+            rotation: match dotenv::var("PROXY_ROTATION")
+                .or_else::<String, _>(|_| Ok(String::from("per_request")))
+                .unwrap()
+                .as_str()
+            {
+                "per_source" => ProxyRotation::PerSource,
+                _ => ProxyRotation::PerRequest,
+            },
+            max_failures: dotenv::var("PROXY_MAX_FAILURES")
+                .or_else::<String, _>(|_| Ok(String::from("3")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            eviction_seconds: dotenv::var("PROXY_EVICTION_SECONDS")
+                .or_else::<String, _>(|_| Ok(String::from("300")))
+                .unwrap()
+                .parse()
+                .unwrap(),
+            stats_interval_seconds: dotenv::var("PROXY_STATS_INTERVAL_SECONDS")
+                .or_else::<String, _>(|_| Ok(String::from("60")))
+                .unwrap()
+                .parse()
+                .unwrap(),
         }
     }
 
@@ -0,0 +1,32 @@
+use actix_web::dev::ServiceRequest;
+use actix_web::Error;
+use actix_web_httpauth::extractors::bearer::{BearerAuth, Config};
+use actix_web_httpauth::extractors::AuthenticationError;
+use subtle::ConstantTimeEq;
+
+use crate::Executor;
+use lib::error_reporting;
+use lib::error_reporting::ReportingContext;
+
+/// Admin endpoints are closed while `ADMIN_TOKEN` is not configured
+pub async fn validator(req: ServiceRequest, credentials: BearerAuth) -> Result<ServiceRequest, Error> {
+    let admin_token = dotenv::var("ADMIN_TOKEN").unwrap_or_default();
+
+    // constant time, so the token can not be guessed byte by byte from response times
+    if !admin_token.is_empty()
+        && bool::from(credentials.token().as_bytes().ct_eq(admin_token.as_bytes()))
+    {
+        return Ok(req);
+    }
+
+    let message = format!("admin auth failed for {path}", path = req.path());
+    error_reporting::warning(message.as_str(), &ReportingContext {
+        executor: &Executor::AdminAuth,
+        action: "validator",
+    });
+
+    let config = req
+        .app_data::<Config>()
+        .map_or_else(Default::default, |data| data.as_ref().clone());
+    Err(AuthenticationError::new(config).into())
+}
@@ -1,6 +1,4 @@
-use std::fmt;
-
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
 #[derive(Serialize, Queryable)]
 pub struct Category {
@@ -8,18 +6,3 @@ pub struct Category {
     pub slug: String,
     pub parent_id: Option<i32>,
 }
-
-#[allow(dead_code)]
-#[derive(Debug, Serialize, Deserialize, Copy, Clone)]
-pub enum CategorySlug {
-    SmartHome,
-    Smartphone,
-    Headphones,
-    Watches,
-}
-
-impl fmt::Display for CategorySlug {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self, f)
-    }
-}
\ No newline at end of file
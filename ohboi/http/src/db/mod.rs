@@ -1,6 +1,8 @@
 pub mod category;
 pub mod product;
 pub mod product_characteristic;
+pub mod product_image;
+pub mod product_variant;
 pub mod source;
 pub mod source_product;
 pub mod user;
@@ -7,9 +7,11 @@ use lib::schema::product::dsl::{enabled, id};
 
 use crate::db::product::entity::Product;
 use crate::db::product_characteristic::repository::get_all_characteristics_of_product;
+use crate::db::product_image::repository::get_all_variants_for_product;
 use crate::dto::product::ProductInfo;
 use crate::endpoint::product::ProductParams;
-use crate::util::product::convert_product_prices;
+use crate::db::product_variant::repository::get_all_enabled_for_product;
+use crate::util::product::{convert_product_prices, convert_variant_prices};
 
 pub fn get_product_info(params: &ProductParams) -> Option<ProductInfo> {
     let connection = &db::establish_connection();
@@ -27,6 +29,10 @@ pub fn get_product_info(params: &ProductParams) -> Option<ProductInfo> {
         // TODO no mutation by reference
         convert_product_prices(&mut p, rate);
         let characteristics = get_all_characteristics_of_product(p.id);
+        let mut variants = get_all_enabled_for_product(p.id);
+        for variant in &mut variants {
+            convert_variant_prices(variant, rate);
+        }
 
         ProductInfo {
             id: p.id,
@@ -35,8 +41,10 @@ pub fn get_product_info(params: &ProductParams) -> Option<ProductInfo> {
             lowest_price: p.lowest_price,
             highest_price: p.highest_price,
             images: p.images,
+            image_variants: get_all_variants_for_product(p.id),
             category: p.category,
             characteristics,
+            variants,
         }
     })
 }
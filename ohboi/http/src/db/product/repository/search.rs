@@ -13,8 +13,8 @@ use crate::db::product_characteristic::{
     product_characteristic_enum_value, product_characteristic_float_value,
 };
 use crate::dto::product::{
-    CharacteristicEnumValue, CharacteristicFloatValue, CharacteristicIntValue,
-    CharacteristicStringValue, ProductCharacteristicsMapped,
+    CharacteristicBoolValue, CharacteristicEnumValue, CharacteristicFloatValue,
+    CharacteristicIntValue, CharacteristicStringValue, ProductCharacteristicsMapped,
 };
 use crate::endpoint::product::{ProductFilters, SearchSortKey};
 
@@ -100,6 +100,7 @@ fn filter_by_characteristics(
     let float_values = get_float_values_expression(&chars.float);
     let string_values = get_string_values_expression(&chars.string);
     let enum_values = get_enum_values_expression(&chars.enums);
+    let bool_values = get_bool_values_expression(&chars.bool);
     let mut all_values = vec![];
 
     if let Some(values) = int_values {
@@ -114,6 +115,9 @@ fn filter_by_characteristics(
     if let Some(values) = enum_values {
         all_values.push(values);
     }
+    if let Some(values) = bool_values {
+        all_values.push(values);
+    }
 
     if !all_values.is_empty() {
         joins.push_str(
@@ -156,6 +160,9 @@ fn get_having_for_characteristic_filter(chars: &ProductCharacteristicsMapped) ->
     for char in &chars.string {
         grouped_filters.push(char.characteristic_id);
     }
+    for char in &chars.bool {
+        grouped_filters.push(char.characteristic_id);
+    }
 
     grouped_filters.sort_unstable();
     grouped_filters.dedup();
@@ -233,6 +240,18 @@ fn filter_by_title(
     (joins, filter, group_by, having)
 }
 
+/// Bool values are stored as 1 or 0, products without the characteristic don't match any of them
+fn get_bool_values_expression(values: &[CharacteristicBoolValue]) -> Option<String> {
+    let converted_to_ids: Vec<CharacteristicIntValue> = values
+        .iter()
+        .map(|char| CharacteristicIntValue {
+            characteristic_id: char.characteristic_id,
+            value: i32::from(char.value),
+        })
+        .collect();
+
+    get_id_values_expression(&converted_to_ids)
+}
 fn get_enum_values_expression(values: &[CharacteristicEnumValue]) -> Option<String> {
     if values.is_empty() {
         return None;
@@ -336,8 +355,10 @@ fn group_values_to_string(grouped_values: BTreeMap<i16, Vec<String>>) -> String
 }
 
 mod tests {
-    use crate::db::product::repository::search::get_id_values_expression;
-    use crate::dto::product::CharacteristicIntValue;
+    use crate::db::product::repository::search::{
+        get_bool_values_expression, get_id_values_expression,
+    };
+    use crate::dto::product::{CharacteristicBoolValue, CharacteristicIntValue};
 
     #[test]
     fn it_creates_int_value_expression() {
@@ -359,4 +380,22 @@ mod tests {
             Some("(1, '{2, 3}'::int[]), (4, '{4}'::int[])".to_owned())
         );
     }
+
+    #[test]
+    fn it_creates_bool_value_expression() {
+        assert_eq!(
+            get_bool_values_expression(&[
+                CharacteristicBoolValue {
+                    characteristic_id: 47,
+                    value: true
+                },
+                CharacteristicBoolValue {
+                    characteristic_id: 48,
+                    value: false
+                }
+            ]),
+            Some("(47, '{1}'::int[]), (48, '{0}'::int[])".to_owned())
+        );
+        assert_eq!(get_bool_values_expression(&[]), None);
+    }
 }
@@ -1,6 +1,7 @@
 use strum::IntoEnumIterator;
 use strum::VariantNames;
 
+use lib::dto::characteristic::bool_characteristic::BoolCharacteristic;
 use lib::dto::characteristic::enum_characteristic::EnumCharacteristic;
 use lib::dto::characteristic::float_characteristic::FloatCharacteristic;
 use lib::dto::characteristic::int_characteristic::IntCharacteristic;
@@ -33,6 +34,11 @@ pub fn get_characteristic_by_id(char_id: i16) -> Option<TypedCharacteristic> {
             return Some(en);
         }
     }
+    for item in BoolCharacteristic::iter() {
+        if get_characteristic_id(&TypedCharacteristic::Bool(item)) == char_id {
+            return Some(TypedCharacteristic::Bool(item));
+        }
+    }
 
     None
 }
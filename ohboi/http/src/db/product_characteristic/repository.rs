@@ -14,8 +14,8 @@ use crate::db::product_characteristic::product_characteristic_enum_value::Produc
 use crate::db::product_characteristic::product_characteristic_float_value::ProductCharacteristicFloatValue;
 use crate::db::product_characteristic::product_characteristic_string_value::ProductCharacteristicStringValue;
 use crate::dto::product::{
-    CharacteristicEnumValue, CharacteristicFloatValue, CharacteristicIntValue,
-    CharacteristicStringValue, ProductCharacteristicsMapped,
+    CharacteristicBoolValue, CharacteristicEnumValue, CharacteristicFloatValue,
+    CharacteristicIntValue, CharacteristicStringValue, ProductCharacteristicsMapped,
 };
 
 pub fn get_all_characteristics_of_product(product_id: i32) -> ProductCharacteristicsMapped {
@@ -24,6 +24,7 @@ pub fn get_all_characteristics_of_product(product_id: i32) -> ProductCharacteris
     let mut int_characteristics = vec![];
     let mut string_characteristics = vec![];
     let mut enum_characteristics = vec![];
+    let mut bool_characteristics = vec![];
 
     for product_char in product_characteristics {
         let characteristic = get_characteristic_by_id(product_char.characteristic_id);
@@ -40,6 +41,9 @@ pub fn get_all_characteristics_of_product(product_id: i32) -> ProductCharacteris
             TypedCharacteristic::Enum(_) => {
                 enum_characteristics.push(product_char);
             }
+            TypedCharacteristic::Bool(_) => {
+                bool_characteristics.push(product_char);
+            }
         }
     }
 
@@ -91,12 +95,20 @@ pub fn get_all_characteristics_of_product(product_id: i32) -> ProductCharacteris
                 .clone(),
         })
         .collect();
+    let bool_values = bool_characteristics
+        .into_iter()
+        .map(|v| CharacteristicBoolValue {
+            characteristic_id: v.characteristic_id,
+            value: v.value_id == 1,
+        })
+        .collect();
 
     ProductCharacteristicsMapped {
         int: int_values,
         float: float_values,
         string: string_values,
         enums: enum_values,
+        bool: bool_values,
     }
 }
 
@@ -147,6 +159,11 @@ fn get_product_characteristics(id: i32) -> Vec<ProductCharacteristic> {
 
     product_characteristic::table
         .filter(filter)
+        .select((
+            product_characteristic::product_id,
+            product_characteristic::characteristic_id,
+            product_characteristic::value_id,
+        ))
         .load::<ProductCharacteristic>(connection)
         .expect("Cannot load product characteristics")
 }
@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+/// One size of the product photo in one format, sizes of the same photo share `image_id`
+#[derive(Serialize, Queryable, Debug)]
+pub struct ProductImageVariant {
+    pub image_id: i32,
+    /// `thumbnail`, `medium` or `large`
+    pub size: String,
+    /// `jpeg` or `webp`
+    pub format: String,
+    pub file_path: String,
+    pub width: i32,
+    pub height: i32,
+}
@@ -0,0 +1,24 @@
+use lib::diesel::prelude::*;
+
+use lib::db;
+use lib::schema::{product_image, product_image_variant};
+use crate::db::product_image::entity::ProductImageVariant;
+
+pub fn get_all_variants_for_product(requested_product_id: i32) -> Vec<ProductImageVariant> {
+    let connection = &db::establish_connection();
+
+    product_image_variant::table
+        .inner_join(product_image::table)
+        .filter(product_image::product_id.eq(requested_product_id))
+        .order((product_image::id.asc(), product_image_variant::id.asc()))
+        .select((
+            product_image_variant::image_id,
+            product_image_variant::size,
+            product_image_variant::format,
+            product_image_variant::file_path,
+            product_image_variant::width,
+            product_image_variant::height,
+        ))
+        .load(connection)
+        .expect("Cannot load product images")
+}
@@ -0,0 +1,22 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+#[derive(Serialize, Queryable, Debug)]
+pub struct ProductVariant {
+    pub id: i32,
+    #[serde(skip)]
+    pub product_id: i32,
+    pub color: Option<String>,
+    pub built_in_memory_gb: Option<i32>,
+    pub ram_gb: Option<i32>,
+    pub lowest_price: BigDecimal,
+    pub highest_price: BigDecimal,
+    #[serde(skip)]
+    pub enabled: bool,
+
+    #[serde(skip)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip)]
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,16 @@
+use lib::diesel::prelude::*;
+
+use lib::db;
+use crate::db::product_variant::entity::ProductVariant;
+
+pub fn get_all_enabled_for_product(requested_product_id: i32) -> Vec<ProductVariant> {
+    use lib::schema::product_variant::dsl::{enabled, id, product_id, product_variant};
+
+    let connection = &db::establish_connection();
+
+    product_variant
+        .filter(product_id.eq(requested_product_id).and(enabled.eq(true)))
+        .order(id.asc())
+        .load(connection)
+        .expect("Cannot load product variants")
+}
@@ -17,4 +17,5 @@ pub struct SourceProduct {
 
     #[serde(skip)]
     pub updated_at: NaiveDateTime,
+    pub variant_id: i32,
 }
\ No newline at end of file
@@ -4,6 +4,9 @@ use bigdecimal::BigDecimal;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::db::product_image::entity::ProductImageVariant;
+use crate::db::product_variant::entity::ProductVariant;
+
 #[derive(Serialize, Debug)]
 pub struct ProductInfo {
     pub id: i32,
@@ -12,8 +15,10 @@ pub struct ProductInfo {
     pub lowest_price: BigDecimal,
     pub highest_price: BigDecimal,
     pub images: Option<Vec<String>>,
+    pub image_variants: Vec<ProductImageVariant>,
     pub category: i32,
     pub characteristics: ProductCharacteristicsMapped,
+    pub variants: Vec<ProductVariant>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -22,6 +27,9 @@ pub struct ProductCharacteristicsMapped {
     pub float: Vec<CharacteristicFloatValue>,
     pub string: Vec<CharacteristicStringValue>,
     pub enums: Vec<CharacteristicEnumValue>,
+    /// Filter by `false` matches only the products where it is known to be false
+    #[serde(default)]
+    pub bool: Vec<CharacteristicBoolValue>,
 }
 // TODO generic value
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -44,3 +52,8 @@ pub struct CharacteristicEnumValue {
     pub characteristic_id: i16,
     pub value: String,
 }
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CharacteristicBoolValue {
+    pub characteristic_id: i16,
+    pub value: bool,
+}
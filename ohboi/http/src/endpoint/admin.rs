@@ -0,0 +1,117 @@
+use actix_web::HttpResponse;
+use actix_web_validator::{Json, Query};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use lib::db::repository::crawl_run::get_recent_runs;
+use lib::db::repository::product_admin;
+use lib::db::repository::product_admin::ProductAdminError;
+use lib::db::repository::unknown_characteristic;
+use lib::error_reporting;
+use lib::error_reporting::ReportingContext;
+
+use crate::Executor;
+
+/// Recorded in the product audit for the operations made through the api
+const ADMIN_HTTP_ACTOR: &str = "http";
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn merge_products(params: Json<MergeProductsParams>) -> HttpResponse {
+    let result = product_admin::merge_products(
+        params.target_product_id,
+        params.merged_product_id,
+        ADMIN_HTTP_ACTOR,
+    );
+
+    match result {
+        Ok(()) => HttpResponse::Ok().json(params.target_product_id),
+        Err(e) => error_response(&e, "merge_products"),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct MergeProductsParams {
+    #[validate(range(min = 0, message = "should be bigger than or equal to zero"))]
+    pub target_product_id: i32,
+    #[validate(range(min = 0, message = "should be bigger than or equal to zero"))]
+    pub merged_product_id: i32,
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn split_source_product(params: Json<SplitSourceProductParams>) -> HttpResponse {
+    let result = product_admin::split_source_product(
+        params.source_product_id,
+        &params.title,
+        ADMIN_HTTP_ACTOR,
+    );
+
+    match result {
+        Ok(product_id) => HttpResponse::Ok().json(product_id),
+        Err(e) => error_response(&e, "split_source_product"),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SplitSourceProductParams {
+    #[validate(range(min = 0, message = "should be bigger than or equal to zero"))]
+    pub source_product_id: i32,
+    #[validate(length(min = 1, max = 1000, message = "should have length from 1 to 1000"))]
+    pub title: String,
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn get_crawl_runs(params: Query<CrawlRunsParams>) -> HttpResponse {
+    let runs = get_recent_runs(params.source.as_deref(), params.limit.unwrap_or(20));
+
+    HttpResponse::Ok().json(runs)
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CrawlRunsParams {
+    /// `site_name` of the source
+    #[validate(length(min = 1, max = 1000, message = "should have length from 1 to 1000"))]
+    pub source: Option<String>,
+    #[validate(range(min = 1, max = 1000, message = "should be from 1 to 1000"))]
+    pub limit: Option<i64>,
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn get_unknown_characteristics(params: Query<UnknownCharacteristicsParams>) -> HttpResponse {
+    let characteristics = unknown_characteristic::get_unknown_characteristics(
+        params.source.as_deref(),
+        params.limit.unwrap_or(20),
+    );
+
+    HttpResponse::Ok().json(characteristics)
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UnknownCharacteristicsParams {
+    /// `site_name` of the source
+    #[validate(length(min = 1, max = 1000, message = "should have length from 1 to 1000"))]
+    pub source: Option<String>,
+    #[validate(range(min = 1, max = 1000, message = "should be from 1 to 1000"))]
+    pub limit: Option<i64>,
+}
+
+fn error_response(error: &ProductAdminError, action: &str) -> HttpResponse {
+    match error {
+        ProductAdminError::ProductNotFound(_) | ProductAdminError::SourceProductNotFound(_) => {
+            HttpResponse::NotFound().json(error.to_string())
+        }
+        ProductAdminError::SameProduct(_)
+        | ProductAdminError::OnlyOffer(_)
+        | ProductAdminError::TitleIsTaken(_) => HttpResponse::BadRequest().json(error.to_string()),
+        ProductAdminError::Database(_) => {
+            error_reporting::warning(
+                error.to_string().as_str(),
+                &ReportingContext {
+                    executor: &Executor::Admin,
+                    action,
+                },
+            );
+
+            HttpResponse::InternalServerError().json("Internal error")
+        }
+    }
+}
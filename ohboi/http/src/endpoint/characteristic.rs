@@ -1,20 +1,68 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+
 use actix_web::HttpResponse;
-use serde::Serialize;
+use actix_web_validator::Query;
+use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
+use validator::{Validate, ValidationError};
 
+use lib::db::entity::category::CategorySlug;
 use lib::db::entity::characteristic::Characteristic;
 use lib::my_enum::CharacteristicGroupSlug;
-use lib::util::all_characteristics::get_all_characteristics_dto;
+use lib::util::all_characteristics::{
+    get_all_characteristics_dto, get_category_characteristics_dto,
+};
 
-pub fn get_all_characteristics() -> HttpResponse {
-    let characteristics = get_all_characteristics_dto();
+/// Characteristics of the category only and their groups, when the category is given
+#[allow(clippy::needless_pass_by_value)]
+pub fn get_all_characteristics(params: Query<CharacteristicsParams>) -> HttpResponse {
+    // unknown category is rejected by the validation
+    let category = params
+        .category
+        .as_deref()
+        .and_then(|slug| CategorySlug::from_str(slug).ok());
+    let characteristics = match category {
+        None => get_all_characteristics_dto(),
+        Some(category) => get_category_characteristics_dto(category),
+    };
+    let groups = get_group_order()
+        .into_iter()
+        .filter(|group| {
+            category.is_none()
+                || characteristics
+                    .iter()
+                    .any(|char| char.group_slug == group.group_slug)
+        })
+        .collect();
 
     HttpResponse::Ok().json(AllCharacteristicsResponse {
         characteristics,
-        groups: get_group_order(),
+        groups,
     })
 }
 
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CharacteristicsParams {
+    /// `slug` of the category, e.g. `smartphone`
+    #[validate(
+        length(min = 1, max = 1000, message = "should have length from 1 to 1000"),
+        custom = "validate_category"
+    )]
+    pub category: Option<String>,
+}
+
+fn validate_category(category: &str) -> Result<(), ValidationError> {
+    if CategorySlug::from_str(category).is_ok() {
+        return Ok(());
+    }
+
+    let mut error = ValidationError::new("unknown_category");
+    error.message = Some(Cow::from("should be a slug of a category"));
+
+    Err(error)
+}
+
 fn get_group_order() -> Vec<CharacteristicGroupSlugOrder> {
     let mut groups_order = vec![];
     for group_slug in CharacteristicGroupSlug::iter() {
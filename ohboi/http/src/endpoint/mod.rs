@@ -8,6 +8,7 @@ pub mod source_product;
 pub mod category;
 pub mod source;
 pub mod characteristic;
+pub mod admin;
 
 pub async fn run_server() -> std::io::Result<()> {
     HttpServer::new(|| {
@@ -28,6 +29,14 @@ pub async fn run_server() -> std::io::Result<()> {
             .service(web::resource("/product").route(web::get().to(product::get_product)))
             // TODO return dates
             .service(web::resource("/source_products").route(web::post().to(source_product::get_source_products)))
+            .service(
+                web::scope("/admin")
+                    .wrap(HttpAuthentication::bearer(auth::admin::validator))
+                    .service(web::resource("/products/merge").route(web::post().to(admin::merge_products)))
+                    .service(web::resource("/source_products/split").route(web::post().to(admin::split_source_product)))
+                    .service(web::resource("/crawl_runs").route(web::get().to(admin::get_crawl_runs)))
+                    .service(web::resource("/unknown_characteristics").route(web::get().to(admin::get_unknown_characteristics)))
+            )
             .default_service(
                 web::resource("")
                     .route(web::get().to(p404))
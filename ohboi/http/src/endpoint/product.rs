@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use lib::db::repository::exchange_rate::try_get_exchange_rate_by_code;
+use lib::db::repository::product_redirect::get_redirect_target;
 use crate::db::product::repository::{get_filtered_products, get_product_info};
 use crate::util::product::convert_product_prices;
 use lib::my_enum::CurrencyEnum;
@@ -12,7 +13,15 @@ use crate::dto::product::ProductCharacteristicsMapped;
 // TODO add hostname to the image urls to remove these dependency from fe
 #[allow(clippy::needless_pass_by_value)]
 pub fn get_product(params: Query<ProductParams>) -> HttpResponse {
-    let product = get_product_info(&params);
+    let product = get_product_info(&params).or_else(|| {
+        // merged products stay reachable by their old ids
+        get_redirect_target(params.id).and_then(|target_id| {
+            get_product_info(&ProductParams {
+                id: target_id,
+                currency: params.currency,
+            })
+        })
+    });
     if product.is_none() {
         return HttpResponse::NotFound().json("Not found");
     }
@@ -34,6 +34,8 @@ async fn main() {
 #[derive(Debug)]
 enum Executor {
     GoogleAuth,
+    AdminAuth,
+    Admin,
 }
 
 impl DisplayString for Executor {
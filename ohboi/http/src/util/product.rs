@@ -1,4 +1,5 @@
 use crate::db::product::entity::Product;
+use crate::db::product_variant::entity::ProductVariant;
 use bigdecimal::{BigDecimal, ToPrimitive};
 use lib::service::currency_converter::convert_to_with_rate;
 
@@ -12,3 +13,14 @@ pub fn convert_product_prices(product: &mut Product, rate: f64) {
         rate,
     ));
 }
+
+pub fn convert_variant_prices(variant: &mut ProductVariant, rate: f64) {
+    variant.highest_price = BigDecimal::from(convert_to_with_rate(
+        variant.highest_price.to_f64().unwrap(),
+        rate,
+    ));
+    variant.lowest_price = BigDecimal::from(convert_to_with_rate(
+        variant.lowest_price.to_f64().unwrap(),
+        rate,
+    ));
+}